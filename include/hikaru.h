@@ -0,0 +1,30 @@
+/* C API for hikaru, built with the `ffi` feature. See src/ffi.rs for the details.
+ *
+ * Build the libraries with `cargo build --release --features ffi`, then link against target/release/libhikaru.a or
+ * libhikaru.so (.dylib on macOS). */
+
+#ifndef HIKARU_H
+#define HIKARU_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define HIKARU_OK 0
+#define HIKARU_INVALID_USERNAME 1
+#define HIKARU_USER_NOT_FOUND 2
+#define HIKARU_NETWORK_ERROR 3
+#define HIKARU_OTHER_ERROR 4
+
+/* Downloads every game `user` has played as newline-delimited JSON, or returns NULL if the download failed. The
+ * outcome is written to `error` unless it is NULL. Free the result with hikaru_string_free. */
+char *hikaru_download_json(const char *user, int *error);
+
+/* Frees a string returned by hikaru_download_json. */
+void hikaru_string_free(char *string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,189 @@
+//! A handle on one of a player's monthly archives, for downloading months individually.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT_ENCODING, CONTENT_LENGTH};
+use serde::{Deserialize, Serialize};
+
+use crate::options::archive_month;
+use crate::{Client, DownloadOptions, Error, GameData, Result, Username};
+
+/// One month of a player's games, as listed by `/pub/player/{user}/games/archives`. Archives sort chronologically.
+///
+/// ```rust,no_run
+/// # fn main() -> hikaru::Result<()> {
+/// let client = hikaru::Client::new();
+/// for archive in client.archives("hikaru")? {
+///     let games = archive.games(&client)?;
+///     println!("{}: {} games", archive, games.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub struct ArchiveMonth {
+    pub year: u16,
+    pub month: u8,
+    /// The API URL of the archive, e.g. `https://api.chess.com/pub/player/hikaru/games/2014/01`.
+    pub url: String,
+    /// The player the archive belongs to, as passed to [Client::archives]. Games are seen from their side.
+    pub user: String,
+}
+
+impl ArchiveMonth {
+    /// `None` if the URL doesn't end in a year and month.
+    pub(crate) fn from_url(url: String, user: &str) -> Option<Self> {
+        let (year, month) = archive_month(&url)?;
+        Some(ArchiveMonth { year, month, url, user: user.into() })
+    }
+
+    /// Downloads the month's games, oldest first.
+    pub fn games(&self, client: &Client) -> Result<Vec<GameData>> {
+        self.games_with(client, &DownloadOptions::default())
+    }
+
+    /// Downloads the month's games, filtered by `options` (which also decides what to do about games that can't be read).
+    pub fn games_with(&self, client: &Client, options: &DownloadOptions) -> Result<Vec<GameData>> {
+        let games = client.configured(options).get_month(&self.url)?;
+        options.game_data(&self.url, games, &self.user).into_iter().collect()
+    }
+}
+
+impl Client {
+    /// Downloads one month of the user's games directly, without asking for the list of archives first. Months without
+    /// games come back empty, and an unknown user is [Error::UserNotFound].
+    pub fn games_in_month(&self, user: &str, year: u16, month: u8) -> Result<Vec<GameData>> {
+        let url = self.endpoint(&format!("player/{}/games/{}/{:02}", Username::new(user)?, year, month));
+        let archive = ArchiveMonth { year, month, url, user: user.into() };
+        archive.games(self).map_err(|err| err.for_user(user))
+    }
+
+    /// Downloads one month of the user's games as a single PGN, straight from Chess.com's `/pgn` endpoint, without going
+    /// through the JSON archive.
+    pub fn download_month_pgn(&self, user: &str, year: u16, month: u8) -> Result<String> {
+        let url = self.endpoint(&format!("player/{}/games/{}/{:02}/pgn", Username::new(user)?, year, month));
+        self.get_cached_text(&url).map_err(|err| err.for_user(user))
+    }
+
+    /// Downloads every month of the user's games as PGN and writes them one after the other to `writer`. Returns how
+    /// many months had games.
+    pub fn write_all_pgn<W: Write>(&self, user: &str, mut writer: W) -> Result<usize> {
+        let mut months = 0;
+        for archive in self.archives(user)? {
+            let pgn = self.get_cached_text(&format!("{}/pgn", archive.url))?;
+            if pgn.trim().is_empty() {
+                continue;
+            }
+            writer.write_all(pgn.trim_end().as_bytes())?;
+            writer.write_all(b"\n\n")?;
+            months += 1;
+        }
+        writer.flush()?;
+        Ok(months)
+    }
+
+    /// Like [Client::write_all_pgn], into a file, replacing it if it already exists.
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// let months = hikaru::Client::new().download_pgn_file("hikaru", "hikaru.pgn")?;
+    /// println!("{} months of games", months);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn download_pgn_file(&self, user: &str, path: impl AsRef<Path>) -> Result<usize> {
+        self.write_all_pgn(user, BufWriter::new(File::create(path)?))
+    }
+}
+
+/// Formats as e.g. `2014-01`.
+impl fmt::Display for ArchiveMonth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{:02}", self.year, self.month)
+    }
+}
+
+/// Which archives a download got through, from [Client::games_with_report].
+#[derive(Debug, Default)]
+pub struct DownloadReport {
+    pub succeeded: Vec<ArchiveMonth>,
+    /// Archives that couldn't be downloaded or read, with why. Only ever filled in with
+    /// [DownloadOptions::skip_failed_archives]; otherwise the first failure fails the download.
+    pub failed: Vec<(ArchiveMonth, Error)>,
+    /// Whether the download was [cancelled](DownloadOptions::cancel_on) before it got through every archive.
+    pub cancelled: bool,
+}
+
+impl DownloadReport {
+    /// Whether every archive was downloaded.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty() && !self.cancelled
+    }
+}
+
+/// How big a player's history is, from [Client::archive_summary], for showing an estimate before downloading it all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveSummary {
+    /// Every monthly archive, oldest first.
+    pub archives: Vec<ArchiveMonth>,
+    /// The size of each archive in bytes, in the same order, or `None` where the API didn't say.
+    pub sizes: Vec<Option<u64>>,
+}
+
+impl ArchiveSummary {
+    /// How many months the player has games in.
+    pub fn count(&self) -> usize {
+        self.archives.len()
+    }
+
+    /// The first and last months with games, or `None` for a player who never finished one.
+    pub fn date_range(&self) -> Option<(&ArchiveMonth, &ArchiveMonth)> {
+        Some((self.archives.first()?, self.archives.last()?))
+    }
+
+    /// The approximate size of all the archives in bytes. Archives whose size is unknown are assumed to be as big as the
+    /// average of the others; `None` if no size is known at all.
+    ///
+    /// This is the size of the JSON. Responses are compressed on the way, so far fewer bytes actually cross the network.
+    pub fn estimated_bytes(&self) -> Option<u64> {
+        let known: Vec<u64> = self.sizes.iter().flatten().copied().collect();
+        if known.is_empty() {
+            return None;
+        }
+        let total: u64 = known.iter().sum();
+        let unknown = (self.sizes.len() - known.len()) as u64;
+        Some(total + total / known.len() as u64 * unknown)
+    }
+}
+
+impl Client {
+    /// Lists the user's archives and asks for the size of each with a HEAD request, without downloading any games.
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// let summary = hikaru::Client::new().archive_summary("hikaru")?;
+    /// if let (Some((first, last)), Some(bytes)) = (summary.date_range(), summary.estimated_bytes()) {
+    ///     println!("{} months from {} to {}, about {} MB", summary.count(), first, last, bytes / 1_000_000);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn archive_summary(&self, user: &str) -> Result<ArchiveSummary> {
+        let archives = self.archives(user)?;
+        // reqwest drops `Content-Length` from compressed responses it decompresses, so ask for the plain size
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT_ENCODING, HeaderValue::from_static("identity"));
+        let sizes = archives.iter()
+            .map(|archive| {
+                let response = self.head(&archive.url, headers.clone())?;
+                Ok(response.headers.get(CONTENT_LENGTH)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.parse().ok()))
+            })
+            .collect::<Result<_>>()?;
+        Ok(ArchiveSummary { archives, sizes })
+    }
+}
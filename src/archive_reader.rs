@@ -0,0 +1,211 @@
+//! Reading a monthly archive one game at a time, straight from the response as it downloads, rather than reading the
+//! whole body and building the month as JSON values first. Archives of very active players run to tens of megabytes.
+
+use std::fmt;
+use std::io::{self, BufReader, Read, Write};
+
+use std::marker::PhantomData;
+
+use serde::de::{self, DeserializeOwned, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use serde_json::Value;
+
+use crate::{ArchivedGame, Error, GameData, GameParseError, Games, MonthGames, Result};
+
+/// Visits the archive object, handing its `games` array to [GameList].
+struct Archive<'a, F, J> {
+    archive: &'a str,
+    on_game: F,
+    json: PhantomData<J>,
+}
+
+/// Visits the `games` array, converting each game as soon as it has been read.
+struct GameList<'a, F, J> {
+    archive: &'a str,
+    on_game: &'a mut F,
+    json: PhantomData<J>,
+}
+
+/// What each game's JSON is read into before it becomes an [ArchivedGame]: the text on its own, or a [Value] to keep.
+pub(crate) trait GameJson: DeserializeOwned {
+    fn archived_game(&self) -> serde_json::Result<ArchivedGame>;
+    fn game_url(&self) -> Option<String>;
+}
+
+impl GameJson for Box<RawValue> {
+    fn archived_game(&self) -> serde_json::Result<ArchivedGame> {
+        serde_json::from_str(self.get())
+    }
+
+    fn game_url(&self) -> Option<String> {
+        serde_json::from_str::<GameUrl>(self.get()).ok().and_then(|game| game.url)
+    }
+}
+
+impl GameJson for Value {
+    fn archived_game(&self) -> serde_json::Result<ArchivedGame> {
+        ArchivedGame::deserialize(self)
+    }
+
+    fn game_url(&self) -> Option<String> {
+        Some(self.get("url")?.as_str()?.to_string())
+    }
+}
+
+/// Just enough of a game to say which one couldn't be read.
+#[derive(Deserialize)]
+struct GameUrl {
+    url: Option<String>,
+}
+
+/// Copies everything read through it to `writer`, e.g. to cache a response while it is being parsed.
+pub(crate) struct Tee<R, W> {
+    pub(crate) reader: R,
+    pub(crate) writer: W,
+}
+
+impl<R: Read, W: Write> Read for Tee<R, W> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.reader.read(buf)?;
+        self.writer.write_all(&buf[..read])?;
+        Ok(read)
+    }
+}
+
+impl<'de, F, J> DeserializeSeed<'de> for Archive<'_, F, J>
+where
+    F: FnMut(std::result::Result<ArchivedGame, GameParseError>, J),
+    J: GameJson,
+{
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> std::result::Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, F, J> Visitor<'de> for Archive<'_, F, J>
+where
+    F: FnMut(std::result::Result<ArchivedGame, GameParseError>, J),
+    J: GameJson,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an archive with a list of games")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(mut self, mut map: A) -> std::result::Result<(), A::Error> {
+        let mut found = false;
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "games" {
+                map.next_value_seed(GameList { archive: self.archive, on_game: &mut self.on_game, json: PhantomData::<J> })?;
+                found = true;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        if found { Ok(()) } else { Err(de::Error::missing_field("games")) }
+    }
+}
+
+impl<'de, F, J> DeserializeSeed<'de> for GameList<'_, F, J>
+where
+    F: FnMut(std::result::Result<ArchivedGame, GameParseError>, J),
+    J: GameJson,
+{
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> std::result::Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, F, J> Visitor<'de> for GameList<'_, F, J>
+where
+    F: FnMut(std::result::Result<ArchivedGame, GameParseError>, J),
+    J: GameJson,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a list of games")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<(), A::Error> {
+        let mut index = 0;
+        // Each game's JSON is set aside on its own first, so one that doesn't match is reported without losing the others
+        while let Some(json) = seq.next_element::<J>()? {
+            let game = json.archived_game().map_err(|err| GameParseError {
+                archive: self.archive.into(),
+                index,
+                game_url: json.game_url(),
+                message: err.to_string(),
+            });
+            (self.on_game)(game, json);
+            index += 1;
+        }
+        Ok(())
+    }
+}
+
+impl Games {
+    /// Reads an archive's games one at a time, so a single game that doesn't match doesn't take the rest of the month with
+    /// it. Each game comes with the JSON it was read from, as a `J`.
+    pub(crate) fn read<'de, R, J>(
+        archive: &str,
+        source: R,
+        on_game: impl FnMut(std::result::Result<ArchivedGame, GameParseError>, J),
+    ) -> Result<()>
+    where
+        R: serde_json::de::Read<'de>,
+        J: GameJson,
+    {
+        let mut deserializer = serde_json::Deserializer::new(source);
+        Archive { archive, on_game, json: PhantomData }.deserialize(&mut deserializer)?;
+        deserializer.end()?;
+        Ok(())
+    }
+
+    pub(crate) fn parse(archive: &str, text: &str) -> Result<MonthGames> {
+        let mut games = vec![];
+        Games::read(archive, serde_json::de::StrRead::new(text), |game, _: Box<RawValue>| games.push(game))?;
+        Ok(games)
+    }
+
+    /// Like [Games::parse], but reads the archive as it goes, without holding all of it in memory.
+    pub(crate) fn parse_reader(archive: &str, reader: impl Read) -> Result<MonthGames> {
+        let mut games = vec![];
+        Games::read(archive, serde_json::de::IoRead::new(BufReader::new(reader)), |game, _: Box<RawValue>| games.push(game))?;
+        Ok(games)
+    }
+}
+
+impl GameData {
+    /// Reads a monthly archive (the JSON from `/pub/player/{user}/games/{year}/{month}`, e.g. saved to a file) from
+    /// `user`'s side, handing over each game as soon as it has been read. Games that can't be read come through as
+    /// [Error::GameParse]. Returns how many games there were.
+    ///
+    /// ```rust,no_run
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> hikaru::Result<()> {
+    /// let mut wins = 0;
+    /// hikaru::GameData::read_archive(File::open("hikaru-2024-01.json")?, "hikaru", |game| {
+    ///     if game.map_or(false, |game| game.win == 1.0) {
+    ///         wins += 1;
+    ///     }
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_archive<R: Read>(reader: R, user: &str, mut on_game: impl FnMut(Result<GameData>)) -> Result<usize> {
+        let mut count = 0;
+        Games::read("archive", serde_json::de::IoRead::new(BufReader::new(reader)), |game, _: Box<RawValue>| {
+            count += 1;
+            on_game(game.map(|game| (game, user).into()).map_err(Error::GameParse));
+        })?;
+        Ok(count)
+    }
+}
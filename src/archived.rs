@@ -0,0 +1,152 @@
+//! Games as the monthly archives list them, with both players side by side, for anyone who wants to do their own
+//! flattening rather than take [GameData]'s view from one side.
+
+use serde::{Deserialize, Serialize};
+
+use crate::bughouse::PartnerGame;
+use crate::{Client, Color, DownloadOptions, GameData, GameResult, Progress, Result, Rules, TimeClass, Title, Username};
+
+/// A game exactly as it appears in a monthly archive. Turn it into a [GameData] from either side with
+/// `GameData::from((game, "username"))`.
+///
+/// ```rust,no_run
+/// # fn main() -> hikaru::Result<()> {
+/// for game in hikaru::Client::new().archived_games_in_month("hikaru", 2023, 7)? {
+///     println!("{}: {} vs {}", game.game_url, game.white.username, game.black.username);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ArchivedGame {
+    #[serde(rename="url")]
+    pub game_url: String,
+    /// The full PGN of the game, moves included. Chess.com occasionally leaves it out.
+    pub pgn: Option<String>,
+    pub time_control: String,
+    pub start_time: Option<u32>,
+    pub end_time: u32,
+    pub rated: bool,
+    /// The final position.
+    pub fen: String,
+    pub time_class: TimeClass,
+    pub rules: Rules,
+    /// The URL of the opening on Chess.com.
+    pub eco: Option<String>,
+    pub tournament: Option<String>,
+    #[serde(rename="match")]
+    pub team_match: Option<String>,
+    pub white: ArchivedPlayer,
+    pub black: ArchivedPlayer,
+    pub accuracies: Option<Accuracies>,
+    pub uuid: Option<String>,
+    pub initial_setup: Option<String>,
+    pub tcn: Option<String>,
+    /// The partner's board, for bughouse games.
+    pub partner_game: Option<PartnerGame>,
+}
+
+impl ArchivedGame {
+    /// The player with the `colour` pieces.
+    pub fn player(&self, colour: Color) -> &ArchivedPlayer {
+        match colour {
+            Color::White => &self.white,
+            Color::Black => &self.black,
+        }
+    }
+
+    /// Which side `username` played, ignoring case. `None` if they didn't play this game.
+    pub fn colour_of(&self, username: &str) -> Option<Color> {
+        if username.eq_ignore_ascii_case(&self.white.username) {
+            Some(Color::White)
+        } else if username.eq_ignore_ascii_case(&self.black.username) {
+            Some(Color::Black)
+        } else {
+            None
+        }
+    }
+}
+
+impl GameData {
+    /// The game from each side, White's first, e.g. to train a model on positions from both players' point of view
+    /// without downloading the archive again under the opponent's name.
+    ///
+    /// ```rust,no_run
+    /// use hikaru::GameData;
+    ///
+    /// # fn main() -> hikaru::Result<()> {
+    /// let games: Vec<GameData> = hikaru::Client::new().archived_games("hikaru")?
+    ///     .into_iter()
+    ///     .flat_map(|game| {
+    ///         let (white, black) = GameData::both_perspectives(game);
+    ///         [white, black]
+    ///     })
+    ///     .collect();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn both_perspectives(game: ArchivedGame) -> (GameData, GameData) {
+        let white = game.white.username.clone();
+        let black = game.black.username.clone();
+        (GameData::from((game.clone(), white.as_str())), GameData::from((game, black.as_str())))
+    }
+}
+
+/// Chess.com's accuracy scores (CAPS), only there for games someone has run Game Review on.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct Accuracies {
+    pub white: f32,
+    pub black: f32,
+}
+
+/// One side of an [ArchivedGame].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ArchivedPlayer {
+    pub username: String,
+    pub rating: u32,
+    /// How the game ended for this player, e.g. `Resigned`.
+    pub result: GameResult,
+    /// The API URL of the player's profile.
+    #[serde(rename="@id")]
+    pub id: String,
+    /// The monthly archives leave these out, but other endpoints and sites include them.
+    #[serde(default, deserialize_with="crate::titled::known_title")]
+    pub title: Option<Title>,
+    /// The API URL of the player's country.
+    #[serde(default)]
+    pub country: Option<String>,
+}
+
+impl Client {
+    /// Downloads every game the user has played as an [ArchivedGame], oldest first.
+    pub fn archived_games(&self, user: &str) -> Result<Vec<ArchivedGame>> {
+        self.archived_games_with(user, &DownloadOptions::default())
+    }
+
+    /// Like [Client::games_with], but into [ArchivedGame]s. The options' [filter](DownloadOptions::filter) needs a
+    /// [GameData], so only its date range applies.
+    pub fn archived_games_with(&self, user: &str, options: &DownloadOptions) -> Result<Vec<ArchivedGame>> {
+        let client = self.configured(options);
+        let urls: Vec<String> = client.get_game_month_urls(user)?
+            .into_iter()
+            .filter(|url| options.includes_archive(url))
+            .collect();
+        options.report(Progress::ArchiveListFetched { count: urls.len() });
+
+        let mut games = vec![];
+        for url in urls {
+            let month = client.get_month(&url)?;
+            for game in options.archived_games(&url, month) {
+                games.push(game?);
+            }
+        }
+        Ok(games)
+    }
+
+    /// Like [Client::games_in_month], but into [ArchivedGame]s.
+    pub fn archived_games_in_month(&self, user: &str, year: u16, month: u8) -> Result<Vec<ArchivedGame>> {
+        let url = self.endpoint(&format!("player/{}/games/{}/{:02}", Username::new(user)?, year, month));
+        let games = self.get_month(&url).map_err(|err| err.for_user(user))?;
+        DownloadOptions::default().archived_games(&url, games).into_iter().collect()
+    }
+}
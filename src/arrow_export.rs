@@ -0,0 +1,110 @@
+//! Arrow and Parquet export, enabled with the `arrow` feature.
+//!
+//! Each [GameData] field becomes a column of the same name. Enums are stored as strings, spelled the way they are in JSON,
+//! and the moves as a single space-separated string.
+
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, BooleanArray, Float32Array, Int32Array, StringArray, UInt32Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::ArrowWriter;
+
+use crate::export::variant;
+use crate::{GameData, Result, Title};
+
+/// Puts the games into a single Arrow record batch, one row per game.
+///
+/// ```rust,no_run
+/// # fn main() -> hikaru::Result<()> {
+/// let games = hikaru::GameData::download(vec!["hikaru"])?;
+/// let batch = hikaru::games_to_record_batch(&games)?;
+/// println!("{} games, {} columns", batch.num_rows(), batch.num_columns());
+/// # Ok(())
+/// # }
+/// ```
+pub fn games_to_record_batch(games: &[GameData]) -> Result<RecordBatch> {
+    let strings = |f: fn(&GameData) -> String| -> ArrayRef {
+        Arc::new(games.iter().map(|game| Some(f(game))).collect::<StringArray>())
+    };
+    let optional_strings = |f: fn(&GameData) -> Option<&str>| -> ArrayRef {
+        Arc::new(games.iter().map(f).collect::<StringArray>())
+    };
+    let numbers = |f: fn(&GameData) -> Option<u32>| -> ArrayRef {
+        Arc::new(games.iter().map(f).collect::<UInt32Array>())
+    };
+    let fractions = |f: fn(&GameData) -> Option<f32>| -> ArrayRef {
+        Arc::new(games.iter().map(f).collect::<Float32Array>())
+    };
+
+    let columns: Vec<(&str, DataType, bool, ArrayRef)> = vec![
+        ("game_url", DataType::Utf8, false, strings(|game| game.game_url.clone())),
+        ("time_control", DataType::Utf8, false, strings(|game| game.time_control.clone())),
+        ("start_time", DataType::UInt32, true, numbers(|game| game.start_time)),
+        ("end_time", DataType::UInt32, false, numbers(|game| Some(game.end_time))),
+        ("rated", DataType::Boolean, false, Arc::new(games.iter().map(|game| Some(game.rated)).collect::<BooleanArray>())),
+        ("fen", DataType::Utf8, false, strings(|game| game.fen.clone())),
+        ("time_class", DataType::Utf8, false, strings(|game| variant(&game.time_class))),
+        ("rules", DataType::Utf8, false, strings(|game| variant(&game.rules))),
+        ("eco_game", DataType::Utf8, true, optional_strings(|game| game.eco_game.as_deref())),
+        ("tournament", DataType::Utf8, true, optional_strings(|game| game.tournament.as_deref())),
+        ("match", DataType::Utf8, true, optional_strings(|game| game.team_match.as_deref())),
+        ("white_rating", DataType::UInt32, false, numbers(|game| Some(game.white_rating))),
+        ("white_username", DataType::Utf8, false, strings(|game| game.white_username.clone())),
+        ("black_rating", DataType::UInt32, false, numbers(|game| Some(game.black_rating))),
+        ("black_username", DataType::Utf8, false, strings(|game| game.black_username.clone())),
+        ("white_title", DataType::Utf8, true, optional_strings(|game| game.white_title.as_ref().map(Title::as_str))),
+        ("black_title", DataType::Utf8, true, optional_strings(|game| game.black_title.as_ref().map(Title::as_str))),
+        ("white_country", DataType::Utf8, true, optional_strings(|game| game.white_country.as_deref())),
+        ("black_country", DataType::Utf8, true, optional_strings(|game| game.black_country.as_deref())),
+        ("eco_pgn", DataType::Utf8, false, strings(|game| game.eco_pgn.clone())),
+        ("eco_url", DataType::Utf8, false, strings(|game| game.eco_url.clone())),
+        ("result", DataType::Utf8, false, strings(|game| variant(&game.result))),
+        ("result_win_lose", DataType::Utf8, false, strings(|game| variant(&game.result_win_lose))),
+        ("termination", DataType::Utf8, false, strings(|game| variant(&game.termination))),
+        ("rating", DataType::UInt32, false, numbers(|game| Some(game.rating))),
+        ("date", DataType::Utf8, false, strings(|game| game.date.clone())),
+        ("colour", DataType::Utf8, false, strings(|game| game.colour.clone())),
+        ("win", DataType::Float32, false, fractions(|game| Some(game.win))),
+        ("player_username", DataType::Utf8, false, strings(|game| game.player_username.clone())),
+        ("canonical_username", DataType::Utf8, false, strings(|game| game.canonical_username.clone())),
+        ("pgn", DataType::Utf8, true, optional_strings(|game| game.pgn.as_deref())),
+        ("white_accuracy", DataType::Float32, true, fractions(|game| game.white_accuracy)),
+        ("black_accuracy", DataType::Float32, true, fractions(|game| game.black_accuracy)),
+        ("accuracy", DataType::Float32, true, fractions(|game| game.accuracy)),
+        ("opponent_username", DataType::Utf8, false, strings(|game| game.opponent_username.clone())),
+        ("opponent_rating", DataType::UInt32, false, numbers(|game| Some(game.opponent_rating))),
+        ("rating_diff", DataType::Int32, false, Arc::new(games.iter().map(|game| Some(game.rating_diff)).collect::<Int32Array>())),
+        ("opponent_result", DataType::Utf8, false, strings(|game| variant(&game.opponent_result))),
+        ("uuid", DataType::Utf8, true, optional_strings(|game| game.uuid.as_deref())),
+        ("initial_setup", DataType::Utf8, true, optional_strings(|game| game.initial_setup.as_deref())),
+        ("start_position_number", DataType::UInt32, true, numbers(|game| game.start_position_number.map(u32::from))),
+        ("tcn", DataType::Utf8, true, optional_strings(|game| game.tcn.as_deref())),
+        ("moves", DataType::Utf8, false, strings(|game| game.moves.join(" "))),
+        ("plies", DataType::UInt32, false, numbers(|game| Some(game.plies))),
+        ("move_count", DataType::UInt32, false, numbers(|game| Some(game.move_count))),
+        ("partner_game_url", DataType::Utf8, true, optional_strings(|game| game.bughouse.as_ref().map(|bughouse| bughouse.partner_game_url.as_str()))),
+        ("partner_username", DataType::Utf8, true, optional_strings(|game| game.bughouse.as_ref().map(|bughouse| bughouse.partner_username.as_str()))),
+        ("partner_opponent_username", DataType::Utf8, true, optional_strings(|game| game.bughouse.as_ref().map(|bughouse| bughouse.partner_opponent_username.as_str()))),
+        ("partner_result", DataType::Utf8, true, Arc::new(games.iter().map(|game| game.bughouse.as_ref().map(|bughouse| variant(&bughouse.partner_result))).collect::<StringArray>())),
+    ];
+
+    let (fields, arrays): (Vec<Field>, Vec<ArrayRef>) = columns.into_iter()
+        .map(|(name, data_type, nullable, array)| (Field::new(name, data_type, nullable), array))
+        .unzip();
+    Ok(RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?)
+}
+
+impl GameData {
+    /// Writes the games to a Parquet file, replacing it if it already exists. The columns are those of
+    /// [games_to_record_batch](crate::games_to_record_batch).
+    pub fn write_parquet(games: &[GameData], path: impl AsRef<Path>) -> Result<()> {
+        let batch = games_to_record_batch(games)?;
+        let mut writer = ArrowWriter::try_new(File::create(path)?, batch.schema(), None)?;
+        writer.write(&batch)?;
+        writer.close()?;
+        Ok(())
+    }
+}
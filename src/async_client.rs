@@ -0,0 +1,270 @@
+//! Async version of the downloader, built on reqwest's async client. Enabled with the `async` feature.
+//!
+//! Monthly archives are fetched concurrently, which is a lot faster than the blocking downloader for players with many years of games.
+//! Hikaru doesn't start a runtime of its own, so the futures need to be driven by your executor (e.g. tokio).
+
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future;
+use futures::stream::{self, StreamExt};
+use reqwest::header::HeaderMap;
+use reqwest::{Response, StatusCode};
+
+use crate::cache::Validators;
+use crate::client::rebase;
+use crate::options::Progress;
+#[cfg(feature = "tracing")]
+use crate::throttle::Instant;
+use crate::throttle::{Outcome, RateLimiter, RetryPolicy};
+use crate::trace::event;
+use crate::{CacheConfig, DownloadOptions, Error, GameData, GameUrls, Games, MonthGames, Result, Username};
+
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::sleep;
+
+/// Waits in steps of at most `i32::MAX` milliseconds (about 24 days): gloo panics on waits that don't fit a `u32`, and
+/// browsers fire longer timeouts straight away.
+#[cfg(target_arch = "wasm32")]
+async fn sleep(mut duration: Duration) {
+    let longest = Duration::from_millis(i32::MAX as u64);
+    while duration > longest {
+        gloo_timers::future::sleep(longest).await;
+        duration -= longest;
+    }
+    gloo_timers::future::sleep(duration).await;
+}
+
+/// Number of monthly archives requested at the same time, unless overridden with [`AsyncClient::concurrency`].
+const DEFAULT_CONCURRENCY: usize = 8;
+
+/// An async client for the Chess.com API.
+#[derive(Debug, Clone)]
+pub struct AsyncClient {
+    client: reqwest::Client,
+    base_url: String,
+    cache: Option<CacheConfig>,
+    limiter: Option<Arc<RateLimiter>>,
+    retry: RetryPolicy,
+    concurrency: usize,
+    /// Replaces the reqwest client's timeout, for downloads with their own [timeout](DownloadOptions::timeout).
+    timeout: Option<Duration>,
+}
+
+impl Default for AsyncClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AsyncClient {
+    /// An async client with the default settings. Use [`ClientBuilder::build_async`](crate::ClientBuilder::build_async) to configure it.
+    pub fn new() -> Self {
+        crate::Client::builder().build_async().expect("Couldn't build the HTTP client")
+    }
+
+    pub(crate) fn from_reqwest(
+        client: reqwest::Client,
+        base_url: String,
+        cache: Option<CacheConfig>,
+        limiter: Option<Arc<RateLimiter>>,
+        retry: RetryPolicy,
+    ) -> Self {
+        AsyncClient {
+            client,
+            base_url,
+            cache,
+            limiter,
+            retry,
+            concurrency: DEFAULT_CONCURRENCY,
+            timeout: None,
+        }
+    }
+
+    /// Like [Client::configured](crate::Client::configured): this client with the settings from `options` in place of
+    /// its own. Browsers don't support timeouts, so there the timeout is ignored.
+    fn configured(&self, options: &DownloadOptions) -> Cow<'_, AsyncClient> {
+        if !options.configures_client() {
+            return Cow::Borrowed(self);
+        }
+        let mut client = self.clone();
+        if let Some(cache) = &options.cache {
+            client.cache = Some(cache.clone());
+        }
+        if let Some(max_retries) = options.max_retries {
+            client.retry = RetryPolicy { max_retries };
+        }
+        if let Some(rate_limit) = options.rate_limit {
+            client.limiter = RateLimiter::shared(rate_limit);
+        }
+        if let Some(timeout) = options.timeout {
+            client.timeout = Some(timeout);
+        }
+        Cow::Owned(client)
+    }
+
+    /// Sets how many monthly archives may be downloaded at once. Values below 1 are treated as 1.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    fn endpoint(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url, path)
+    }
+
+    async fn get(&self, url: &str, headers: HeaderMap) -> Result<Response> {
+        let mut attempt = 0;
+        loop {
+            if let Some(limiter) = &self.limiter {
+                let wait = limiter.reserve();
+                if !wait.is_zero() {
+                    event!(debug, url, wait_ms = wait.as_millis() as u64, "waiting for the rate limiter");
+                }
+                sleep(wait).await;
+            }
+            #[cfg(feature = "tracing")]
+            let started = Instant::now();
+            let request = self.client.get(url).headers(headers.clone());
+            #[cfg(not(target_arch = "wasm32"))]
+            let request = match self.timeout {
+                Some(timeout) => request.timeout(timeout),
+                None => request,
+            };
+            let result = request.send().await;
+            event!(debug, url, status = ?result.as_ref().ok().map(Response::status), elapsed_ms = started.elapsed().as_millis() as u64, "request finished");
+            let outcome = match &result {
+                Ok(response) => Outcome::Response(response.status(), response.headers()),
+                Err(err) => Outcome::failed(err),
+            };
+            if let Some(delay) = self.retry.retry_delay(attempt, outcome) {
+                event!(warn, url, attempt = attempt + 1, delay_ms = delay.as_millis() as u64, error = ?result.as_ref().err(), "retrying request");
+                sleep(delay).await;
+                attempt += 1;
+                continue;
+            }
+
+            let response = result?;
+            let status = response.status();
+            if status == StatusCode::TOO_MANY_REQUESTS {
+                event!(warn, url, "rate limited by Chess.com");
+                return Err(Error::RateLimited);
+            }
+            if status.is_client_error() || status.is_server_error() {
+                return Err(Error::Status { url: url.into(), status });
+            }
+            return Ok(response);
+        }
+    }
+
+    async fn get_text(&self, url: &str) -> Result<String> {
+        Ok(self.get(url, HeaderMap::new()).await?.text().await?)
+    }
+
+    /// Goes through the cache when there is one, revalidating stale copies with a conditional request.
+    async fn get_cached_text(&self, url: &str) -> Result<String> {
+        let cache = match &self.cache {
+            Some(cache) => cache,
+            None => return self.get_text(url).await,
+        };
+        if let Some(text) = cache.read(url) {
+            return Ok(text);
+        }
+
+        let cached = cache.read_stale(url);
+        let headers = cached.as_ref().map(|cached| cached.validators.to_headers()).unwrap_or_default();
+        let response = self.get(url, headers).await?;
+        if let (StatusCode::NOT_MODIFIED, Some(cached)) = (response.status(), cached) {
+            cache.touch(url)?;
+            return Ok(cached.body);
+        }
+
+        let validators = Validators::from_headers(response.headers());
+        let text = response.text().await?;
+        cache.write(url, &text, &validators)?;
+        Ok(text)
+    }
+
+    async fn get_game_month_urls(&self, user: &str) -> Result<Vec<String>> {
+        let url = self.endpoint(&format!("player/{}/games/archives", Username::new(user)?));
+
+        let text = self.get_text(&url).await.map_err(|err| err.for_user(user))?;
+
+        let game_urls: GameUrls = serde_json::from_str(&text)?;
+
+        Ok(game_urls.archives.into_iter().map(|url| rebase(&self.base_url, url)).collect())
+    }
+
+    async fn get_games(&self, game_month: &str) -> Result<MonthGames> {
+        let games = async {
+            let games_text = self.get_cached_text(game_month).await?;
+            Games::parse(game_month, &games_text)
+        };
+        #[cfg(feature = "tracing")]
+        let games = tracing::Instrument::instrument(games, tracing::info_span!("archive", url = game_month));
+        games.await
+    }
+
+    /// Downloads all of a user's games. Archives are requested concurrently, but the games come back in chronological order.
+    pub async fn games(&self, user: &str) -> Result<Vec<GameData>> {
+        self.games_with(user, &DownloadOptions::default()).await
+    }
+
+    /// Downloads the user's games from the archives selected by `options`. If the download is
+    /// [cancelled](DownloadOptions::cancel_on), no more archives are started, and the games from those already
+    /// started come back.
+    pub async fn games_with(&self, user: &str, options: &DownloadOptions) -> Result<Vec<GameData>> {
+        let client = self.configured(options);
+        let client = &*client;
+        let mut urls: Vec<String> = client.get_game_month_urls(user).await?
+            .into_iter()
+            .filter(|url| options.includes_archive(url))
+            .collect();
+        if options.newest_first() {
+            urls.reverse();
+        }
+        options.report(Progress::ArchiveListFetched { count: urls.len() });
+
+        let months: Vec<Result<Vec<GameData>>> = stream::iter(urls)
+            .take_while(|_| future::ready(!options.is_cancelled()))
+            .map(|url| async move {
+                let games = client.get_games(&url).await?;
+                options.game_data(&url, games, user).into_iter().collect::<Result<Vec<_>>>()
+            })
+            .buffered(client.concurrency)
+            .collect()
+            .await;
+
+        let mut game_data = vec![];
+        for month in months {
+            let mut month = month?;
+            if options.newest_first() {
+                month.reverse();
+            }
+            game_data.extend(month);
+        }
+        Ok(game_data)
+    }
+}
+
+impl GameData {
+    /// Async equivalent of [`GameData::download`].
+    ///
+    /// ```rust,no_run
+    /// # async fn run() -> hikaru::Result<()> {
+    /// use hikaru::GameData;
+    ///
+    /// let games = GameData::download_async(vec!["hikaru","GMHikaruOnTwitch"]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_async(users: Vec<&str>) -> Result<Vec<GameData>> {
+        let client = AsyncClient::new();
+        let mut game_data = vec![];
+        for user in users {
+            game_data.extend(client.games(user).await?);
+        }
+        Ok(game_data)
+    }
+}
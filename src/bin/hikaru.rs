@@ -0,0 +1,161 @@
+//! The `hikaru` command, for getting at Chess.com games without writing any Rust. Built with the `cli` feature:
+//!
+//! ```text
+//! cargo install hikaru --features cli
+//! hikaru download hikaru --since 2023-01 --format csv > hikaru.csv
+//! hikaru stats hikaru
+//! hikaru sync hikaru magnuscarlsen --db games.sqlite
+//! ```
+
+use std::fs::File;
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use hikaru::{Client, CsvSink, DownloadOptions, GameData, GameStore, NdjsonSink, Summary};
+
+#[derive(Parser)]
+#[command(name = "hikaru", version, about = "Download games from Chess.com")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Downloads a player's games
+    Download {
+        user: String,
+        /// First month to download, e.g. 2023-01
+        #[arg(long, value_parser = parse_month)]
+        since: Option<(u16, u8)>,
+        /// Last month to download, e.g. 2023-12
+        #[arg(long, value_parser = parse_month)]
+        until: Option<(u16, u8)>,
+        #[arg(long, value_enum, default_value_t = Format::Ndjson)]
+        format: Format,
+        /// Where to write the games. Standard output if left out
+        #[arg(long, short)]
+        output: Option<String>,
+    },
+    /// Prints a summary of a player's results
+    Stats { user: String },
+    /// Brings a SQLite database of games up to date, only downloading what it doesn't have yet
+    Sync {
+        #[arg(required = true)]
+        users: Vec<String>,
+        #[arg(long, default_value = "games.sqlite")]
+        db: String,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Csv,
+    Pgn,
+    Ndjson,
+}
+
+/// Reads `YYYY-MM`.
+fn parse_month(text: &str) -> Result<(u16, u8), String> {
+    let invalid = || format!("expected a month like 2023-01, got {}", text);
+    let (year, month) = text.split_once('-').ok_or_else(invalid)?;
+    let year = year.parse().map_err(|_| invalid())?;
+    let month = month.parse().map_err(|_| invalid())?;
+    if !(1..=12).contains(&month) {
+        return Err(invalid());
+    }
+    Ok((year, month))
+}
+
+fn download(user: &str, options: DownloadOptions, format: Format, output: Box<dyn Write>) -> hikaru::Result<usize> {
+    let client = Client::new();
+    match format {
+        Format::Ndjson => client.games_into(user, options, NdjsonSink::new(output)),
+        Format::Csv => client.games_into(user, options, CsvSink::new(output)),
+        Format::Pgn => {
+            let games = client.games_with(user, &options)?;
+            GameData::export_pgn(&games, output)?;
+            Ok(games.len())
+        }
+    }
+}
+
+fn print_stats(user: &str) -> hikaru::Result<()> {
+    let games = Client::new().games(user)?;
+    let summary = Summary::from_games(&games);
+    let record = |record: &hikaru::Record| {
+        format!("{} games, +{} ={} -{} ({:.1}%)", record.games(), record.wins, record.draws, record.losses, record.score() * 100.0)
+    };
+
+    println!("{}", user);
+    println!("  overall:  {}", record(&summary.overall));
+    println!("  white:    {}", record(&summary.white));
+    println!("  black:    {}", record(&summary.black));
+
+    let mut ratings: Vec<_> = summary.ratings.iter().collect();
+    ratings.sort_by_key(|(time_class, _)| String::from((*time_class).clone()));
+    for (time_class, range) in ratings {
+        println!("  {:<9} {} now, {} at best, {} at worst", format!("{}:", String::from(time_class.clone())), range.last, range.highest, range.lowest);
+    }
+
+    let mut openings: Vec<_> = summary.by_opening.iter().collect();
+    openings.sort_by_key(|(_, record)| std::cmp::Reverse(record.games()));
+    if !openings.is_empty() {
+        println!("  most played openings:");
+        for (name, opening) in openings.into_iter().take(5) {
+            println!("    {}: {}", name, record(opening));
+        }
+    }
+    println!("  longest streaks: {} wins, {} losses", summary.longest_win_streak, summary.longest_loss_streak);
+    Ok(())
+}
+
+fn sync(users: &[String], db: &str) -> hikaru::Result<()> {
+    let mut store = GameStore::open(db)?;
+    let client = Client::new();
+    for user in users {
+        let options = match store.last_end_time(user)? {
+            // A second early, for games that ended in the same second as the newest one stored; upserting absorbs the overlap
+            Some(end_time) => DownloadOptions::new().ended_after(end_time.saturating_sub(1)),
+            None => DownloadOptions::new(),
+        };
+        let games = client.games_with(user, &options)?;
+        let written = store.upsert(&games)?;
+        eprintln!("{}: {} games synced", user, written);
+    }
+    Ok(())
+}
+
+fn run(cli: Cli) -> hikaru::Result<()> {
+    match cli.command {
+        Command::Download { user, since, until, format, output } => {
+            let mut options = DownloadOptions::new();
+            if let Some((year, month)) = since {
+                options = options.since(year, month);
+            }
+            if let Some((year, month)) = until {
+                options = options.until(year, month);
+            }
+            let output: Box<dyn Write> = match output {
+                Some(path) => Box::new(File::create(path)?),
+                None => Box::new(io::stdout().lock()),
+            };
+            let count = download(&user, options, format, output)?;
+            eprintln!("{} games", count);
+            Ok(())
+        }
+        Command::Stats { user } => print_stats(&user),
+        Command::Sync { users, db } => sync(&users, &db),
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,844 @@
+//! A compact chess board with legal move generation, FEN and SAN support.
+//!
+//! It is built for replaying games, not for searching them, so it favours simplicity over speed.
+//! Castling is stored by rook square, which makes Chess960 positions work the same way as standard ones.
+
+use std::fmt;
+use std::ops::Not;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, ParsedPgn, Result};
+
+pub(crate) const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+const KNIGHT_STEPS: [(i8, i8); 8] = [(1, 2), (2, 1), (2, -1), (1, -2), (-1, -2), (-2, -1), (-2, 1), (-1, 2)];
+const KING_STEPS: [(i8, i8); 8] = [(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)];
+const ROOK_DIRECTIONS: [(i8, i8); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+const BISHOP_DIRECTIONS: [(i8, i8); 4] = [(1, 1), (-1, 1), (-1, -1), (1, -1)];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Color {
+    #[serde(alias = "white")]
+    White,
+    #[serde(alias = "black")]
+    Black,
+}
+
+impl Not for Color {
+    type Output = Color;
+
+    fn not(self) -> Color {
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    Pawn,
+    Knight,
+    Bishop,
+    Rook,
+    Queen,
+    King,
+}
+
+impl Role {
+    fn from_char(c: char) -> Option<Role> {
+        match c.to_ascii_uppercase() {
+            'P' => Some(Role::Pawn),
+            'N' => Some(Role::Knight),
+            'B' => Some(Role::Bishop),
+            'R' => Some(Role::Rook),
+            'Q' => Some(Role::Queen),
+            'K' => Some(Role::King),
+            _ => None,
+        }
+    }
+
+    /// What the piece is worth in pawns, the usual 1, 3, 3, 5 and 9. Kings count as 0, since they are never traded.
+    pub fn value(self) -> u32 {
+        match self {
+            Role::Pawn => 1,
+            Role::Knight | Role::Bishop => 3,
+            Role::Rook => 5,
+            Role::Queen => 9,
+            Role::King => 0,
+        }
+    }
+
+    /// The uppercase letter for this piece, as used in SAN and for White in FEN.
+    pub fn char(self) -> char {
+        match self {
+            Role::Pawn => 'P',
+            Role::Knight => 'N',
+            Role::Bishop => 'B',
+            Role::Rook => 'R',
+            Role::Queen => 'Q',
+            Role::King => 'K',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Piece {
+    pub color: Color,
+    pub role: Role,
+}
+
+impl Piece {
+    fn from_fen_char(c: char) -> Option<Piece> {
+        let color = if c.is_ascii_uppercase() { Color::White } else { Color::Black };
+        Role::from_char(c).map(|role| Piece { color, role })
+    }
+
+    fn fen_char(self) -> char {
+        match self.color {
+            Color::White => self.role.char(),
+            Color::Black => self.role.char().to_ascii_lowercase(),
+        }
+    }
+}
+
+/// Squares are numbered from a1 = 0 to h8 = 63.
+pub type Square = u8;
+
+fn square(file: i8, rank: i8) -> Option<Square> {
+    if (0..8).contains(&file) && (0..8).contains(&rank) {
+        Some((rank * 8 + file) as Square)
+    } else {
+        None
+    }
+}
+
+fn file_of(square: Square) -> i8 {
+    (square % 8) as i8
+}
+
+fn rank_of(square: Square) -> i8 {
+    (square / 8) as i8
+}
+
+fn offset(from: Square, (file, rank): (i8, i8)) -> Option<Square> {
+    square(file_of(from) + file, rank_of(from) + rank)
+}
+
+/// The square's name, e.g. `e4`.
+pub fn square_name(square: Square) -> String {
+    format!("{}{}", (b'a' + square % 8) as char, square / 8 + 1)
+}
+
+/// Parses a square name like `e4`.
+pub fn parse_square(name: &str) -> Option<Square> {
+    let mut chars = name.chars();
+    let file = chars.next()?;
+    let rank = chars.next()?;
+    if chars.next().is_some() || !('a'..='h').contains(&file) || !('1'..='8').contains(&rank) {
+        return None;
+    }
+    square(file as i8 - 'a' as i8, rank as i8 - '1' as i8)
+}
+
+/// A move on a particular board. Castling is represented as the king's move, with the rook it castles with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Move {
+    pub from: Square,
+    pub to: Square,
+    pub role: Role,
+    pub capture: Option<Role>,
+    pub promotion: Option<Role>,
+    /// The square of the rook involved, if this move castles.
+    pub castle: Option<Square>,
+    pub en_passant: bool,
+}
+
+/// A chess position, including everything a FEN records.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Board {
+    squares: [Option<Piece>; 64],
+    turn: Color,
+    /// Rooks that can still castle: `[white king side, white queen side, black king side, black queen side]`.
+    castling: [Option<Square>; 4],
+    en_passant: Option<Square>,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Board::from_fen(STARTING_FEN).expect("The starting position is valid")
+    }
+}
+
+impl fmt::Debug for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Board({})", self.to_fen())
+    }
+}
+
+impl Board {
+    /// The standard starting position.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads a position from FEN. Castling rights may be given as `KQkq` or as rook files (Shredder/X-FEN style, for Chess960).
+    /// Returns `None` if the FEN is malformed; it doesn't check whether the position could arise in a game.
+    pub fn from_fen(fen: &str) -> Option<Board> {
+        let mut fields = fen.split_whitespace();
+        let placement = fields.next()?;
+        let turn = fields.next().unwrap_or("w");
+        let castling = fields.next().unwrap_or("-");
+        let en_passant = fields.next().unwrap_or("-");
+        let halfmove_clock = fields.next().unwrap_or("0").parse().ok()?;
+        let fullmove_number = fields.next().unwrap_or("1").parse().ok()?;
+
+        let mut squares = [None; 64];
+        let ranks: Vec<&str> = placement.split('/').collect();
+        if ranks.len() != 8 {
+            return None;
+        }
+        for (index, rank_text) in ranks.iter().enumerate() {
+            let rank = 7 - index as i8;
+            let mut file = 0;
+            for c in rank_text.chars() {
+                if let Some(empty) = c.to_digit(10) {
+                    // Checked as it goes, so a long run of digits can't overflow
+                    file += empty as i8;
+                    if file > 8 {
+                        return None;
+                    }
+                } else {
+                    squares[square(file, rank)? as usize] = Some(Piece::from_fen_char(c)?);
+                    file += 1;
+                }
+            }
+            if file != 8 {
+                return None;
+            }
+        }
+
+        let turn = match turn {
+            "w" => Color::White,
+            "b" => Color::Black,
+            _ => return None,
+        };
+
+        let mut board = Board {
+            squares,
+            turn,
+            castling: [None; 4],
+            en_passant: None,
+            halfmove_clock,
+            fullmove_number,
+        };
+
+        for c in castling.chars().filter(|&c| c != '-') {
+            let color = if c.is_ascii_uppercase() { Color::White } else { Color::Black };
+            let king = board.king_square(color)?;
+            let back_rank = rank_of(king);
+            let rook_at = |file: i8| square(file, back_rank).filter(|&sq| board.piece_at(sq) == Some(Piece { color, role: Role::Rook }));
+            let rook = match c.to_ascii_lowercase() {
+                'k' => (file_of(king) + 1..8).rev().find_map(rook_at),
+                'q' => (0..file_of(king)).find_map(rook_at),
+                file @ 'a'..='h' => rook_at(file as i8 - 'a' as i8),
+                _ => return None,
+            };
+            // A castling right without a rook to castle with can't be used, so it is dropped rather than rejected
+            let rook = match rook {
+                Some(rook) => rook,
+                None => continue,
+            };
+            let side = if file_of(rook) > file_of(king) { 0 } else { 1 };
+            board.castling[color as usize * 2 + side] = Some(rook);
+        }
+
+        if en_passant != "-" {
+            board.en_passant = Some(parse_square(en_passant)?);
+        }
+
+        Some(board)
+    }
+
+    pub fn to_fen(&self) -> String {
+        let mut fen = String::new();
+        for rank in (0..8).rev() {
+            let mut empty = 0;
+            for file in 0..8 {
+                match self.squares[(rank * 8 + file) as usize] {
+                    Some(piece) => {
+                        if empty > 0 {
+                            fen.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        fen.push(piece.fen_char());
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                fen.push_str(&empty.to_string());
+            }
+            if rank > 0 {
+                fen.push('/');
+            }
+        }
+
+        fen.push_str(if self.turn == Color::White { " w " } else { " b " });
+
+        let mut castling = String::new();
+        for (index, rook) in self.castling.iter().enumerate() {
+            let rook = match rook {
+                Some(rook) => *rook,
+                None => continue,
+            };
+            let color = if index < 2 { Color::White } else { Color::Black };
+            let king_side = index % 2 == 0;
+            // Use KQkq when the rook is the outermost one on its side, which is always the case in standard chess
+            let outermost = match self.king_square(color) {
+                Some(king) => {
+                    let between: Vec<i8> = if king_side { (file_of(rook) + 1..8).collect() } else { (0..file_of(rook)).collect() };
+                    between.into_iter().all(|file| {
+                        square(file, rank_of(king)).and_then(|sq| self.piece_at(sq)) != Some(Piece { color, role: Role::Rook })
+                    })
+                }
+                None => true,
+            };
+            let c = if outermost {
+                if king_side { 'K' } else { 'Q' }
+            } else {
+                (b'A' + file_of(rook) as u8) as char
+            };
+            castling.push(if color == Color::White { c } else { c.to_ascii_lowercase() });
+        }
+        fen.push_str(if castling.is_empty() { "-" } else { &castling });
+
+        fen.push(' ');
+        fen.push_str(&self.en_passant.map(square_name).unwrap_or_else(|| "-".into()));
+        fen.push_str(&format!(" {} {}", self.halfmove_clock, self.fullmove_number));
+        fen
+    }
+
+    pub fn piece_at(&self, square: Square) -> Option<Piece> {
+        self.squares[square as usize]
+    }
+
+    pub fn turn(&self) -> Color {
+        self.turn
+    }
+
+    /// All pieces on the board, with their squares.
+    pub fn pieces(&self) -> impl Iterator<Item = (Square, Piece)> + '_ {
+        self.squares.iter().enumerate().filter_map(|(sq, piece)| piece.map(|piece| (sq as Square, piece)))
+    }
+
+    pub fn king_square(&self, color: Color) -> Option<Square> {
+        self.pieces()
+            .find(|(_, piece)| *piece == Piece { color, role: Role::King })
+            .map(|(sq, _)| sq)
+    }
+
+    /// Whether the side to move is in check.
+    pub fn is_check(&self) -> bool {
+        self.king_square(self.turn).is_some_and(|king| self.is_attacked(king, !self.turn))
+    }
+
+    /// Squares of the `by` pieces that attack `target`.
+    pub fn attackers(&self, target: Square, by: Color) -> Vec<Square> {
+        let mut attackers = vec![];
+        let is = |sq: Square, roles: &[Role]| self.piece_at(sq).is_some_and(|piece| piece.color == by && roles.contains(&piece.role));
+
+        let pawn_rank = if by == Color::White { -1 } else { 1 };
+        for file in [-1, 1] {
+            if let Some(sq) = offset(target, (file, pawn_rank)).filter(|&sq| is(sq, &[Role::Pawn])) {
+                attackers.push(sq);
+            }
+        }
+        for step in KNIGHT_STEPS {
+            if let Some(sq) = offset(target, step).filter(|&sq| is(sq, &[Role::Knight])) {
+                attackers.push(sq);
+            }
+        }
+        for step in KING_STEPS {
+            if let Some(sq) = offset(target, step).filter(|&sq| is(sq, &[Role::King])) {
+                attackers.push(sq);
+            }
+        }
+        for (directions, roles) in [(ROOK_DIRECTIONS, [Role::Rook, Role::Queen]), (BISHOP_DIRECTIONS, [Role::Bishop, Role::Queen])] {
+            for direction in directions {
+                let mut current = target;
+                while let Some(sq) = offset(current, direction) {
+                    if self.piece_at(sq).is_some() {
+                        if is(sq, &roles) {
+                            attackers.push(sq);
+                        }
+                        break;
+                    }
+                    current = sq;
+                }
+            }
+        }
+        attackers
+    }
+
+    pub fn is_attacked(&self, target: Square, by: Color) -> bool {
+        !self.attackers(target, by).is_empty()
+    }
+
+    fn push_pawn_moves(&self, from: Square, to: Square, capture: Option<Role>, moves: &mut Vec<Move>) {
+        let last_rank = if self.turn == Color::White { 7 } else { 0 };
+        let mv = Move { from, to, role: Role::Pawn, capture, promotion: None, castle: None, en_passant: false };
+        if rank_of(to) == last_rank {
+            for promotion in [Role::Queen, Role::Rook, Role::Bishop, Role::Knight] {
+                moves.push(Move { promotion: Some(promotion), ..mv });
+            }
+        } else {
+            moves.push(mv);
+        }
+    }
+
+    /// Moves that follow the piece movement rules, without checking whether they leave the king in check.
+    fn pseudo_legal_moves(&self) -> Vec<Move> {
+        let mut moves = vec![];
+        let us = self.turn;
+        let target = |sq: Square| match self.piece_at(sq) {
+            None => Some(None),
+            Some(piece) if piece.color != us => Some(Some(piece.role)),
+            Some(_) => None,
+        };
+
+        for (from, piece) in self.pieces().filter(|(_, piece)| piece.color == us) {
+            let step = |to: Square, capture: Option<Role>| Move { from, to, role: piece.role, capture, promotion: None, castle: None, en_passant: false };
+            match piece.role {
+                Role::Pawn => {
+                    let forward = if us == Color::White { 1 } else { -1 };
+                    let start_rank = if us == Color::White { 1 } else { 6 };
+                    if let Some(to) = offset(from, (0, forward)).filter(|&sq| self.piece_at(sq).is_none()) {
+                        self.push_pawn_moves(from, to, None, &mut moves);
+                        if rank_of(from) == start_rank {
+                            if let Some(to) = offset(from, (0, 2 * forward)).filter(|&sq| self.piece_at(sq).is_none()) {
+                                moves.push(step(to, None));
+                            }
+                        }
+                    }
+                    for file in [-1, 1] {
+                        if let Some(to) = offset(from, (file, forward)) {
+                            match self.piece_at(to) {
+                                Some(captured) if captured.color != us => self.push_pawn_moves(from, to, Some(captured.role), &mut moves),
+                                None if self.en_passant == Some(to) => {
+                                    moves.push(Move { en_passant: true, ..step(to, Some(Role::Pawn)) });
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                Role::Knight | Role::King => {
+                    let steps = if piece.role == Role::Knight { KNIGHT_STEPS } else { KING_STEPS };
+                    for to in steps.iter().filter_map(|&s| offset(from, s)) {
+                        if let Some(capture) = target(to) {
+                            moves.push(step(to, capture));
+                        }
+                    }
+                }
+                Role::Bishop | Role::Rook | Role::Queen => {
+                    let directions: Vec<(i8, i8)> = match piece.role {
+                        Role::Bishop => BISHOP_DIRECTIONS.to_vec(),
+                        Role::Rook => ROOK_DIRECTIONS.to_vec(),
+                        _ => [ROOK_DIRECTIONS, BISHOP_DIRECTIONS].concat(),
+                    };
+                    for direction in directions {
+                        let mut current = from;
+                        while let Some(to) = offset(current, direction) {
+                            match target(to) {
+                                Some(capture) => {
+                                    moves.push(step(to, capture));
+                                    if capture.is_some() {
+                                        break;
+                                    }
+                                }
+                                None => break,
+                            }
+                            current = to;
+                        }
+                    }
+                }
+            }
+        }
+
+        moves.extend(self.castling_moves());
+        moves
+    }
+
+    fn castling_moves(&self) -> Vec<Move> {
+        let us = self.turn;
+        let king = match self.king_square(us) {
+            Some(king) => king,
+            None => return vec![],
+        };
+        if self.is_attacked(king, !us) {
+            return vec![];
+        }
+
+        let back_rank = rank_of(king);
+        let mut moves = vec![];
+        for side in 0..2 {
+            let rook = match self.castling[us as usize * 2 + side] {
+                Some(rook) => rook,
+                None => continue,
+            };
+            let (king_file, rook_file) = if side == 0 { (6, 5) } else { (2, 3) };
+            let king_to = square(king_file, back_rank).expect("on the board");
+            let rook_to = square(rook_file, back_rank).expect("on the board");
+
+            let span = |a: Square, b: Square| a.min(b)..=a.max(b);
+            // Everything the king and rook pass over (or land on) must be empty, apart from the king and rook themselves
+            let clear = span(king, king_to).chain(span(rook, rook_to))
+                .all(|sq| sq == king || sq == rook || self.piece_at(sq).is_none());
+            let safe = span(king, king_to).all(|sq| !self.is_attacked(sq, !us));
+            if clear && safe {
+                moves.push(Move { from: king, to: king_to, role: Role::King, capture: None, promotion: None, castle: Some(rook), en_passant: false });
+            }
+        }
+        moves
+    }
+
+    /// Every legal move for the side to move.
+    pub fn legal_moves(&self) -> Vec<Move> {
+        self.pseudo_legal_moves()
+            .into_iter()
+            .filter(|mv| {
+                let after = self.played(mv);
+                after.king_square(self.turn).is_none_or(|king| !after.is_attacked(king, !self.turn))
+            })
+            .collect()
+    }
+
+    /// Plays a move, which is assumed to be legal in this position.
+    pub fn play(&mut self, mv: &Move) {
+        let us = self.turn;
+        let piece = Piece { color: us, role: mv.role };
+
+        self.squares[mv.from as usize] = None;
+        if let Some(rook) = mv.castle {
+            self.squares[rook as usize] = None;
+            let rook_file = if file_of(mv.to) == 6 { 5 } else { 3 };
+            let rook_to = square(rook_file, rank_of(mv.to)).expect("on the board");
+            self.squares[rook_to as usize] = Some(Piece { color: us, role: Role::Rook });
+        }
+        if mv.en_passant {
+            let captured = square(file_of(mv.to), rank_of(mv.from)).expect("on the board");
+            self.squares[captured as usize] = None;
+        }
+        self.squares[mv.to as usize] = Some(mv.promotion.map_or(piece, |role| Piece { color: us, role }));
+
+        // Moving the king or a rook, or capturing a rook, gives up the matching castling rights
+        for index in 0..4 {
+            let color = if index < 2 { Color::White } else { Color::Black };
+            if let Some(rook) = self.castling[index] {
+                let king_moved = mv.role == Role::King && color == us;
+                if king_moved || rook == mv.from || rook == mv.to {
+                    self.castling[index] = None;
+                }
+            }
+        }
+
+        self.en_passant = if mv.role == Role::Pawn && (rank_of(mv.to) - rank_of(mv.from)).abs() == 2 {
+            square(file_of(mv.from), (rank_of(mv.from) + rank_of(mv.to)) / 2)
+        } else {
+            None
+        };
+
+        if mv.role == Role::Pawn || mv.capture.is_some() {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock += 1;
+        }
+        if us == Color::Black {
+            self.fullmove_number += 1;
+        }
+        self.turn = !us;
+    }
+
+    /// The position after playing `mv`.
+    pub fn played(&self, mv: &Move) -> Board {
+        let mut board = self.clone();
+        board.play(mv);
+        board
+    }
+
+    /// Finds the legal move a SAN string refers to, e.g. `Nbd7`, `exd6`, `O-O-O` or `e8=Q+`.
+    pub fn parse_san(&self, san: &str) -> Option<Move> {
+        let san = san.trim_end_matches(['+', '#', '!', '?']);
+        let legal = self.legal_moves();
+
+        if san == "O-O" || san == "O-O-O" || san == "0-0" || san == "0-0-0" {
+            let king_side = san.len() == 3;
+            return legal.into_iter().find(|mv| mv.castle.is_some() && (file_of(mv.to) == 6) == king_side);
+        }
+
+        let mut chars: Vec<char> = san.chars().collect();
+        let role = match chars.first() {
+            Some(&c) if "NBRQK".contains(c) => {
+                chars.remove(0);
+                Role::from_char(c)?
+            }
+            _ => Role::Pawn,
+        };
+
+        let promotion = match chars.iter().rposition(|&c| "NBRQ".contains(c)) {
+            Some(index) => {
+                let promotion = Role::from_char(chars[index]);
+                chars.truncate(index);
+                if chars.last() == Some(&'=') {
+                    chars.pop();
+                }
+                promotion
+            }
+            None => None,
+        };
+
+        chars.retain(|&c| c != 'x' && c != '-' && c != ':');
+        if chars.len() < 2 {
+            return None;
+        }
+        let to = parse_square(&chars[chars.len() - 2..].iter().collect::<String>())?;
+        let disambiguation = &chars[..chars.len() - 2];
+        let from_file = disambiguation.iter().find(|c| c.is_ascii_lowercase()).map(|&c| c as i8 - 'a' as i8);
+        let from_rank = disambiguation.iter().find(|c| c.is_ascii_digit()).map(|&c| c as i8 - '1' as i8);
+
+        let mut candidates = legal.into_iter().filter(|mv| {
+            mv.role == role
+                && mv.to == to
+                && mv.castle.is_none()
+                && mv.promotion == promotion
+                && from_file.is_none_or(|file| file_of(mv.from) == file)
+                && from_rank.is_none_or(|rank| rank_of(mv.from) == rank)
+        });
+        let mv = candidates.next()?;
+        // Ambiguous SAN doesn't identify a move
+        if candidates.next().is_some() {
+            return None;
+        }
+        Some(mv)
+    }
+
+    /// Writes a legal move in SAN, including check and mate markers.
+    pub fn san(&self, mv: &Move) -> String {
+        let mut san = if mv.castle.is_some() {
+            if file_of(mv.to) == 6 { "O-O".to_string() } else { "O-O-O".to_string() }
+        } else {
+            let mut san = String::new();
+            if mv.role == Role::Pawn {
+                if mv.capture.is_some() {
+                    san.push((b'a' + file_of(mv.from) as u8) as char);
+                }
+            } else {
+                san.push(mv.role.char());
+                let others: Vec<Move> = self.legal_moves().into_iter()
+                    .filter(|other| other.role == mv.role && other.to == mv.to && other.from != mv.from && other.castle.is_none())
+                    .collect();
+                if !others.is_empty() {
+                    let file_unique = others.iter().all(|other| file_of(other.from) != file_of(mv.from));
+                    let rank_unique = others.iter().all(|other| rank_of(other.from) != rank_of(mv.from));
+                    let from = square_name(mv.from);
+                    if file_unique {
+                        san.push_str(&from[..1]);
+                    } else if rank_unique {
+                        san.push_str(&from[1..]);
+                    } else {
+                        san.push_str(&from);
+                    }
+                }
+            }
+            if mv.capture.is_some() {
+                san.push('x');
+            }
+            san.push_str(&square_name(mv.to));
+            if let Some(promotion) = mv.promotion {
+                san.push('=');
+                san.push(promotion.char());
+            }
+            san
+        };
+
+        let after = self.played(mv);
+        if after.is_check() {
+            san.push(if after.legal_moves().is_empty() { '#' } else { '+' });
+        }
+        san
+    }
+
+    /// Writes a move in UCI notation, e.g. `e2e4` or `e7e8q`. In `chess960` mode castling is written as the king taking
+    /// its own rook, as engines expect when `UCI_Chess960` is enabled.
+    pub fn uci(&self, mv: &Move, chess960: bool) -> String {
+        let to = match mv.castle {
+            Some(rook) if chess960 => rook,
+            _ => mv.to,
+        };
+        let mut uci = format!("{}{}", square_name(mv.from), square_name(to));
+        if let Some(promotion) = mv.promotion {
+            uci.push(promotion.char().to_ascii_lowercase());
+        }
+        uci
+    }
+
+    /// Whether this position uses castling that standard UCI notation can't express, i.e. it is a Chess960 setup.
+    pub(crate) fn needs_chess960(&self) -> bool {
+        self.castling.iter().enumerate().any(|(index, rook)| {
+            let (king_square, rook_squares) = if index < 2 { (4, [7, 0]) } else { (60, [63, 56]) };
+            let color = if index < 2 { Color::White } else { Color::Black };
+            rook.is_some_and(|rook| self.king_square(color) != Some(king_square) || rook != rook_squares[index % 2])
+        })
+    }
+}
+
+/// The position a game started from: the PGN's `FEN` tag if there is one (Chess960 and custom setups), otherwise the standard start.
+pub(crate) fn starting_position(pgn: &ParsedPgn) -> Result<Board> {
+    match pgn.tags.get("FEN") {
+        Some(fen) => Board::from_fen(fen).ok_or_else(|| Error::InvalidFen(fen.clone())),
+        None => Ok(Board::new()),
+    }
+}
+
+/// Plays through the game's moves, returning each position along with the move played in it.
+pub(crate) fn replay(start: &Board, pgn: &ParsedPgn) -> Result<Vec<(Board, Move)>> {
+    let mut board = start.clone();
+    let mut positions = vec![];
+    for san in &pgn.moves {
+        let mv = board.parse_san(san.as_str()).ok_or_else(|| Error::IllegalMove(san.to_string()))?;
+        let next = board.played(&mv);
+        positions.push((board, mv));
+        board = next;
+    }
+    Ok(positions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+    /// Counts the positions `depth` moves from `board`.
+    fn perft(board: &Board, depth: u32) -> u64 {
+        let moves = board.legal_moves();
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+        moves.iter().map(|mv| perft(&board.played(mv), depth - 1)).sum()
+    }
+
+    fn board(fen: &str) -> Board {
+        Board::from_fen(fen).expect("valid FEN")
+    }
+
+    /// Plays moves given in SAN, checking each one reads back the same.
+    fn play_san(board: &mut Board, moves: &[&str]) {
+        for san in moves {
+            let mv = board.parse_san(san).unwrap_or_else(|| panic!("{} isn't legal in {:?}", san, board));
+            assert_eq!(board.san(&mv), *san);
+            board.play(&mv);
+        }
+    }
+
+    #[test]
+    fn perft_start_position() {
+        let start = Board::new();
+        assert_eq!([1, 2, 3].map(|depth| perft(&start, depth)), [20, 400, 8_902]);
+    }
+
+    #[test]
+    fn perft_kiwipete() {
+        assert_eq!([1, 2, 3].map(|depth| perft(&board(KIWIPETE), depth)), [48, 2_039, 97_862]);
+    }
+
+    #[test]
+    fn perft_en_passant_and_pins() {
+        let position = board("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1");
+        assert_eq!([1, 2, 3, 4].map(|depth| perft(&position, depth)), [14, 191, 2_812, 43_238]);
+    }
+
+    #[test]
+    fn perft_promotions() {
+        let position = board("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1");
+        assert_eq!([1, 2, 3].map(|depth| perft(&position, depth)), [6, 264, 9_467]);
+        let position = board("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8");
+        assert_eq!([1, 2, 3].map(|depth| perft(&position, depth)), [44, 1_486, 62_379]);
+    }
+
+    #[test]
+    fn fen_round_trips() {
+        for fen in [STARTING_FEN, KIWIPETE, "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1"] {
+            assert_eq!(board(fen).to_fen(), fen);
+        }
+    }
+
+    #[test]
+    fn malformed_fens_are_rejected() {
+        for fen in [
+            "99999999999999999/8/8/8/8/8/8/8 w - - 0 1",
+            "9/8/8/8/8/8/8/8 w - - 0 1",
+            "rnbqkbnrp/8/8/8/8/8/8/8 w - - 0 1",
+            "7/8/8/8/8/8/8/8 w - - 0 1",
+            "8/8/8/8/8/8/8 w - - 0 1",
+            "8/8/8/8/8/8/8/8 x - - 0 1",
+            "8/8/8/8/8/8/8/7X w - - 0 1",
+        ] {
+            assert_eq!(Board::from_fen(fen), None, "{}", fen);
+        }
+    }
+
+    #[test]
+    fn castling() {
+        let mut position = board("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+        play_san(&mut position, &["O-O", "O-O-O"]);
+        assert_eq!(position.to_fen(), "2kr3r/8/8/8/8/8/8/R4RK1 w - - 2 2");
+
+        // Not through check, nor out of it
+        let position = board("r3k2r/8/8/8/8/8/5r2/R3K2R w KQkq - 0 1");
+        assert!(position.parse_san("O-O").is_none());
+        assert!(position.parse_san("O-O-O").is_some());
+        let position = board("r3k2r/8/8/8/8/8/4r3/R3K2R w KQkq - 0 1");
+        assert!(position.legal_moves().iter().all(|mv| mv.castle.is_none()));
+    }
+
+    #[test]
+    fn en_passant() {
+        let mut position = Board::new();
+        play_san(&mut position, &["e4", "a6", "e5", "d5", "exd6"]);
+        assert_eq!(position.to_fen(), "rnbqkbnr/1pp1pppp/p2P4/8/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 3");
+    }
+
+    #[test]
+    fn promotion_and_mate() {
+        let mut position = board("7k/4P3/6K1/8/8/8/8/8 w - - 0 1");
+        play_san(&mut position, &["e8=Q#"]);
+        assert!(position.legal_moves().is_empty());
+        assert!(position.is_check());
+    }
+
+    #[test]
+    fn disambiguation() {
+        let position = board("4k3/8/8/8/8/8/4K3/R6R w - - 0 1");
+        let mv = position.parse_san("Rad1").expect("legal");
+        assert_eq!(position.san(&mv), "Rad1");
+        let position = board("4k3/8/8/R7/8/8/8/R3K3 w - - 0 1");
+        let mv = position.parse_san("R1a3").expect("legal");
+        assert_eq!(position.san(&mv), "R1a3");
+    }
+
+    #[test]
+    fn uci() {
+        let position = Board::new();
+        let mv = position.parse_san("Nf3").expect("legal");
+        assert_eq!(position.uci(&mv, false), "g1f3");
+        let position = board("4k3/8/8/8/8/8/8/4K2R w K - 0 1");
+        let mv = position.parse_san("O-O").expect("legal");
+        assert_eq!(position.uci(&mv, false), "e1g1");
+        assert_eq!(position.uci(&mv, true), "e1h1");
+    }
+}
@@ -0,0 +1,59 @@
+//! Bughouse games, which are played by two teams of two on a pair of boards. Each archive entry is one board; the
+//! other one, the player's partner's, is linked from it.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ArchivedPlayer, GameData, GameResult};
+
+/// The other board of a bughouse game, as the archive links it in `partner_game`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PartnerGame {
+    pub url: String,
+    pub white: ArchivedPlayer,
+    pub black: ArchivedPlayer,
+}
+
+/// The partner's board of a bughouse game. The player's own board is the rest of the [GameData].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Bughouse {
+    /// The game the partner played at the same time.
+    pub partner_game_url: String,
+    /// The player's teammate, who has the other colour on their board.
+    pub partner_username: String,
+    pub partner_rating: u32,
+    /// The opponent's teammate, playing the partner.
+    pub partner_opponent_username: String,
+    pub partner_opponent_rating: u32,
+    /// How the partner's board ended for the partner.
+    pub partner_result: GameResult,
+    pub partner_opponent_result: GameResult,
+}
+
+impl Bughouse {
+    /// Teammates sit on opposite colours, so a player who is White has a partner playing Black.
+    pub(crate) fn new(partner_game: PartnerGame, player_is_white: bool) -> Self {
+        let (partner, partner_opponent) = if player_is_white {
+            (partner_game.black, partner_game.white)
+        } else {
+            (partner_game.white, partner_game.black)
+        };
+        Bughouse {
+            partner_game_url: partner_game.url,
+            partner_username: partner.username,
+            partner_rating: partner.rating,
+            partner_opponent_username: partner_opponent.username,
+            partner_opponent_rating: partner_opponent.rating,
+            partner_result: partner.result,
+            partner_opponent_result: partner_opponent.result,
+        }
+    }
+}
+
+impl GameData {
+    /// Whether a bughouse game was decided on the partner's board rather than the player's own.
+    pub fn decided_on_partner_board(&self) -> bool {
+        [&self.result, &self.opponent_result]
+            .iter()
+            .any(|result| matches!(result, GameResult::BugHousePartnerWin | GameResult::BugHousePartnerLose))
+    }
+}
@@ -0,0 +1,278 @@
+//! On-disk cache for monthly archives.
+//!
+//! Archives for past months never change, so a copy downloaded after the month ended is served from disk forever. The
+//! current month is still being played, so its copy (like a past month's copy from before it ended) is only trusted for
+//! [CacheConfig::current_month_ttl]. After that it is revalidated
+//! with the ETag and Last-Modified headers Chess.com sent along with it, so an unchanged month costs a `304 Not Modified`
+//! rather than a full download.
+
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::{HeaderMap, HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use serde::{Deserialize, Serialize};
+
+use crate::date::days_from_civil;
+use crate::options::archive_month;
+use crate::Result;
+
+/// How long the current month's archive is served from the cache, unless overridden.
+const DEFAULT_CURRENT_MONTH_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// The headers needed to ask the API whether a cached response is still current.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct Validators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl Validators {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name| headers.get(name).and_then(|value: &HeaderValue| value.to_str().ok()).map(String::from);
+        Validators {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        }
+    }
+
+    /// `If-None-Match` and `If-Modified-Since` headers for a conditional request.
+    pub(crate) fn to_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        let values = [(IF_NONE_MATCH, &self.etag), (IF_MODIFIED_SINCE, &self.last_modified)];
+        for (name, value) in values {
+            if let Some(value) = value.as_deref().and_then(|value| HeaderValue::from_str(value).ok()) {
+                headers.insert(name, value);
+            }
+        }
+        headers
+    }
+}
+
+/// A cached response that may be out of date.
+pub(crate) struct Cached {
+    pub(crate) body: String,
+    pub(crate) validators: Validators,
+}
+
+/// Where and how to cache monthly archives.
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use hikaru::{CacheConfig, Client};
+///
+/// # fn main() -> hikaru::Result<()> {
+/// let client = Client::builder()
+///     .cache(CacheConfig::new("chess-cache").current_month_ttl(Duration::from_secs(600)))
+///     .build()?;
+///
+/// // The second download only requests the archive list and the current month
+/// let games = client.games("hikaru")?;
+/// let games = client.games("hikaru")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    dir: PathBuf,
+    current_month_ttl: Duration,
+    force_refresh: bool,
+}
+
+impl CacheConfig {
+    /// Caches archives as JSON files under `dir`, one folder per user. The folder is created if needed.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        CacheConfig {
+            dir: dir.into(),
+            current_month_ttl: DEFAULT_CURRENT_MONTH_TTL,
+            force_refresh: false,
+        }
+    }
+
+    /// How long a cached copy of the current (still changing) month is used before it is downloaded again. Defaults to an hour.
+    pub fn current_month_ttl(mut self, ttl: Duration) -> Self {
+        self.current_month_ttl = ttl;
+        self
+    }
+
+    /// Ignores anything already cached and downloads every archive again, overwriting the cached copies.
+    pub fn force_refresh(mut self, force_refresh: bool) -> Self {
+        self.force_refresh = force_refresh;
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Where the archive at `url` is stored, e.g. `<dir>/hikaru/2014-01.json`.
+    pub(crate) fn path(&self, url: &str) -> PathBuf {
+        let user = url.trim_end_matches('/').rsplit('/').nth(3);
+        match (user, archive_month(url)) {
+            (Some(user), Some((year, month))) => self.dir.join(user.to_lowercase()).join(format!("{}-{:02}.json", year, month)),
+            // Anything that isn't a monthly archive gets a flattened version of its URL as the file name
+            _ => {
+                let name: String = url.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+                self.dir.join(format!("{}.json", name))
+            }
+        }
+    }
+
+    fn is_fresh(&self, url: &str, path: &Path) -> bool {
+        let modified = match fs::metadata(path).and_then(|metadata| metadata.modified()) {
+            Ok(modified) => modified,
+            Err(_) => return false,
+        };
+        let modified_at = modified.duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs() as i64);
+        match archive_month(url) {
+            // Copies written while the month was still in progress may be missing games, so only later ones are final
+            Some((year, month)) if modified_at >= month_end(year, month) => true,
+            _ => SystemTime::now().duration_since(modified).is_ok_and(|age| age < self.current_month_ttl),
+        }
+    }
+
+    /// The cached body for `url`, if there is one that is still valid.
+    pub(crate) fn read(&self, url: &str) -> Option<String> {
+        if self.force_refresh {
+            return None;
+        }
+        let path = self.path(url);
+        if !self.is_fresh(url, &path) {
+            return None;
+        }
+        fs::read_to_string(path).ok()
+    }
+
+    /// The cached copy of `url` opened for reading, if there is one that is still valid.
+    pub(crate) fn open(&self, url: &str) -> Option<File> {
+        if self.force_refresh {
+            return None;
+        }
+        let path = self.path(url);
+        if !self.is_fresh(url, &path) {
+            return None;
+        }
+        File::open(path).ok()
+    }
+
+    /// The cached body for `url` whether or not it has expired, so it can be revalidated.
+    pub(crate) fn read_stale(&self, url: &str) -> Option<Cached> {
+        if self.force_refresh {
+            return None;
+        }
+        let path = self.path(url);
+        let body = fs::read_to_string(&path).ok()?;
+        Some(Cached { body, validators: read_validators(&path) })
+    }
+
+    /// The validators of the cached copy of `url` whether or not it has expired, without reading the copy itself.
+    pub(crate) fn stale_validators(&self, url: &str) -> Option<Validators> {
+        if self.force_refresh {
+            return None;
+        }
+        let path = self.path(url);
+        path.is_file().then(|| read_validators(&path))
+    }
+
+    pub(crate) fn write(&self, url: &str, body: &str, validators: &Validators) -> Result<()> {
+        let mut file = self.create(url)?;
+        file.write_all(body.as_bytes())?;
+        self.finish(file, validators)
+    }
+
+    /// Starts writing a new copy of `url`, which replaces the old one once [finished](CacheConfig::finish).
+    pub(crate) fn create(&self, url: &str) -> Result<AtomicFile> {
+        let path = self.path(url);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        AtomicFile::create(path)
+    }
+
+    pub(crate) fn finish(&self, file: AtomicFile, validators: &Validators) -> Result<()> {
+        let path = file.finish()?;
+        let mut meta = AtomicFile::create(validators_path(&path))?;
+        meta.write_all(serde_json::to_string(validators)?.as_bytes())?;
+        meta.finish()?;
+        Ok(())
+    }
+
+    /// Forgets the cached copy of `url`, e.g. because it turned out to be corrupt.
+    pub(crate) fn remove(&self, url: &str) {
+        let path = self.path(url);
+        let _ = fs::remove_file(validators_path(&path));
+        let _ = fs::remove_file(path);
+    }
+
+    /// Marks the cached copy of `url` as fresh again, after the API confirmed it hasn't changed.
+    pub(crate) fn touch(&self, url: &str) -> Result<()> {
+        File::options().append(true).open(self.path(url))?.set_modified(SystemTime::now())?;
+        Ok(())
+    }
+}
+
+/// The Unix timestamp at which a month is over, i.e. midnight UTC on the first of the next month.
+fn month_end(year: u16, month: u8) -> i64 {
+    let (year, month) = if month >= 12 { (year + 1, 1) } else { (year, month + 1) };
+    days_from_civil(year.into(), month, 1) * 24 * 60 * 60
+}
+
+/// A file that is written under a temporary name next to where it belongs, and only renamed into place once it is
+/// complete. An interrupted download (or another process writing the same archive) never leaves half a file behind; a
+/// file that is dropped without being finished is deleted.
+pub(crate) struct AtomicFile {
+    path: PathBuf,
+    temporary: PathBuf,
+    file: Option<BufWriter<File>>,
+}
+
+impl AtomicFile {
+    fn create(path: PathBuf) -> Result<Self> {
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(format!(".{}.tmp", std::process::id()));
+        let temporary = PathBuf::from(temporary);
+        let file = BufWriter::new(File::create(&temporary)?);
+        Ok(AtomicFile { path, temporary, file: Some(file) })
+    }
+
+    /// Moves the file into place, returning where that is.
+    fn finish(mut self) -> Result<PathBuf> {
+        if let Some(file) = self.file.take() {
+            file.into_inner().map_err(|err| err.into_error())?;
+        }
+        fs::rename(&self.temporary, &self.path)?;
+        Ok(std::mem::take(&mut self.path))
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.file.as_mut().map_or(Ok(0), |file| file.write(buf))
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.as_mut().map_or(Ok(()), |file| file.flush())
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        self.file.take();
+        let _ = fs::remove_file(&self.temporary);
+    }
+}
+
+fn read_validators(path: &Path) -> Validators {
+    fs::read_to_string(validators_path(path))
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+/// The ETag and Last-Modified headers are kept next to the archive, e.g. `2014-01.json.meta`.
+fn validators_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".meta");
+    PathBuf::from(name)
+}
@@ -0,0 +1,140 @@
+//! Single games by ID, from the `callback` endpoint the Chess.com website uses to show a game.
+//!
+//! This isn't part of the published API. It may change or disappear without notice, so only what's needed to identify
+//! and replay a game is read from it, and everything but the ID is optional.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::export::tag;
+use crate::{tcn, Client, GameId, Result};
+
+const CALLBACK: &str = "https://www.chess.com/callback";
+
+/// The tags a PGN lists first, in order.
+const SEVEN_TAG_ROSTER: [&str; 7] = ["Event", "Site", "Date", "Round", "White", "Black", "Result"];
+
+#[derive(Debug, Deserialize)]
+struct CallbackResponse {
+    game: CallbackGameJson,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CallbackGameJson {
+    #[serde(default)]
+    pgn_headers: HashMap<String, Value>,
+    move_list: Option<String>,
+    end_time: Option<u32>,
+    is_rated: Option<bool>,
+    result_message: Option<String>,
+}
+
+/// A game fetched on its own with [Client::game_by_id].
+///
+/// ```rust,no_run
+/// use hikaru::{Client, GameId};
+///
+/// # fn main() -> hikaru::Result<()> {
+/// let id = GameId::parse_url("https://www.chess.com/game/live/123456789")?;
+/// let game = Client::new().game_by_id(id)?;
+/// println!("{:?} vs {:?}: {:?}", game.white(), game.black(), game.result());
+/// println!("{}", game.pgn());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallbackGame {
+    pub id: GameId,
+    /// The game's PGN tag pairs, e.g. `White`, `Black`, `Result` and `TimeControl`.
+    pub headers: HashMap<String, String>,
+    /// The moves in Chess.com's compact TCN encoding.
+    pub tcn: Option<String>,
+    /// The moves in SAN, decoded from the TCN. Empty if there isn't one or it can't be read.
+    pub moves: Vec<String>,
+    pub end_time: Option<u32>,
+    pub rated: Option<bool>,
+    /// How the game ended, in words, e.g. `Hikaru won by resignation`.
+    pub result_message: Option<String>,
+}
+
+impl CallbackGame {
+    pub fn white(&self) -> Option<&str> {
+        self.header("White")
+    }
+
+    pub fn black(&self) -> Option<&str> {
+        self.header("Black")
+    }
+
+    /// The result the way PGN writes it, e.g. `1-0`.
+    pub fn result(&self) -> Option<&str> {
+        self.header("Result")
+    }
+
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(String::as_str)
+    }
+
+    /// The game as PGN, with the seven standard tags first and the rest in alphabetical order, e.g. to read with
+    /// [ParsedPgn::parse](crate::ParsedPgn::parse).
+    pub fn pgn(&self) -> String {
+        let mut others: Vec<(&String, &String)> = self.headers.iter()
+            .filter(|(name, _)| !SEVEN_TAG_ROSTER.contains(&name.as_str()))
+            .collect();
+        others.sort();
+        let tags: Vec<String> = SEVEN_TAG_ROSTER.iter()
+            .filter_map(|&name| Some(tag(name, self.header(name)?)))
+            .chain(others.into_iter().map(|(name, value)| tag(name, value)))
+            .collect();
+
+        let black_first = self.header("FEN").is_some_and(|fen| fen.split_whitespace().nth(1) == Some("b"));
+        let mut movetext: Vec<String> = vec![];
+        for (index, san) in self.moves.iter().enumerate() {
+            let ply = index + usize::from(black_first);
+            if ply % 2 == 0 {
+                movetext.push(format!("{}. {}", ply / 2 + 1, san));
+            } else if index == 0 {
+                movetext.push(format!("{}... {}", ply / 2 + 1, san));
+            } else {
+                movetext.push(san.clone());
+            }
+        }
+        movetext.push(self.result().unwrap_or("*").to_string());
+        format!("{}\n\n{}\n", tags.join("\n"), movetext.join(" "))
+    }
+}
+
+impl Client {
+    /// Downloads a single game, without the rest of its month. Useful for games found elsewhere, e.g. linked from a
+    /// tournament page or a report.
+    ///
+    /// This goes through Chess.com's website rather than the published API, so it is unofficial and might stop
+    /// working; see [CallbackGame]. The rate limit and retries apply, but the [base URL](crate::ClientBuilder::base_url)
+    /// and the cache don't. An unknown game is [Error::Status](crate::Error::Status) with a 404.
+    pub fn game_by_id(&self, id: GameId) -> Result<CallbackGame> {
+        let url = format!("{}/{}/game/{}", CALLBACK, id.kind, id.id);
+        let response: CallbackResponse = self.get_json(&url)?;
+        let game = response.game;
+        let headers: HashMap<String, String> = game.pgn_headers.into_iter()
+            .map(|(name, value)| match value {
+                Value::String(text) => (name, text),
+                value => (name, value.to_string()),
+            })
+            .collect();
+        let moves = game.move_list.as_deref()
+            .and_then(|tcn| tcn::tcn_to_san(tcn, headers.get("FEN").map(String::as_str)).ok())
+            .unwrap_or_default();
+        Ok(CallbackGame {
+            id,
+            headers,
+            tcn: game.move_list,
+            moves,
+            end_time: game.end_time,
+            rated: game.is_rated,
+            result_message: game.result_message,
+        })
+    }
+}
@@ -0,0 +1,45 @@
+//! Stopping a download early from another thread, e.g. when a GUI's Cancel button is pressed.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Cancels the downloads it is passed to with [DownloadOptions::cancel_on](crate::DownloadOptions::cancel_on). Clones
+/// share the same flag, so keep one and hand the other to the download.
+///
+/// Downloads check the token between monthly archives. Once it is cancelled, a download stops before the next archive
+/// and returns the games it already has; a request in flight is left to finish, or to hit the
+/// [timeout](crate::DownloadOptions::timeout).
+///
+/// ```rust,no_run
+/// use std::thread;
+/// use hikaru::{CancellationToken, Client, DownloadOptions};
+///
+/// # fn main() -> hikaru::Result<()> {
+/// let token = CancellationToken::new();
+/// let options = DownloadOptions::new().cancel_on(token.clone());
+/// let download = thread::spawn(move || Client::new().games_with_report("hikaru", &options));
+///
+/// // The user changed their mind
+/// token.cancel();
+/// let (games, report) = download.join().unwrap()?;
+/// println!("{} games before cancelling, cancelled: {}", games.len(), report.cancelled);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stops the downloads using this token, or any that start with it later.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
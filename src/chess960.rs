@@ -0,0 +1,38 @@
+//! Chess960's numbered starting positions.
+
+/// Where the knights stand among the five squares left after the bishops and queen, in the standard numbering's order.
+const KNIGHTS: [(usize, usize); 10] = [(0, 1), (0, 2), (0, 3), (0, 4), (1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)];
+
+/// The starting position's number (0 to 959) in the standard Scharnagl numbering, where the usual starting position is
+/// 518. `None` if the FEN's back ranks aren't a Chess960 starting position.
+pub(crate) fn start_position_number(fen: &str) -> Option<u16> {
+    let placement = fen.split_whitespace().next()?;
+    let ranks: Vec<&str> = placement.split('/').collect();
+    let (black, white) = (*ranks.first()?, *ranks.last()?);
+    // Checked as ASCII so that the 8 bytes are 8 squares
+    if ranks.len() != 8 || !white.is_ascii() || white.len() != 8 || black != white.to_lowercase() {
+        return None;
+    }
+    let pieces: Vec<char> = white.chars().collect();
+
+    let bishops: Vec<usize> = (0..8).filter(|&file| pieces[file] == 'B').collect();
+    let (dark, light) = match bishops[..] {
+        [a, b] if a % 2 == 0 && b % 2 == 1 => (a, b),
+        [a, b] if a % 2 == 1 && b % 2 == 0 => (b, a),
+        _ => return None,
+    };
+    let mut rest: Vec<(usize, char)> = pieces.iter().copied().enumerate().filter(|(_, piece)| *piece != 'B').collect();
+    let queen = rest.iter().position(|(_, piece)| *piece == 'Q')?;
+    rest.remove(queen);
+    let knights: Vec<usize> = (0..rest.len()).filter(|&index| rest[index].1 == 'N').collect();
+    let knights = match knights[..] {
+        [a, b] => KNIGHTS.iter().position(|&pair| pair == (a, b))?,
+        _ => return None,
+    };
+    let others: String = rest.iter().map(|(_, piece)| *piece).filter(|&piece| piece != 'N').collect();
+    if others != "RKR" {
+        return None;
+    }
+    let number = ((knights * 6 + queen) * 4 + dark / 2) * 4 + light / 2;
+    Some(number as u16)
+}
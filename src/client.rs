@@ -0,0 +1,729 @@
+//! The [Client] owns the HTTP client used to talk to the Chess.com API, so connections are reused between requests and
+//! headers, timeouts and proxies only need to be configured once.
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::Read;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+#[cfg(any(not(target_arch = "wasm32"), feature = "async"))]
+use reqwest::header::{HeaderValue, USER_AGENT};
+use reqwest::StatusCode;
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::Proxy;
+use serde::de::DeserializeOwned;
+
+use crate::archive_reader::Tee;
+use crate::cache::Validators;
+use crate::date::{self, year_month};
+use crate::options::Progress;
+use crate::throttle::{Outcome, RateLimiter, RetryPolicy, DEFAULT_MAX_RETRIES};
+use crate::trace::event;
+use crate::transport::Transport;
+#[cfg(feature = "tracing")]
+use crate::throttle::Instant;
+use crate::{dedupe_games, ArchiveMonth, CacheConfig, DownloadOptions, DownloadReport, Error, GameData, GameIter, GameUrls, Games, HttpResponse, HttpTransport, MonthGames,
+    Result, StreamingResponse, Username};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::ReqwestTransport;
+
+/// Sent when no User-Agent is configured. Chess.com asks API users to identify themselves, so please set your own with contact info.
+#[cfg(any(not(target_arch = "wasm32"), feature = "async"))]
+const DEFAULT_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"), " (+https://github.com/elibenporat/hikaru)");
+
+/// Where the API lives, unless changed with [ClientBuilder::base_url].
+pub(crate) const DEFAULT_BASE_URL: &str = "https://api.chess.com/pub";
+
+/// Points a URL the API handed out (e.g. a monthly archive) at `base_url`, so a mirror's own listings lead back to it.
+pub(crate) fn rebase(base_url: &str, url: String) -> String {
+    match url.strip_prefix(DEFAULT_BASE_URL) {
+        Some(path) if base_url != DEFAULT_BASE_URL => format!("{}{}", base_url, path),
+        _ => url,
+    }
+}
+
+/// Checks an ID (e.g. a club's) before it goes into an endpoint's path: letters, digits, hyphens and underscores only,
+/// so it can't lead to some other endpoint.
+pub(crate) fn path_id(id: &str) -> Result<&str> {
+    let valid = !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(id)
+    } else {
+        Err(Error::InvalidId(id.into()))
+    }
+}
+
+/// A blocking client for the Chess.com API.
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use hikaru::Client;
+///
+/// # fn main() -> hikaru::Result<()> {
+/// let client = Client::builder()
+///     .user_agent("my-chess-app/1.0 (me@example.com)")
+///     .timeout(Duration::from_secs(30))
+///     .build()?;
+///
+/// let games = client.games("hikaru")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Client {
+    transport: Transport,
+    base_url: String,
+    cache: Option<CacheConfig>,
+    limiter: Option<Arc<RateLimiter>>,
+    retry: RetryPolicy,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Client {
+    /// A client with the default settings. Panics if the TLS backend can't be initialized, use [Client::builder] to handle that case.
+    pub fn new() -> Self {
+        Self::builder().build().expect("Couldn't build the HTTP client")
+    }
+
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::default()
+    }
+
+    /// This client with the [cache](DownloadOptions::cache), [retries](DownloadOptions::max_retries),
+    /// [rate limit](DownloadOptions::rate_limit) and [timeout](DownloadOptions::timeout) from `options` in place of its
+    /// own, where they are set.
+    pub(crate) fn configured(&self, options: &DownloadOptions) -> Cow<'_, Client> {
+        if !options.configures_client() {
+            return Cow::Borrowed(self);
+        }
+        let mut client = self.clone();
+        if let Some(cache) = &options.cache {
+            client.cache = Some(cache.clone());
+        }
+        if let Some(max_retries) = options.max_retries {
+            client.retry = RetryPolicy { max_retries };
+        }
+        if let Some(rate_limit) = options.rate_limit {
+            client.limiter = RateLimiter::shared(rate_limit);
+        }
+        if let Some(timeout) = options.timeout {
+            client.transport = client.transport.with_timeout(timeout);
+        }
+        Cow::Owned(client)
+    }
+
+    /// The URL of an API endpoint, e.g. `player/hikaru` under the [base URL](ClientBuilder::base_url).
+    pub(crate) fn endpoint(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url, path)
+    }
+
+    /// A URL the API handed out (e.g. a game's tournament), pointed at the [base URL](ClientBuilder::base_url) like the
+    /// archives are.
+    pub(crate) fn rebased(&self, url: &str) -> String {
+        rebase(&self.base_url, url.to_string())
+    }
+
+    /// Sends a GET request, waiting for the rate limiter and retrying temporary failures. Rate limiting and error statuses
+    /// that are still there after the last retry become errors.
+    pub(crate) fn get(&self, url: &str, headers: HeaderMap) -> Result<HttpResponse> {
+        self.send(url, headers, |transport, url, headers| transport.get(url, headers))
+    }
+
+    /// Like [Client::get], but a HEAD request: only the headers come back.
+    pub(crate) fn head(&self, url: &str, headers: HeaderMap) -> Result<HttpResponse> {
+        self.send(url, headers, |transport, url, headers| transport.head(url, headers))
+    }
+
+    /// Like [Client::get], but the body is left to be read as it arrives.
+    pub(crate) fn get_streaming(&self, url: &str, headers: HeaderMap) -> Result<StreamingResponse> {
+        self.send(url, headers, |transport, url, headers| transport.get_streaming(url, headers))
+    }
+
+    fn send<R: Response>(&self, url: &str, headers: HeaderMap, request: impl Fn(&dyn HttpTransport, &str, &HeaderMap) -> Result<R>) -> Result<R> {
+        let mut attempt = 0;
+        loop {
+            if let Some(limiter) = &self.limiter {
+                let wait = limiter.reserve();
+                if !wait.is_zero() {
+                    event!(debug, url, wait_ms = wait.as_millis() as u64, "waiting for the rate limiter");
+                }
+                thread::sleep(wait);
+            }
+            #[cfg(feature = "tracing")]
+            let started = Instant::now();
+            let result = request(self.transport.http(), url, &headers);
+            event!(debug, url, status = ?result.as_ref().ok().map(Response::status), elapsed_ms = started.elapsed().as_millis() as u64, "request finished");
+            let outcome = match &result {
+                Ok(response) => Outcome::Response(response.status(), response.headers()),
+                Err(Error::Network(err)) => Outcome::failed(err),
+                Err(_) => Outcome::Failed { transient: false },
+            };
+            if let Some(delay) = self.retry.retry_delay(attempt, outcome) {
+                event!(warn, url, attempt = attempt + 1, delay_ms = delay.as_millis() as u64, error = ?result.as_ref().err(), "retrying request");
+                thread::sleep(delay);
+                attempt += 1;
+                continue;
+            }
+
+            let response = result?;
+            let status = response.status();
+            if status == StatusCode::TOO_MANY_REQUESTS {
+                event!(warn, url, "rate limited by Chess.com");
+                return Err(Error::RateLimited);
+            }
+            // A 304 is only asked for by the cache, which handles it
+            if status.is_client_error() || status.is_server_error() {
+                return Err(Error::Status { url: url.into(), status });
+            }
+            return Ok(response);
+        }
+    }
+
+    pub(crate) fn get_text(&self, url: &str) -> Result<String> {
+        Ok(self.get(url, HeaderMap::new())?.body)
+    }
+
+    /// Fetches and parses one of the API's JSON documents.
+    pub(crate) fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T> {
+        Ok(serde_json::from_str(&self.get_text(url)?)?)
+    }
+
+    /// Like `get_text`, but goes through the cache when there is one. Stale copies are revalidated with a conditional request.
+    pub(crate) fn get_cached_text(&self, url: &str) -> Result<String> {
+        let cache = match &self.cache {
+            Some(cache) => cache,
+            None => return self.get_text(url),
+        };
+        if let Some(text) = cache.read(url) {
+            return Ok(text);
+        }
+
+        let cached = cache.read_stale(url);
+        let headers = cached.as_ref().map(|cached| cached.validators.to_headers()).unwrap_or_default();
+        let response = self.get(url, headers)?;
+        if let (StatusCode::NOT_MODIFIED, Some(cached)) = (response.status, cached) {
+            cache.touch(url)?;
+            return Ok(cached.body);
+        }
+
+        let validators = Validators::from_headers(&response.headers);
+        cache.write(url, &response.body, &validators)?;
+        Ok(response.body)
+    }
+
+    pub(crate) fn get_game_month_urls(&self, user: &str) -> Result<Vec<String>> {
+        let url = self.endpoint(&format!("player/{}/games/archives", Username::new(user)?));
+
+        let text = self.get_text(&url).map_err(|err| err.for_user(user))?;
+
+        let game_urls: GameUrls = serde_json::from_str(&text)?;
+
+        Ok(game_urls.archives.into_iter().map(|url| rebase(&self.base_url, url)).collect())
+    }
+
+    /// Downloads and parses a monthly archive, reading the games as the response arrives. A body that isn't JSON at all (an
+    /// HTML error page, or a response cut off halfway) is thrown out of the cache and downloaded once more before giving up.
+    pub(crate) fn get_month(&self, game_month: &str) -> Result<MonthGames> {
+        self.get_month_with(game_month, |url, reader| Games::parse_reader(url, reader))
+    }
+
+    /// Like `get_month`, reading the archive with `parse`.
+    pub(crate) fn get_month_with<T>(&self, game_month: &str, parse: impl Fn(&str, &mut dyn Read) -> Result<T>) -> Result<T> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("archive", url = game_month).entered();
+        match self.read_month(game_month, &parse) {
+            Err(Error::Parse(_)) => {
+                event!(warn, "archive isn't valid JSON, downloading it again");
+                if let Some(cache) = &self.cache {
+                    cache.remove(game_month);
+                }
+                self.read_month(game_month, &parse)
+            }
+            result => result,
+        }
+    }
+
+    /// Parses a monthly archive from the cache, or from the response while copying it into the cache. Stale copies are
+    /// revalidated with a conditional request, as in `get_cached_text`.
+    fn read_month<T>(&self, url: &str, parse: &impl Fn(&str, &mut dyn Read) -> Result<T>) -> Result<T> {
+        let cache = match &self.cache {
+            Some(cache) => cache,
+            None => return parse(url, &mut self.get_streaming(url, HeaderMap::new())?.body),
+        };
+        if let Some(mut file) = cache.open(url) {
+            return parse(url, &mut file);
+        }
+
+        let validators = cache.stale_validators(url);
+        let headers = validators.as_ref().map(Validators::to_headers).unwrap_or_default();
+        let response = self.get_streaming(url, headers)?;
+        if response.status == StatusCode::NOT_MODIFIED && validators.is_some() {
+            cache.touch(url)?;
+            return parse(url, &mut File::open(cache.path(url))?);
+        }
+
+        // The new copy only replaces the old one once the whole archive has been read
+        let validators = Validators::from_headers(&response.headers);
+        let mut tee = Tee { reader: response.body, writer: cache.create(url)? };
+        let games = parse(url, &mut tee)?;
+        cache.finish(tee.writer, &validators)?;
+        Ok(games)
+    }
+
+    /// Lists the months the user played in, oldest first. Each can be downloaded on its own with [ArchiveMonth::games].
+    pub fn archives(&self, user: &str) -> Result<Vec<ArchiveMonth>> {
+        Ok(self.get_game_month_urls(user)?
+            .into_iter()
+            .filter_map(|url| ArchiveMonth::from_url(url, user))
+            .collect())
+    }
+
+    /// Downloads all of a user's games, oldest first.
+    pub fn games(&self, user: &str) -> Result<Vec<GameData>> {
+        self.games_with(user, &DownloadOptions::default())
+    }
+
+    /// Downloads the user's games from the archives selected by `options`, oldest first unless its
+    /// [order](DownloadOptions::order) says otherwise.
+    /// Archives outside the requested range are never requested.
+    pub fn games_with(&self, user: &str, options: &DownloadOptions) -> Result<Vec<GameData>> {
+        Ok(self.games_with_report(user, options)?.0)
+    }
+
+    /// Like [Client::games_with], but also reports which archives were downloaded. With
+    /// [DownloadOptions::skip_failed_archives], archives that fail are listed there instead of failing the download.
+    ///
+    /// ```rust,no_run
+    /// use hikaru::{Client, DownloadOptions};
+    ///
+    /// # fn main() -> hikaru::Result<()> {
+    /// let options = DownloadOptions::new().skip_failed_archives(true);
+    /// let (games, report) = Client::new().games_with_report("hikaru", &options)?;
+    /// for (archive, err) in &report.failed {
+    ///     eprintln!("couldn't download {}: {}", archive, err);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// If the month changes while the download is running, the newest archive is checked again at the end (see
+    /// [Client::refresh_latest]), so games finished around midnight at the end of the month aren't missed.
+    pub fn games_with_report(&self, user: &str, options: &DownloadOptions) -> Result<(Vec<GameData>, DownloadReport)> {
+        self.download(user, options, &Full)
+    }
+
+    /// Downloads the archives selected by `options` the way `download` reads them, with everything the options say about
+    /// order, concurrency, cancellation and failed archives, and checks the newest archive again if the month changed
+    /// meanwhile.
+    pub(crate) fn download<D: Download>(&self, user: &str, options: &DownloadOptions, download: &D) -> Result<(Vec<D::Game>, DownloadReport)> {
+        let client = self.configured(options);
+        let started = year_month(date::now());
+        let (mut games, mut report) = client.download_archives(user, options, download)?;
+        if year_month(date::now()) != started && !report.cancelled {
+            let refreshed = client.refresh_latest_with(user, &mut games, options, download)?;
+            report.succeeded.extend(refreshed.succeeded);
+            report.failed.extend(refreshed.failed);
+        }
+        Ok((games, report))
+    }
+
+    /// Downloads the games the user has finished since the newest one in `games`, and adds them to the end. Returns how
+    /// many were added.
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// let client = hikaru::Client::new();
+    /// let mut games = client.games("hikaru")?;
+    ///
+    /// // Later on
+    /// let added = client.refresh_latest("hikaru", &mut games)?;
+    /// println!("{} new games", added);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn refresh_latest(&self, user: &str, games: &mut Vec<GameData>) -> Result<usize> {
+        let before = games.len();
+        self.refresh_latest_with(user, games, &DownloadOptions::default(), &Full)?;
+        Ok(games.len() - before)
+    }
+
+    fn refresh_latest_with<D: Download>(
+        &self,
+        user: &str,
+        games: &mut Vec<D::Game>,
+        options: &DownloadOptions,
+        download: &D,
+    ) -> Result<DownloadReport> {
+        // A second early, for games that ended in the same second as the newest one. Those already there are skipped.
+        let after = games.iter().map(Downloaded::end_time).max().map(|latest| latest.saturating_sub(1));
+        let options = match after {
+            Some(after) => options.clone().ended_after(after),
+            None => options.clone(),
+        };
+        let (latest, report) = self.download_archives(user, &options, download)?;
+        let known: HashSet<String> = games.iter()
+            .filter(|game| after.is_some_and(|after| game.end_time() > after))
+            .map(|game| game.game_url().to_string())
+            .collect();
+        let latest = latest.into_iter().filter(|game| !known.contains(game.game_url()));
+        if options.newest_first() {
+            games.splice(0..0, latest);
+        } else {
+            games.extend(latest);
+        }
+        Ok(report)
+    }
+
+    fn download_archives<D: Download>(&self, user: &str, options: &DownloadOptions, download: &D) -> Result<(Vec<D::Game>, DownloadReport)> {
+        let mut archives: Vec<ArchiveMonth> = self.get_game_month_urls(user)?
+            .into_iter()
+            .filter(|url| options.includes_archive(url))
+            .filter_map(|url| ArchiveMonth::from_url(url, user))
+            .collect();
+        if options.newest_first() {
+            archives.reverse();
+        }
+        options.report(Progress::ArchiveListFetched { count: archives.len() });
+
+        let mut game_data = vec![];
+        let mut report = DownloadReport::default();
+        // With some concurrency, a batch of months is downloaded at once and then handled in order
+        let concurrency = options.concurrency_or_default();
+        for (index, batch) in archives.chunks(concurrency).enumerate() {
+            if options.is_cancelled() {
+                let remaining = archives.len() - index * concurrency;
+                event!(info, remaining, "download cancelled");
+                options.report(Progress::Cancelled { remaining });
+                report.cancelled = true;
+                break;
+            }
+            for (archive, month) in batch.iter().zip(self.get_months(batch, download)) {
+                let games = month.and_then(|month| download.convert(options, &archive.url, month, user));
+                match games {
+                    Ok(mut games) => {
+                        if options.newest_first() {
+                            games.reverse();
+                        }
+                        game_data.extend(games);
+                        report.succeeded.push(archive.clone());
+                    }
+                    Err(err) if options.skips_failed_archives() => {
+                        options.report(Progress::ArchiveSkipped { month: (archive.year, archive.month) });
+                        report.failed.push((archive.clone(), err));
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+        }
+        Ok((game_data, report))
+    }
+
+    /// Downloads the archives, each on its own thread if there's more than one.
+    fn get_months<D: Download>(&self, archives: &[ArchiveMonth], download: &D) -> Vec<Result<D::Month>> {
+        if let [archive] = archives {
+            return vec![download.fetch(self, &archive.url)];
+        }
+        thread::scope(|scope| {
+            let handles: Vec<_> = archives.iter()
+                .map(|archive| scope.spawn(move || download.fetch(self, &archive.url)))
+                .collect();
+            handles.into_iter()
+                .map(|handle| handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+                .collect()
+        })
+    }
+
+    /// Downloads the games of several users (e.g. one player's different accounts) into a single list, sorted by when
+    /// they ended. A game between two of the users is only included once, as in [dedupe_games].
+    pub fn games_many<I, S>(&self, users: I) -> Result<Vec<GameData>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut games = vec![];
+        for user in users {
+            games.extend(self.games(user.as_ref())?);
+        }
+        let mut games = dedupe_games(games);
+        games.sort_by_key(|game| game.end_time);
+        Ok(games)
+    }
+
+    /// Lazily iterates over all of a user's games, downloading each month only when it is reached. See [GameIter].
+    pub fn games_iter(&self, user: &str) -> Result<GameIter> {
+        self.games_iter_with(user, DownloadOptions::default())
+    }
+
+    /// Lazily iterates over the user's games from the archives selected by `options`.
+    pub fn games_iter_with(&self, user: &str, options: DownloadOptions) -> Result<GameIter> {
+        let client = self.configured(&options).into_owned();
+        let mut urls: Vec<String> = client.get_game_month_urls(user)?
+            .into_iter()
+            .filter(|url| options.includes_archive(url))
+            .collect();
+        if options.newest_first() {
+            urls.reverse();
+        }
+        options.report(Progress::ArchiveListFetched { count: urls.len() });
+        Ok(GameIter::new(client, user, options, urls))
+    }
+
+    /// Like [Client::games], but downloads up to `concurrency` monthly archives at the same time. Games are still returned oldest first.
+    /// The same as [Client::games_with] with [DownloadOptions::concurrency], which also has the other options.
+    ///
+    /// Keep `concurrency` modest (a handful of threads), Chess.com rate limits clients that make too many parallel requests.
+    pub fn games_parallel(&self, user: &str, concurrency: usize) -> Result<Vec<GameData>> {
+        self.games_with(user, &DownloadOptions::new().concurrency(concurrency))
+    }
+}
+
+/// A way of downloading archives with [Client::download]: how each month is read, and what its games become.
+pub(crate) trait Download: Sync {
+    /// A month as read from its archive.
+    type Month: Send;
+    type Game: Downloaded;
+
+    fn fetch(&self, client: &Client, url: &str) -> Result<Self::Month>;
+
+    /// Turns a month's games into what the download returns, keeping the ones the options select.
+    fn convert(&self, options: &DownloadOptions, url: &str, month: Self::Month, user: &str) -> Result<Vec<Self::Game>>;
+}
+
+/// What checking the newest archive again needs to know about a downloaded game.
+pub(crate) trait Downloaded {
+    fn end_time(&self) -> u32;
+    fn game_url(&self) -> &str;
+}
+
+/// Downloads the full [GameData], as [Client::games_with] does.
+struct Full;
+
+impl Download for Full {
+    type Month = MonthGames;
+    type Game = GameData;
+
+    fn fetch(&self, client: &Client, url: &str) -> Result<MonthGames> {
+        client.get_month(url)
+    }
+
+    fn convert(&self, options: &DownloadOptions, url: &str, month: MonthGames, user: &str) -> Result<Vec<GameData>> {
+        options.game_data(url, month, user).into_iter().collect()
+    }
+}
+
+impl Downloaded for GameData {
+    fn end_time(&self) -> u32 {
+        self.end_time
+    }
+
+    fn game_url(&self) -> &str {
+        &self.game_url
+    }
+}
+
+/// What [Client::send] needs from a response to decide whether to retry it.
+trait Response {
+    fn status(&self) -> StatusCode;
+    fn headers(&self) -> &HeaderMap;
+}
+
+impl Response for HttpResponse {
+    fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+}
+
+impl Response for StreamingResponse {
+    fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+}
+
+/// Configures a [Client]. Anything that isn't set falls back to reqwest's defaults.
+#[derive(Debug, Default)]
+pub struct ClientBuilder {
+    user_agent: Option<String>,
+    timeout: Option<Duration>,
+    headers: HeaderMap,
+    #[cfg(not(target_arch = "wasm32"))]
+    proxies: Vec<Proxy>,
+    cache: Option<CacheConfig>,
+    rate_limit: Option<f64>,
+    max_retries: Option<u32>,
+    transport: Option<Transport>,
+    base_url: Option<String>,
+    no_compression: bool,
+}
+
+impl ClientBuilder {
+    /// Sets the User-Agent header. Chess.com recommends including a way to contact you, e.g. an email address.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sets a timeout for each request, from connecting until the body has been read.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Adds headers sent with every request. Later calls add to (and override) what was set before.
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+
+    /// Routes requests through a proxy. May be called more than once. Not available in the browser, which picks its own.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Caches monthly archives on disk. See [CacheConfig].
+    pub fn cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Sends at most this many requests per second, across all threads using the client. Unlimited by default, and for
+    /// rates that aren't positive.
+    ///
+    /// Chess.com doesn't publish a limit, but serial requests are never throttled, so with parallel downloads a few
+    /// requests per second is a safe choice.
+    pub fn rate_limit(mut self, requests_per_second: f64) -> Self {
+        self.rate_limit = Some(requests_per_second);
+        self
+    }
+
+    /// How many times a request is retried after a 429, a server error, a timeout or a failed connection, waiting twice
+    /// as long each time (or as long as the API's `Retry-After` header asks). Defaults to 3; 0 disables retries.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    /// Whether to ask for gzip or deflate compressed responses, which are decompressed transparently. On by default;
+    /// monthly archives of active players shrink to a fraction of their size. In the browser, `fetch` always negotiates
+    /// compression itself.
+    pub fn compression(mut self, enabled: bool) -> Self {
+        self.no_compression = !enabled;
+        self
+    }
+
+    /// Sends requests through `transport` instead of reqwest, e.g. a [MockTransport](crate::MockTransport) in tests.
+    ///
+    /// The User-Agent, timeout, headers, proxies and compression only configure the default reqwest transport, so they are ignored.
+    /// The cache, rate limit and retries still apply. The async client always uses reqwest.
+    pub fn transport(mut self, transport: impl HttpTransport + 'static) -> Self {
+        self.transport = Some(Transport::custom(transport));
+        self
+    }
+
+    /// Sends requests to a mirror of the API (or a local record/replay server) instead of `https://api.chess.com/pub`,
+    /// e.g. `http://localhost:8080/pub`. Archive URLs listed by the API are rewritten to point there too.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into().trim_end_matches('/').to_string());
+        self
+    }
+
+    fn base_url_or_default(&self) -> String {
+        self.base_url.clone().unwrap_or_else(|| DEFAULT_BASE_URL.to_string())
+    }
+
+    fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy { max_retries: self.max_retries.unwrap_or(DEFAULT_MAX_RETRIES) }
+    }
+
+    /// The headers reqwest sends with every request. Only reqwest's clients use them, and in the browser that's the async one.
+    #[cfg(any(not(target_arch = "wasm32"), feature = "async"))]
+    fn headers(&self) -> Result<HeaderMap> {
+        let mut headers = self.headers.clone();
+        if let Some(user_agent) = &self.user_agent {
+            let value = HeaderValue::from_str(user_agent).map_err(|_| Error::InvalidHeader(USER_AGENT.to_string()))?;
+            headers.insert(USER_AGENT, value);
+        }
+        else if !headers.contains_key(USER_AGENT) {
+            headers.insert(USER_AGENT, HeaderValue::from_static(DEFAULT_USER_AGENT));
+        }
+        Ok(headers)
+    }
+
+    /// Builds a blocking [Client]. In the browser (`wasm32`) there is no blocking reqwest client, so this fails with
+    /// [Error::NoTransport](crate::Error) unless a [transport](ClientBuilder::transport) was set.
+    pub fn build(self) -> Result<Client> {
+        let retry = self.retry_policy();
+        let base_url = self.base_url_or_default();
+        let transport = match self.transport {
+            Some(transport) => transport,
+            #[cfg(target_arch = "wasm32")]
+            None => return Err(Error::NoTransport),
+            #[cfg(not(target_arch = "wasm32"))]
+            None => {
+                let mut builder = reqwest::blocking::Client::builder()
+                    .default_headers(self.headers()?)
+                    .gzip(!self.no_compression)
+                    .deflate(!self.no_compression);
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                for proxy in self.proxies {
+                    builder = builder.proxy(proxy);
+                }
+                Transport::reqwest(ReqwestTransport::new(builder.build()?))
+            }
+        };
+        Ok(Client {
+            transport,
+            base_url,
+            limiter: self.rate_limit.and_then(RateLimiter::shared),
+            retry,
+            cache: self.cache,
+        })
+    }
+
+    /// Builds an [AsyncClient](crate::AsyncClient) with the same settings. This is the client to use in the browser,
+    /// where reqwest sends requests with `fetch`.
+    #[cfg(feature = "async")]
+    pub fn build_async(self) -> Result<crate::AsyncClient> {
+        let retry = self.retry_policy();
+        let base_url = self.base_url_or_default();
+        let builder = reqwest::Client::builder().default_headers(self.headers()?);
+        #[cfg(not(target_arch = "wasm32"))]
+        let builder = {
+            let mut builder = builder.gzip(!self.no_compression).deflate(!self.no_compression);
+            if let Some(timeout) = self.timeout {
+                builder = builder.timeout(timeout);
+            }
+            for proxy in self.proxies {
+                builder = builder.proxy(proxy);
+            }
+            builder
+        };
+        Ok(crate::AsyncClient::from_reqwest(
+            builder.build()?,
+            base_url,
+            self.cache,
+            self.rate_limit.and_then(RateLimiter::shared),
+            retry,
+        ))
+    }
+}
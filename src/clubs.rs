@@ -0,0 +1,130 @@
+//! Clubs: their profiles, members and team matches.
+//!
+//! Clubs are identified by the last part of their URL, e.g. `chess-com-developer-community` for
+//! `https://www.chess.com/club/chess-com-developer-community`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::path_id;
+use crate::{Client, GameData, Result};
+
+/// A club's profile, from `/pub/club/{id}`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Club {
+    /// The API URL of the club.
+    #[serde(rename = "@id")]
+    pub id: String,
+    pub name: String,
+    pub club_id: u64,
+    pub icon: Option<String>,
+    /// The API URL of the club's country.
+    pub country: Option<String>,
+    pub average_daily_rating: Option<u32>,
+    pub members_count: u32,
+    /// When the club was created, as a Unix timestamp.
+    pub created: u32,
+    /// When a member last did something in the club, as a Unix timestamp.
+    pub last_activity: u32,
+    /// `"public"` or `"private"`.
+    pub visibility: String,
+    /// The URL to ask to join the club.
+    pub join_request: Option<String>,
+    /// The API URLs of the club's admins.
+    #[serde(default)]
+    pub admin: Vec<String>,
+    pub description: Option<String>,
+}
+
+/// A member of a club.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ClubMember {
+    pub username: String,
+    /// When they joined the club, as a Unix timestamp.
+    pub joined: u32,
+}
+
+/// A club's members, from `/pub/club/{id}/members`, grouped by how recently they were active in the club.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ClubMembers {
+    #[serde(default)]
+    pub weekly: Vec<ClubMember>,
+    #[serde(default)]
+    pub monthly: Vec<ClubMember>,
+    #[serde(default)]
+    pub all_time: Vec<ClubMember>,
+}
+
+impl ClubMembers {
+    /// Every member, however active. Each member is only in one of the groups.
+    pub fn all(&self) -> impl Iterator<Item = &ClubMember> {
+        self.weekly.iter().chain(&self.monthly).chain(&self.all_time)
+    }
+
+    /// The usernames of every member, ready to be passed to [Client::games_many] or [GameData::download_many].
+    pub fn usernames(&self) -> impl Iterator<Item = &str> {
+        self.all().map(|member| member.username.as_str())
+    }
+}
+
+/// A team match the club took part in.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ClubMatch {
+    pub name: String,
+    /// The API URL of the match.
+    #[serde(rename = "@id")]
+    pub id: String,
+    /// The API URL of the opposing club.
+    pub opponent: String,
+    /// When the match started (or will start), as a Unix timestamp.
+    pub start_time: Option<u32>,
+    pub time_class: String,
+    /// `"win"`, `"lose"` or `"draw"` for finished matches.
+    pub result: Option<String>,
+}
+
+/// A club's team matches, from `/pub/club/{id}/matches`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ClubMatches {
+    #[serde(default)]
+    pub finished: Vec<ClubMatch>,
+    #[serde(default)]
+    pub in_progress: Vec<ClubMatch>,
+    #[serde(default)]
+    pub registered: Vec<ClubMatch>,
+}
+
+impl Client {
+    /// Downloads a club's profile. The ID is the last part of the club's URL, e.g. `chess-com-developer-community`; one
+    /// with anything but letters, digits, hyphens and underscores is [Error::InvalidId](crate::Error::InvalidId).
+    pub fn club(&self, id: &str) -> Result<Club> {
+        self.get_json(&self.endpoint(&format!("club/{}", path_id(id)?)))
+    }
+
+    /// Downloads the list of a club's members.
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// let client = hikaru::Client::new();
+    /// let members = client.club_members("chess-com-developer-community")?;
+    /// let games = client.games_many(members.usernames())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn club_members(&self, id: &str) -> Result<ClubMembers> {
+        self.get_json(&self.endpoint(&format!("club/{}/members", path_id(id)?)))
+    }
+
+    /// Downloads the list of a club's team matches, finished, in progress and upcoming.
+    pub fn club_matches(&self, id: &str) -> Result<ClubMatches> {
+        self.get_json(&self.endpoint(&format!("club/{}/matches", path_id(id)?)))
+    }
+
+    /// Downloads the games of every member of a club, merged as in [Client::games_many].
+    ///
+    /// Big clubs have thousands of members, each with an archive request per month played, so consider a
+    /// [rate limit](crate::ClientBuilder::rate_limit) and a [cache](crate::ClientBuilder::cache).
+    pub fn club_games(&self, id: &str) -> Result<Vec<GameData>> {
+        let members = self.club_members(id)?;
+        self.games_many(members.usernames())
+    }
+}
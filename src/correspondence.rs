@@ -0,0 +1,51 @@
+//! When the moves of a finished daily game were made.
+//!
+//! Daily PGNs annotate each move with `[%clk]`, the time the player still had for that move when they made it. Each
+//! move gets the same allowance, so the clock gives how long the move took, and adding those up from the game's start
+//! gives when it was made.
+
+use std::time::Duration;
+
+use crate::{GameData, TimeControl};
+
+impl GameData {
+    /// How long each move took, in the order they were played. `None` unless this is a daily game whose PGN has a clock
+    /// time for every move.
+    ///
+    /// Time on vacation counts against nobody's clock, so it isn't included.
+    pub fn thinking_times(&self) -> Option<Vec<Duration>> {
+        let allowance = match self.time_control.parse().ok()? {
+            TimeControl::Daily { seconds_per_move } => Duration::from_secs(seconds_per_move.into()),
+            _ => return None,
+        };
+        let pgn = self.parsed_pgn()?;
+        if pgn.clock_times.is_empty() || pgn.clock_times.len() != pgn.moves.len() {
+            return None;
+        }
+        Some(pgn.clock_times.iter().map(|&remaining| allowance.saturating_sub(remaining)).collect())
+    }
+
+    /// When each move was made, as Unix timestamps, worked out from [GameData::thinking_times] and the game's start.
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// for game in hikaru::GameData::download(vec!["hikaru"])? {
+    ///     if let Some(timestamps) = game.move_timestamps() {
+    ///         println!("{}: last move at {:?}", game.game_url, timestamps.last());
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn move_timestamps(&self) -> Option<Vec<u32>> {
+        let start = self.start_time?;
+        let mut elapsed = Duration::ZERO;
+        Some(self.thinking_times()?
+            .into_iter()
+            .map(|thinking| {
+                elapsed += thinking;
+                start + elapsed.as_secs() as u32
+            })
+            .collect())
+    }
+}
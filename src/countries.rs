@@ -0,0 +1,65 @@
+//! Countries, and the players who represent them.
+//!
+//! Countries are identified by their two-letter ISO 3166 code, e.g. `US` or `NO`. Chess.com also has a few codes of its
+//! own, such as `XE` for England and `XS` for Scotland.
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::path_id;
+use crate::{Client, Result};
+
+/// A country, from `/pub/country/{iso}`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Country {
+    /// The API URL of the country.
+    #[serde(rename = "@id")]
+    pub id: String,
+    pub name: String,
+    /// The ISO code, e.g. `US`.
+    pub code: String,
+}
+
+#[derive(Deserialize)]
+struct CountryPlayers {
+    players: Vec<String>,
+}
+
+impl Country {
+    /// Downloads a country's details, using a [Client] with the default settings.
+    pub fn download(iso_code: &str) -> Result<Country> {
+        Client::new().country(iso_code)
+    }
+
+    /// Downloads the usernames of everyone who has picked this country on their profile, using a [Client] with the default
+    /// settings. See [Client::country_players].
+    pub fn players(iso_code: &str) -> Result<Vec<String>> {
+        Client::new().country_players(iso_code)
+    }
+}
+
+impl Client {
+    /// Downloads a country's profile, by its two-letter ISO code, e.g. `US`. A code with anything but letters, digits,
+    /// hyphens and underscores is [Error::InvalidId](crate::Error::InvalidId).
+    pub fn country(&self, iso_code: &str) -> Result<Country> {
+        self.get_json(&self.endpoint(&format!("country/{}", path_id(iso_code)?.to_uppercase())))
+    }
+
+    /// Downloads the usernames of everyone who has picked this country on their profile. Chess.com only updates the list
+    /// every few hours, and for big countries it runs into the hundreds of thousands.
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// let client = hikaru::Client::new();
+    /// let players = client.country_players("IS")?;
+    ///
+    /// // Every hundredth Icelandic player
+    /// let sample = players.iter().step_by(100);
+    /// let games = client.games_many(sample)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn country_players(&self, iso_code: &str) -> Result<Vec<String>> {
+        let url = self.endpoint(&format!("country/{}/players", path_id(iso_code)?.to_uppercase()));
+        Ok(self.get_json::<CountryPlayers>(&url)?.players)
+    }
+}
@@ -0,0 +1,189 @@
+//! CSV export, enabled with the `csv` feature.
+//!
+//! Every [GameData] field becomes a column, named after the field unless renamed with [CsvOptions::rename]. After
+//! `result_win_lose` comes one more, `standard_result`, with the result in PGN notation, e.g. `1-0`.
+
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use crate::{Error, GameData, GameSink, Result};
+
+/// Which columns to write, and what to call them.
+///
+/// ```rust,no_run
+/// use hikaru::{CsvOptions, GameData};
+///
+/// # fn main() -> hikaru::Result<()> {
+/// let games = GameData::download(vec!["hikaru"])?;
+/// let options = CsvOptions::new()
+///     .columns(["date", "time_class", "rating", "win"])
+///     .rename("win", "score");
+///
+/// GameData::to_csv_writer_with(&games, std::io::stdout(), &options)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CsvOptions {
+    columns: Option<Vec<String>>,
+    renames: Vec<(String, String)>,
+}
+
+impl CsvOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only writes these fields, in this order. By default every field is written, in the order `GameData` declares them.
+    pub fn columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Uses `header` instead of the field name in the header row.
+    pub fn rename(mut self, field: impl Into<String>, header: impl Into<String>) -> Self {
+        self.renames.push((field.into(), header.into()));
+        self
+    }
+
+    fn header(&self, field: &str) -> String {
+        self.renames.iter()
+            .rev()
+            .find(|(from, _)| from == field)
+            .map_or_else(|| field.to_string(), |(_, to)| to.clone())
+    }
+}
+
+/// Renders a JSON value as a CSV cell. Lists (e.g. moves) are joined with spaces.
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Array(values) => values.iter().map(cell).collect::<Vec<_>>().join(" "),
+        value => value.to_string(),
+    }
+}
+
+/// A game's fields by name, with `standard_result` added after `result_win_lose`.
+fn row(game: &GameData) -> Result<Map<String, Value>> {
+    let fields = match serde_json::to_value(game)? {
+        Value::Object(fields) => fields,
+        _ => unreachable!("GameData serializes to an object"),
+    };
+    let mut row = Map::new();
+    for (field, value) in fields {
+        let is_result = field == "result_win_lose";
+        row.insert(field, value);
+        if is_result {
+            row.insert("standard_result".into(), game.standard_result().into());
+        }
+    }
+    Ok(row)
+}
+
+/// Checks the columns against a game's fields.
+fn check_columns(columns: &[String], row: &Map<String, Value>) -> Result<()> {
+    match columns.iter().find(|column| !row.contains_key(*column)) {
+        Some(unknown) => Err(Error::UnknownColumn(unknown.clone())),
+        None => Ok(()),
+    }
+}
+
+impl GameData {
+    /// Writes the games as CSV, with a header row and every field as a column.
+    pub fn to_csv_writer<W: Write>(games: &[GameData], writer: W) -> Result<()> {
+        Self::to_csv_writer_with(games, writer, &CsvOptions::default())
+    }
+
+    /// Writes the games as CSV, with the columns chosen by `options`.
+    pub fn to_csv_writer_with<W: Write>(games: &[GameData], writer: W, options: &CsvOptions) -> Result<()> {
+        let rows = games.iter().map(row).collect::<Result<Vec<_>>>()?;
+
+        let columns: Vec<String> = match &options.columns {
+            Some(columns) => columns.clone(),
+            None => rows.first().map(|row| row.keys().cloned().collect()).unwrap_or_default(),
+        };
+        // Without games there are no fields to name, so unless the columns were given there is nothing to write
+        if columns.is_empty() {
+            return Ok(());
+        }
+        if let Some(row) = rows.first() {
+            check_columns(&columns, row)?;
+        }
+
+        let mut csv = ::csv::Writer::from_writer(writer);
+        csv.write_record(columns.iter().map(|column| options.header(column)))?;
+        for row in &rows {
+            csv.write_record(columns.iter().map(|column| cell(&row[column])))?;
+        }
+        csv.flush()?;
+        Ok(())
+    }
+
+    /// Writes the games to a CSV file, replacing it if it already exists.
+    pub fn write_csv_file(games: &[GameData], path: impl AsRef<Path>) -> Result<()> {
+        Self::to_csv_writer(games, File::create(path)?)
+    }
+}
+
+/// Writes games as CSV as they arrive, a [GameSink] version of [GameData::to_csv_writer_with].
+///
+/// The header row is written along with the first game, so nothing at all is written if there are no games.
+///
+/// ```rust,no_run
+/// use std::fs::File;
+/// use hikaru::{CsvOptions, CsvSink, GameData};
+///
+/// # fn main() -> hikaru::Result<()> {
+/// let options = CsvOptions::new().columns(["date", "rating", "win"]);
+/// GameData::download_into("hikaru", CsvSink::with_options(File::create("hikaru.csv")?, options))?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct CsvSink<W: Write> {
+    csv: ::csv::Writer<W>,
+    options: CsvOptions,
+    /// Known once the header has been written.
+    columns: Option<Vec<String>>,
+}
+
+impl<W: Write> CsvSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self::with_options(writer, CsvOptions::default())
+    }
+
+    pub fn with_options(writer: W, options: CsvOptions) -> Self {
+        CsvSink { csv: ::csv::Writer::from_writer(writer), options, columns: None }
+    }
+}
+
+impl<W: Write> GameSink for CsvSink<W> {
+    fn write(&mut self, game: &GameData) -> Result<()> {
+        let row = row(game)?;
+        let columns = match &self.columns {
+            Some(columns) => columns,
+            None => {
+                let columns = self.options.columns.clone().unwrap_or_else(|| row.keys().cloned().collect());
+                check_columns(&columns, &row)?;
+                let options = &self.options;
+                self.csv.write_record(columns.iter().map(|column| options.header(column)))?;
+                self.columns.insert(columns)
+            }
+        };
+        self.csv.write_record(columns.iter().map(|column| cell(&row[column])))?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.csv.flush()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+//! Daily (correspondence) games that are still being played, from `/pub/player/{user}/games` and
+//! `/pub/player/{user}/games/to-move`.
+//!
+//! These have a different shape from archived games: there is no result yet, the players are only given as profile URLs,
+//! and there is a deadline for the next move.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Client, Color, Result, Rules, TimeClass, Username};
+
+/// A daily game in progress.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DailyGame {
+    /// The game's page on Chess.com.
+    pub url: String,
+    /// The API URL of White's profile.
+    pub white: String,
+    /// The API URL of Black's profile.
+    pub black: String,
+    /// The current position.
+    pub fen: String,
+    /// The moves so far.
+    pub pgn: Option<String>,
+    /// Whose move it is.
+    pub turn: Color,
+    /// The deadline for the next move, as a Unix timestamp. 0 for games that haven't started.
+    pub move_by: u32,
+    /// The API URL of the player offering a draw, if there is an offer on the table.
+    pub draw_offer: Option<String>,
+    /// When the last move was made, as a Unix timestamp.
+    pub last_activity: u32,
+    pub start_time: Option<u32>,
+    pub time_control: String,
+    pub time_class: TimeClass,
+    pub rules: Rules,
+    /// The API URL of the tournament the game belongs to.
+    pub tournament: Option<String>,
+    /// The API URL of the team match the game belongs to.
+    #[serde(rename = "match")]
+    pub team_match: Option<String>,
+}
+
+/// A daily game waiting for the player to move.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct GameToMove {
+    /// The game's page on Chess.com.
+    pub url: String,
+    /// The deadline for the move, as a Unix timestamp.
+    pub move_by: u32,
+    /// Whether the opponent has offered a draw.
+    #[serde(default)]
+    pub draw_offer: bool,
+    /// When the last move was made, as a Unix timestamp.
+    pub last_activity: u32,
+}
+
+#[derive(Deserialize)]
+struct DailyGames<T> {
+    games: Vec<T>,
+}
+
+impl Client {
+    /// Downloads the user's daily games that are still in progress.
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// for game in hikaru::Client::new().daily_games("erik")? {
+    ///     println!("{:?} to move in {}", game.turn, game.url);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn daily_games(&self, user: &str) -> Result<Vec<DailyGame>> {
+        let url = self.endpoint(&format!("player/{}/games", Username::new(user)?));
+        let games: DailyGames<DailyGame> = self.get_json(&url).map_err(|err| err.for_user(user))?;
+        Ok(games.games)
+    }
+
+    /// Downloads the user's daily games where it is their turn to move, e.g. for a bot deciding which game to play next.
+    pub fn games_to_move(&self, user: &str) -> Result<Vec<GameToMove>> {
+        let url = self.endpoint(&format!("player/{}/games/to-move", Username::new(user)?));
+        let games: DailyGames<GameToMove> = self.get_json(&url).map_err(|err| err.for_user(user))?;
+        Ok(games.games)
+    }
+}
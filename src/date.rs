@@ -0,0 +1,60 @@
+//! Calendar arithmetic for the Unix timestamps the API uses, so we don't need a date crate just to find a month.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The current Unix timestamp.
+pub(crate) fn now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_secs() as i64)
+}
+
+/// Converts a Unix timestamp (seconds, UTC) into `(year, month, day)`.
+pub(crate) fn civil_from_timestamp(timestamp: i64) -> (i32, u8, u8) {
+    civil_from_days(timestamp.div_euclid(86_400))
+}
+
+/// Converts days since 1970-01-01 into `(year, month, day)`. This is Howard Hinnant's `civil_from_days` algorithm.
+pub(crate) fn civil_from_days(days: i64) -> (i32, u8, u8) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u8;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+    let year = (year_of_era + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+    (year, month, day)
+}
+
+/// The `(year, month)` a Unix timestamp falls in.
+pub(crate) fn year_month(timestamp: i64) -> (u16, u8) {
+    let (year, month, _) = civil_from_timestamp(timestamp);
+    (year as u16, month)
+}
+
+/// The UTC date of a Unix timestamp the way PGN writes dates, e.g. `2014.01.06`.
+pub(crate) fn pgn_date(timestamp: i64) -> String {
+    let (year, month, day) = civil_from_timestamp(timestamp);
+    format!("{}.{:02}.{:02}", year, month, day)
+}
+
+/// Converts `(year, month, day)` into days since 1970-01-01, the inverse of [civil_from_days].
+pub(crate) fn days_from_civil(year: i32, month: u8, day: u8) -> i64 {
+    let year = i64::from(year) - if month <= 2 { 1 } else { 0 };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let mp = (i64::from(month) + 9) % 12;
+    let day_of_year = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Reads a PGN date and time, e.g. `2014.01.06` and `19:37:51`, as a Unix timestamp. Both are taken to be UTC.
+pub(crate) fn timestamp_from_pgn(date: &str, time: &str) -> Option<i64> {
+    let mut date = date.split('.').map(str::parse::<i64>);
+    let (year, month, day) = (date.next()?.ok()?, date.next()?.ok()?, date.next()?.ok()?);
+    let mut time = time.split(':').map(str::parse::<i64>);
+    let (hours, minutes, seconds) = (time.next()?.ok()?, time.next()?.ok()?, time.next()?.ok()?);
+    let days = days_from_civil(year as i32, month as u8, day as u8);
+    Some(days * 86_400 + hours * 3600 + minutes * 60 + seconds)
+}
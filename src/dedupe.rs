@@ -0,0 +1,49 @@
+//! Removing duplicate games, from downloading several players who played each other or merging old and new downloads.
+
+use std::collections::HashMap;
+
+use crate::GameData;
+
+/// Removes games that appear more than once, keeping the first copy of each in its original place. Two records are the
+/// same game if they share a [uuid](GameData::uuid) or a [game URL](GameData::game_url). When one copy has a PGN and
+/// the one kept doesn't, the copy with the PGN replaces it.
+///
+/// Records of a game from different players' points of view count as duplicates too, so the one kept is from the side
+/// of whichever player came first.
+///
+/// ```rust,no_run
+/// # fn main() -> hikaru::Result<()> {
+/// let mut games = hikaru::GameData::download(vec!["hikaru", "magnuscarlsen"])?;
+/// games = hikaru::dedupe_games(games);
+/// # Ok(())
+/// # }
+/// ```
+pub fn dedupe_games(games: Vec<GameData>) -> Vec<GameData> {
+    let mut kept: Vec<GameData> = Vec::with_capacity(games.len());
+    let mut by_url: HashMap<String, usize> = HashMap::new();
+    let mut by_uuid: HashMap<String, usize> = HashMap::new();
+    for game in games {
+        let existing = by_url.get(&game.game_url)
+            .or_else(|| game.uuid.as_ref().and_then(|uuid| by_uuid.get(uuid)))
+            .copied();
+        match existing {
+            Some(index) => {
+                if kept[index].pgn.is_none() && game.pgn.is_some() {
+                    by_url.insert(game.game_url.clone(), index);
+                    if let Some(uuid) = &game.uuid {
+                        by_uuid.insert(uuid.clone(), index);
+                    }
+                    kept[index] = game;
+                }
+            }
+            None => {
+                by_url.insert(game.game_url.clone(), kept.len());
+                if let Some(uuid) = &game.uuid {
+                    by_uuid.insert(uuid.clone(), kept.len());
+                }
+                kept.push(game);
+            }
+        }
+    }
+    kept
+}
@@ -0,0 +1,249 @@
+//! Naming openings from their moves, with a small built-in ECO table.
+//!
+//! The table covers the main openings and their best known variations rather than all 500 ECO codes. Games are matched
+//! by position, so transpositions (e.g. 1.Nf3 Nf6 2.d4 d5 3.Bf4 into the London System) are still recognised.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::board::Board;
+use crate::{GameData, ParsedPgn};
+
+/// An opening, e.g. `B90 Sicilian Defense: Najdorf Variation`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Opening {
+    /// The ECO code, e.g. `B90`.
+    pub eco: String,
+    /// The full name, family first, e.g. `Sicilian Defense: Najdorf Variation`.
+    pub name: String,
+}
+
+impl Opening {
+    /// Names the opening of a game, by the last position reached that is in Hikaru's built-in table. `None` for games
+    /// that didn't start from the standard position, or when nothing in the table was reached.
+    ///
+    /// ```rust
+    /// use hikaru::{Opening, ParsedPgn};
+    ///
+    /// let pgn = ParsedPgn::parse("1. e4 c5 2. Nf3 d6 3. d4 cxd4 4. Nxd4 Nf6 5. Nc3 a6 6. Be3 e5 *");
+    /// let opening = Opening::from_pgn(&pgn).unwrap();
+    /// assert_eq!(opening.eco, "B90");
+    /// assert_eq!(opening.name, "Sicilian Defense: Najdorf Variation");
+    /// assert_eq!(opening.family(), "Sicilian Defense");
+    ///
+    /// // Transpositions end up in the same place
+    /// let pgn = ParsedPgn::parse("1. Nf3 Nf6 2. d4 d5 3. Bf4 *");
+    /// assert_eq!(Opening::from_pgn(&pgn).unwrap().name, "London System");
+    /// ```
+    pub fn from_pgn(pgn: &ParsedPgn) -> Option<Opening> {
+        if pgn.tags.contains_key("FEN") {
+            return None;
+        }
+        let positions = positions();
+        let mut board = Board::new();
+        let mut opening = None;
+        for san in pgn.moves.iter().take(MAX_PLIES) {
+            let mv = match board.parse_san(san.as_str()) {
+                Some(mv) => mv,
+                None => break,
+            };
+            board.play(&mv);
+            if let Some(&index) = positions.get(&position_key(&board)) {
+                opening = Some(index);
+            }
+        }
+        opening.map(|index| {
+            let (eco, name, _) = OPENINGS[index];
+            Opening { eco: eco.into(), name: name.into() }
+        })
+    }
+
+    /// The opening without the variation, e.g. `Sicilian Defense`.
+    pub fn family(&self) -> &str {
+        self.name.split(':').next().unwrap_or(&self.name).trim()
+    }
+}
+
+/// Formats as e.g. `B90 Sicilian Defense: Najdorf Variation`.
+impl fmt::Display for Opening {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.eco, self.name)
+    }
+}
+
+/// `(ECO, name, moves)`
+const OPENINGS: &[(&str, &str, &str)] = &[
+    ("A00", "Polish Opening", "b4"),
+    ("A00", "Grob Opening", "g4"),
+    ("A00", "Hungarian Opening", "g3"),
+    ("A00", "Van't Kruijs Opening", "e3"),
+    ("A00", "Amar Opening", "Nh3"),
+    ("A01", "Nimzo-Larsen Attack", "b3"),
+    ("A02", "Bird Opening", "f4"),
+    ("A02", "Bird Opening: From's Gambit", "f4 e5"),
+    ("A03", "Bird Opening: Dutch Variation", "f4 d5"),
+    ("A04", "Zukertort Opening", "Nf3"),
+    ("A05", "King's Indian Attack", "Nf3 Nf6 g3"),
+    ("A10", "English Opening", "c4"),
+    ("A15", "English Opening: Anglo-Indian Defense", "c4 Nf6"),
+    ("A20", "English Opening: King's English Variation", "c4 e5"),
+    ("A30", "English Opening: Symmetrical Variation", "c4 c5"),
+    ("A40", "Queen's Pawn Game", "d4"),
+    ("A40", "Englund Gambit", "d4 e5"),
+    ("A43", "Benoni Defense: Old Benoni", "d4 c5"),
+    ("A45", "Indian Defense", "d4 Nf6"),
+    ("A45", "Trompowsky Attack", "d4 Nf6 Bg5"),
+    ("A46", "Indian Defense: Knights Variation", "d4 Nf6 Nf3"),
+    ("A50", "Indian Defense: Normal Variation", "d4 Nf6 c4"),
+    ("A51", "Indian Defense: Budapest Defense", "d4 Nf6 c4 e5"),
+    ("A56", "Benoni Defense", "d4 Nf6 c4 c5"),
+    ("A57", "Benko Gambit", "d4 Nf6 c4 c5 d5 b5"),
+    ("A60", "Benoni Defense: Modern Variation", "d4 Nf6 c4 c5 d5 e6"),
+    ("A80", "Dutch Defense", "d4 f5"),
+    ("B00", "Nimzowitsch Defense", "e4 Nc6"),
+    ("B00", "Owen Defense", "e4 b6"),
+    ("B01", "Scandinavian Defense", "e4 d5"),
+    ("B01", "Scandinavian Defense: Mieses-Kotroc Variation", "e4 d5 exd5 Qxd5"),
+    ("B02", "Alekhine Defense", "e4 Nf6"),
+    ("B06", "Modern Defense", "e4 g6"),
+    ("B07", "Pirc Defense", "e4 d6 d4 Nf6 Nc3 g6"),
+    ("B10", "Caro-Kann Defense", "e4 c6"),
+    ("B12", "Caro-Kann Defense: Advance Variation", "e4 c6 d4 d5 e5"),
+    ("B13", "Caro-Kann Defense: Exchange Variation", "e4 c6 d4 d5 exd5 cxd5"),
+    ("B18", "Caro-Kann Defense: Classical Variation", "e4 c6 d4 d5 Nc3 dxe4 Nxe4 Bf5"),
+    ("B20", "Sicilian Defense", "e4 c5"),
+    ("B21", "Sicilian Defense: Smith-Morra Gambit", "e4 c5 d4 cxd4 c3"),
+    ("B22", "Sicilian Defense: Alapin Variation", "e4 c5 c3"),
+    ("B23", "Sicilian Defense: Closed", "e4 c5 Nc3"),
+    ("B27", "Sicilian Defense", "e4 c5 Nf3"),
+    ("B30", "Sicilian Defense: Old Sicilian", "e4 c5 Nf3 Nc6"),
+    ("B30", "Sicilian Defense: Rossolimo Variation", "e4 c5 Nf3 Nc6 Bb5"),
+    ("B32", "Sicilian Defense: Open", "e4 c5 Nf3 Nc6 d4 cxd4 Nxd4"),
+    ("B33", "Sicilian Defense: Sveshnikov Variation", "e4 c5 Nf3 Nc6 d4 cxd4 Nxd4 Nf6 Nc3 e5"),
+    ("B34", "Sicilian Defense: Accelerated Dragon", "e4 c5 Nf3 Nc6 d4 cxd4 Nxd4 g6"),
+    ("B40", "Sicilian Defense: French Variation", "e4 c5 Nf3 e6"),
+    ("B41", "Sicilian Defense: Kan Variation", "e4 c5 Nf3 e6 d4 cxd4 Nxd4 a6"),
+    ("B44", "Sicilian Defense: Taimanov Variation", "e4 c5 Nf3 e6 d4 cxd4 Nxd4 Nc6"),
+    ("B50", "Sicilian Defense: Modern Variations", "e4 c5 Nf3 d6"),
+    ("B51", "Sicilian Defense: Moscow Variation", "e4 c5 Nf3 d6 Bb5+"),
+    ("B54", "Sicilian Defense: Open", "e4 c5 Nf3 d6 d4 cxd4 Nxd4"),
+    ("B70", "Sicilian Defense: Dragon Variation", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 g6"),
+    ("B80", "Sicilian Defense: Scheveningen Variation", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 e6"),
+    ("B90", "Sicilian Defense: Najdorf Variation", "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3 a6"),
+    ("C00", "French Defense", "e4 e6"),
+    ("C01", "French Defense: Exchange Variation", "e4 e6 d4 d5 exd5"),
+    ("C02", "French Defense: Advance Variation", "e4 e6 d4 d5 e5"),
+    ("C03", "French Defense: Tarrasch Variation", "e4 e6 d4 d5 Nd2"),
+    ("C10", "French Defense: Paulsen Variation", "e4 e6 d4 d5 Nc3"),
+    ("C11", "French Defense: Classical Variation", "e4 e6 d4 d5 Nc3 Nf6"),
+    ("C15", "French Defense: Winawer Variation", "e4 e6 d4 d5 Nc3 Bb4"),
+    ("C20", "King's Pawn Game", "e4 e5"),
+    ("C20", "King's Pawn Game: Wayward Queen Attack", "e4 e5 Qh5"),
+    ("C21", "Danish Gambit", "e4 e5 d4 exd4 c3"),
+    ("C22", "Center Game", "e4 e5 d4 exd4 Qxd4"),
+    ("C23", "Bishop's Opening", "e4 e5 Bc4"),
+    ("C25", "Vienna Game", "e4 e5 Nc3"),
+    ("C30", "King's Gambit", "e4 e5 f4"),
+    ("C33", "King's Gambit Accepted", "e4 e5 f4 exf4"),
+    ("C40", "King's Knight Opening", "e4 e5 Nf3"),
+    ("C40", "Elephant Gambit", "e4 e5 Nf3 d5"),
+    ("C40", "Latvian Gambit", "e4 e5 Nf3 f5"),
+    ("C41", "Philidor Defense", "e4 e5 Nf3 d6"),
+    ("C42", "Russian Game", "e4 e5 Nf3 Nf6"),
+    ("C44", "King's Knight Opening: Normal Variation", "e4 e5 Nf3 Nc6"),
+    ("C44", "Ponziani Opening", "e4 e5 Nf3 Nc6 c3"),
+    ("C44", "Scotch Game", "e4 e5 Nf3 Nc6 d4"),
+    ("C45", "Scotch Game", "e4 e5 Nf3 Nc6 d4 exd4 Nxd4"),
+    ("C46", "Three Knights Opening", "e4 e5 Nf3 Nc6 Nc3"),
+    ("C47", "Four Knights Game", "e4 e5 Nf3 Nc6 Nc3 Nf6"),
+    ("C50", "Italian Game", "e4 e5 Nf3 Nc6 Bc4"),
+    ("C50", "Italian Game: Giuoco Piano", "e4 e5 Nf3 Nc6 Bc4 Bc5"),
+    ("C51", "Italian Game: Evans Gambit", "e4 e5 Nf3 Nc6 Bc4 Bc5 b4"),
+    ("C55", "Italian Game: Two Knights Defense", "e4 e5 Nf3 Nc6 Bc4 Nf6"),
+    ("C57", "Italian Game: Two Knights Defense, Fried Liver Attack", "e4 e5 Nf3 Nc6 Bc4 Nf6 Ng5 d5 exd5 Nxd5 Nxf7"),
+    ("C60", "Ruy Lopez", "e4 e5 Nf3 Nc6 Bb5"),
+    ("C65", "Ruy Lopez: Berlin Defense", "e4 e5 Nf3 Nc6 Bb5 Nf6"),
+    ("C68", "Ruy Lopez: Exchange Variation", "e4 e5 Nf3 Nc6 Bb5 a6 Bxc6"),
+    ("C70", "Ruy Lopez: Morphy Defense", "e4 e5 Nf3 Nc6 Bb5 a6"),
+    ("C84", "Ruy Lopez: Closed", "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7"),
+    ("D00", "Queen's Pawn Game", "d4 d5"),
+    ("D00", "Blackmar-Diemer Gambit", "d4 d5 e4"),
+    ("D02", "Queen's Pawn Game: Zukertort Variation", "d4 d5 Nf3"),
+    ("D02", "London System", "d4 d5 Nf3 Nf6 Bf4"),
+    ("D06", "Queen's Gambit", "d4 d5 c4"),
+    ("D07", "Queen's Gambit Declined: Chigorin Defense", "d4 d5 c4 Nc6"),
+    ("D08", "Queen's Gambit Declined: Albin Countergambit", "d4 d5 c4 e5"),
+    ("D10", "Slav Defense", "d4 d5 c4 c6"),
+    ("D20", "Queen's Gambit Accepted", "d4 d5 c4 dxc4"),
+    ("D30", "Queen's Gambit Declined", "d4 d5 c4 e6"),
+    ("D35", "Queen's Gambit Declined: Exchange Variation", "d4 d5 c4 e6 Nc3 Nf6 cxd5"),
+    ("D43", "Semi-Slav Defense", "d4 d5 c4 e6 Nc3 Nf6 Nf3 c6"),
+    ("D80", "Grünfeld Defense", "d4 Nf6 c4 g6 Nc3 d5"),
+    ("E00", "Catalan Opening", "d4 Nf6 c4 e6 g3"),
+    ("E11", "Bogo-Indian Defense", "d4 Nf6 c4 e6 Nf3 Bb4+"),
+    ("E12", "Queen's Indian Defense", "d4 Nf6 c4 e6 Nf3 b6"),
+    ("E20", "Nimzo-Indian Defense", "d4 Nf6 c4 e6 Nc3 Bb4"),
+    ("E60", "King's Indian Defense", "d4 Nf6 c4 g6"),
+    ("E70", "King's Indian Defense: Normal Variation", "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6"),
+];
+
+/// Openings are no longer named after this many plies, however the game went on.
+const MAX_PLIES: usize = 24;
+
+/// What two positions need to share to be the same opening: the pieces, whose move it is, and castling rights.
+fn position_key(board: &Board) -> String {
+    board.to_fen().split(' ').take(3).collect::<Vec<_>>().join(" ")
+}
+
+/// The position each table entry ends in, mapped to its index.
+fn positions() -> &'static HashMap<String, usize> {
+    static POSITIONS: OnceLock<HashMap<String, usize>> = OnceLock::new();
+    POSITIONS.get_or_init(|| {
+        OPENINGS.iter()
+            .enumerate()
+            .map(|(index, (_, _, moves))| {
+                let mut board = Board::new();
+                for san in moves.split(' ') {
+                    let mv = board.parse_san(san).expect("table moves are legal");
+                    board.play(&mv);
+                }
+                (position_key(&board), index)
+            })
+            .collect()
+    })
+}
+
+/// An ECO code in its usual form (`B90`), or `None` if `code` isn't one.
+fn normalize_eco(code: &str) -> Option<String> {
+    let code = code.trim().to_ascii_uppercase();
+    let mut chars = code.chars();
+    let valid = matches!(chars.next(), Some('A'..='E'))
+        && chars.clone().count() == 2
+        && chars.all(|c| c.is_ascii_digit());
+    Some(code).filter(|_| valid)
+}
+
+impl GameData {
+    /// Names the game's opening from its moves. `None` without a PGN; otherwise see [Opening::from_pgn].
+    pub fn opening(&self) -> Option<Opening> {
+        self.parsed_pgn().and_then(|pgn| Opening::from_pgn(&pgn))
+    }
+
+    /// The name of the game's opening, e.g. `Sicilian Defense: Najdorf Variation`. See [GameData::opening].
+    pub fn opening_name(&self) -> Option<String> {
+        self.opening().map(|opening| opening.name)
+    }
+
+    /// The opening without the variation, e.g. `Sicilian Defense`. See [GameData::opening].
+    pub fn opening_family(&self) -> Option<String> {
+        self.opening().map(|opening| opening.family().to_string())
+    }
+
+    /// The game's ECO code, e.g. `B90`. Chess.com's own code from the PGN is used when it has one, otherwise it is
+    /// worked out from the moves.
+    pub fn eco_code(&self) -> Option<String> {
+        normalize_eco(&self.eco_pgn).or_else(|| self.opening().map(|opening| opening.eco))
+    }
+}
+
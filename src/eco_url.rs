@@ -0,0 +1,164 @@
+//! Opening names read back out of Chess.com's `ECOUrl` links.
+//!
+//! The URL is the opening's name with spaces, colons and apostrophes turned into hyphens or dropped, sometimes followed
+//! by the moves that led to the position, e.g. `Sicilian-Defense-Open-Najdorf-Variation-6.Be3-e5`. That loses where the
+//! opening ends and the variation starts, so the split is made after the first word that usually ends an opening's
+//! name: `Opening`, `Defense`, `Game`, `Gambit`, `Attack` or `System`.
+
+use crate::GameData;
+
+const OPENINGS_PATH: &str = "/openings/";
+
+/// Words that end the opening part of a name, e.g. the `Defense` in `Sicilian Defense`.
+const FAMILY_ENDINGS: &[&str] = &["Opening", "Defense", "Defence", "Game", "Gambit", "Attack", "System"];
+
+/// Words that lose their apostrophe in URLs.
+const POSSESSIVES: &[(&str, &str)] = &[
+    ("Kings", "King's"),
+    ("Queens", "Queen's"),
+    ("Bishops", "Bishop's"),
+    ("Alekhines", "Alekhine's"),
+    ("Petrovs", "Petrov's"),
+    ("Philidors", "Philidor's"),
+    ("Owens", "Owen's"),
+    ("Birds", "Bird's"),
+    ("Larsens", "Larsen's"),
+];
+
+/// Names that are hyphenated anyway, so can't be told apart from the hyphens that replace spaces.
+const HYPHENATED: &[&str] = &[
+    "Caro-Kann", "Nimzo-Indian", "Bogo-Indian", "Nimzo-Larsen", "Smith-Morra", "Mieses-Kotroc", "Max-Lange",
+    "Semi-Slav", "Semi-Tarrasch", "Colle-Zukertort", "Fajarowicz-Richter", "Ponziani-Steinitz",
+];
+
+/// An opening as Chess.com names it in a game's [eco_url](GameData::eco_url).
+///
+/// ```rust
+/// use hikaru::EcoUrl;
+///
+/// let url = EcoUrl::parse("https://www.chess.com/openings/Sicilian-Defense-Open-Najdorf-Variation-6.Be3-e5").unwrap();
+/// assert_eq!(url.opening_name, "Sicilian Defense");
+/// assert_eq!(url.opening_slug, "Sicilian-Defense");
+/// assert_eq!(url.variation.as_deref(), Some("Open Najdorf Variation"));
+/// assert_eq!(url.variation_slug.as_deref(), Some("Open-Najdorf-Variation"));
+/// assert_eq!(url.moves, vec!["6.Be3", "e5"]);
+/// assert_eq!(url.name(), "Sicilian Defense: Open Najdorf Variation");
+///
+/// let url = EcoUrl::parse("https://www.chess.com/openings/Caro-Kann-Defense").unwrap();
+/// assert_eq!(url.opening_name, "Caro-Kann Defense");
+/// assert_eq!(url.variation, None);
+///
+/// let url = EcoUrl::parse("https://www.chess.com/openings/Queens-Pawn-Opening-1...d5").unwrap();
+/// assert_eq!(url.name(), "Queen's Pawn Opening");
+/// assert_eq!(url.moves, vec!["1...d5"]);
+///
+/// let url = EcoUrl::parse("https://www.chess.com/openings/Ruy-Lopez-Opening-Morphy-Defense-4.Ba4-Nf6-5.O-O").unwrap();
+/// assert_eq!(url.opening_name, "Ruy Lopez Opening");
+/// assert_eq!(url.moves, vec!["4.Ba4", "Nf6", "5.O-O"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EcoUrl {
+    /// Everything after `/openings/`, e.g. `Sicilian-Defense-Open-Najdorf-Variation-6.Be3-e5`.
+    pub slug: String,
+    /// The opening without the variation, e.g. `Sicilian Defense`.
+    pub opening_name: String,
+    /// The opening's part of the slug, e.g. `Sicilian-Defense`.
+    pub opening_slug: String,
+    /// The variation, e.g. `Open Najdorf Variation`, if the URL names one.
+    pub variation: Option<String>,
+    /// The variation's part of the slug, e.g. `Open-Najdorf-Variation`.
+    pub variation_slug: Option<String>,
+    /// The moves at the end of the URL, numbered the way Chess.com writes them, e.g. `6.Be3` and `e5`. Empty for most
+    /// URLs.
+    pub moves: Vec<String>,
+}
+
+impl EcoUrl {
+    /// Reads a `https://www.chess.com/openings/...` URL. `None` for anything else, including the empty URLs of games
+    /// that didn't start from the standard position.
+    pub fn parse(url: &str) -> Option<EcoUrl> {
+        let path = url.split(['?', '#']).next().unwrap_or_default();
+        let slug = path.get(path.find(OPENINGS_PATH)? + OPENINGS_PATH.len()..)?.trim_end_matches('/');
+        let words: Vec<&str> = slug.split('-').filter(|word| !word.is_empty()).collect();
+        let name_words = words.iter().position(|word| is_move(word)).unwrap_or(words.len());
+        if name_words == 0 {
+            return None;
+        }
+        let (name, moves) = words.split_at(name_words);
+        let family_words = name.iter()
+            .position(|word| FAMILY_ENDINGS.contains(word))
+            .map_or(name.len(), |index| index + 1);
+        let (family, variation) = name.split_at(family_words);
+        Some(EcoUrl {
+            slug: slug.to_string(),
+            opening_name: readable(family),
+            opening_slug: family.join("-"),
+            variation: Some(readable(variation)).filter(|_| !variation.is_empty()),
+            variation_slug: Some(variation.join("-")).filter(|_| !variation.is_empty()),
+            moves: castling_rejoined(moves),
+        })
+    }
+
+    /// The full name, in the same form as [Opening::name](crate::Opening::name), e.g.
+    /// `Sicilian Defense: Open Najdorf Variation`.
+    pub fn name(&self) -> String {
+        match &self.variation {
+            Some(variation) => format!("{}: {}", self.opening_name, variation),
+            None => self.opening_name.clone(),
+        }
+    }
+}
+
+/// Whether a slug word starts the moves, e.g. `6.Be3` or `1...d5`.
+fn is_move(word: &str) -> bool {
+    let digits = word.chars().take_while(char::is_ascii_digit).count();
+    digits > 0 && word[digits..].starts_with('.')
+}
+
+/// Puts castling back together after splitting on hyphens, e.g. `5.O-O`.
+fn castling_rejoined(words: &[&str]) -> Vec<String> {
+    let mut moves: Vec<String> = vec![];
+    for &word in words {
+        match moves.last_mut() {
+            Some(last) if last.ends_with('O') && word.starts_with('O') => {
+                last.push('-');
+                last.push_str(word);
+            }
+            _ => moves.push(word.to_string()),
+        }
+    }
+    moves
+}
+
+/// Turns slug words back into a name, with spaces, apostrophes and the hyphens that belong there.
+fn readable(words: &[&str]) -> String {
+    let mut name = String::new();
+    let mut index = 0;
+    while index < words.len() {
+        if !name.is_empty() {
+            name.push(' ');
+        }
+        let pair = words.get(index + 1).map(|next| format!("{}-{}", words[index], next));
+        match pair.filter(|pair| HYPHENATED.contains(&pair.as_str())) {
+            Some(pair) => {
+                name.push_str(&pair);
+                index += 2;
+            }
+            None => {
+                let word = words[index];
+                let possessive = POSSESSIVES.iter().find(|(plain, _)| *plain == word);
+                name.push_str(possessive.map_or(word, |(_, possessive)| possessive));
+                index += 1;
+            }
+        }
+    }
+    name
+}
+
+impl GameData {
+    /// The opening Chess.com named the game after, read from [eco_url](GameData::eco_url). Unlike
+    /// [GameData::opening] this needs no PGN, but only says what's in the URL.
+    pub fn parsed_eco_url(&self) -> Option<EcoUrl> {
+        EcoUrl::parse(&self.eco_url)
+    }
+}
@@ -0,0 +1,393 @@
+//! Runs games through any UCI engine, e.g. Stockfish, to get an evaluation and best move for every position.
+//!
+//! The engine has to be installed separately; Hikaru only talks to it over stdin/stdout.
+//!
+//! ```rust,no_run
+//! use hikaru::{Color, EngineConfig, GameData};
+//!
+//! # fn main() -> hikaru::Result<()> {
+//! let games = GameData::download_since("hikaru", 1_700_000_000)?;
+//! let config = EngineConfig::new("stockfish").depth(18).threads(4);
+//!
+//! let analysis = games[0].analyze(&config)?;
+//! println!("White blundered {} times", analysis.blunders(Color::White));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::board::{self, Board, Color};
+use crate::{Error, GameData, ParsedPgn, Result};
+
+/// Search depth used when neither [EngineConfig::depth] nor [EngineConfig::movetime] is set.
+const DEFAULT_DEPTH: u32 = 15;
+
+/// How to run the engine, and how long to think about each position.
+#[derive(Debug, Clone)]
+pub struct EngineConfig {
+    path: PathBuf,
+    depth: Option<u32>,
+    movetime: Option<Duration>,
+    options: Vec<(String, String)>,
+}
+
+impl EngineConfig {
+    /// `path` is the engine executable. A bare name like `"stockfish"` is looked up on the `PATH`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        EngineConfig {
+            path: path.into(),
+            depth: None,
+            movetime: None,
+            options: vec![],
+        }
+    }
+
+    /// Searches each position to this depth.
+    pub fn depth(mut self, depth: u32) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    /// Searches each position for this long. If a depth is also set, the search stops at whichever comes first.
+    pub fn movetime(mut self, movetime: Duration) -> Self {
+        self.movetime = Some(movetime);
+        self
+    }
+
+    pub fn threads(self, threads: u32) -> Self {
+        self.option("Threads", threads.to_string())
+    }
+
+    /// Size of the engine's hash table, in MB.
+    pub fn hash(self, megabytes: u32) -> Self {
+        self.option("Hash", megabytes.to_string())
+    }
+
+    /// Sets any other UCI option understood by the engine.
+    pub fn option(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.options.push((name.into(), value.into()));
+        self
+    }
+
+    fn go_command(&self) -> String {
+        let mut go = "go".to_string();
+        match (self.depth, self.movetime) {
+            (None, None) => go.push_str(&format!(" depth {}", DEFAULT_DEPTH)),
+            (depth, movetime) => {
+                if let Some(depth) = depth {
+                    go.push_str(&format!(" depth {}", depth));
+                }
+                if let Some(movetime) = movetime {
+                    go.push_str(&format!(" movetime {}", movetime.as_millis()));
+                }
+            }
+        }
+        go
+    }
+}
+
+/// An evaluation, always from White's point of view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Score {
+    /// Positive when White is better.
+    Centipawns(i32),
+    /// Forced mate for `winner` in `moves` moves. `moves` is 0 when the position is already checkmate.
+    Mate { winner: Color, moves: u32 },
+}
+
+impl Score {
+    /// Converts from the side to move's point of view, which is how UCI engines report scores.
+    fn from_side_to_move(kind: &str, value: i32, turn: Color) -> Option<Score> {
+        let white = if turn == Color::White { 1 } else { -1 };
+        match kind {
+            "cp" => Some(Score::Centipawns(value * white)),
+            "mate" => {
+                // "mate 0" means the side to move is already mated
+                let winner = if value > 0 { turn } else { !turn };
+                Some(Score::Mate { winner, moves: value.unsigned_abs() })
+            }
+            _ => None,
+        }
+    }
+
+    /// Chance of winning for `color`, between -1 (lost) and 1 (won), using the same curve as lichess.
+    fn winning_chances(self, color: Color) -> f64 {
+        let white = match self {
+            Score::Centipawns(cp) => 2.0 / (1.0 + (-0.003_682_08 * f64::from(cp)).exp()) - 1.0,
+            Score::Mate { winner: Color::White, .. } => 1.0,
+            Score::Mate { winner: Color::Black, .. } => -1.0,
+        };
+        if color == Color::White { white } else { -white }
+    }
+
+    /// Centipawns for `color`, with mates and large advantages capped at ±1000.
+    fn capped_centipawns(self, color: Color) -> i32 {
+        let white = match self {
+            Score::Centipawns(cp) => cp.clamp(-1000, 1000),
+            Score::Mate { winner: Color::White, .. } => 1000,
+            Score::Mate { winner: Color::Black, .. } => -1000,
+        };
+        if color == Color::White { white } else { -white }
+    }
+}
+
+/// The engine's verdict on a single position.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Evaluation {
+    pub score: Score,
+    /// The engine's best move in UCI notation, or `None` if the game is over.
+    pub best_move: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Classification {
+    Inaccuracy,
+    Mistake,
+    Blunder,
+}
+
+impl Classification {
+    /// Classifies a move by how much it dropped the mover's winning chances (on a -1 to 1 scale), using lichess' thresholds.
+    fn from_drop(drop: f64) -> Option<Classification> {
+        if drop >= 0.3 {
+            Some(Classification::Blunder)
+        } else if drop >= 0.2 {
+            Some(Classification::Mistake)
+        } else if drop >= 0.1 {
+            Some(Classification::Inaccuracy)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MoveAnalysis {
+    /// Half-move number, starting at 1.
+    pub ply: usize,
+    pub color: Color,
+    pub san: String,
+    pub uci: String,
+    /// Evaluation before the move was played, i.e. with best play.
+    pub eval_before: Score,
+    /// Evaluation after the move was played.
+    pub eval_after: Score,
+    /// What the engine would have played instead, in UCI notation.
+    pub best_move: Option<String>,
+    /// How many centipawns the move gave away, capped at 1000 per move.
+    pub centipawn_loss: u32,
+    /// `None` for moves that weren't an inaccuracy or worse.
+    pub classification: Option<Classification>,
+}
+
+/// Per-move evaluations for a whole game.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GameAnalysis {
+    pub moves: Vec<MoveAnalysis>,
+}
+
+impl GameAnalysis {
+    fn count(&self, color: Color, classification: Classification) -> usize {
+        self.moves.iter()
+            .filter(|mv| mv.color == color && mv.classification == Some(classification))
+            .count()
+    }
+
+    pub fn inaccuracies(&self, color: Color) -> usize {
+        self.count(color, Classification::Inaccuracy)
+    }
+
+    pub fn mistakes(&self, color: Color) -> usize {
+        self.count(color, Classification::Mistake)
+    }
+
+    pub fn blunders(&self, color: Color) -> usize {
+        self.count(color, Classification::Blunder)
+    }
+
+    /// Average centipawn loss per move for one side. 0 if that side made no moves.
+    pub fn average_centipawn_loss(&self, color: Color) -> f64 {
+        let losses: Vec<u32> = self.moves.iter().filter(|mv| mv.color == color).map(|mv| mv.centipawn_loss).collect();
+        if losses.is_empty() {
+            0.0
+        } else {
+            f64::from(losses.iter().sum::<u32>()) / losses.len() as f64
+        }
+    }
+}
+
+/// A running UCI engine. The process is shut down when this is dropped.
+#[derive(Debug)]
+pub struct Engine {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    config: EngineConfig,
+}
+
+impl Engine {
+    /// Starts the engine and waits for it to finish the UCI handshake.
+    pub fn spawn(config: &EngineConfig) -> Result<Engine> {
+        let mut child = Command::new(&config.path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let stdin = child.stdin.take().ok_or_else(|| Error::Engine("couldn't open the engine's stdin".into()))?;
+        let stdout = child.stdout.take().ok_or_else(|| Error::Engine("couldn't open the engine's stdout".into()))?;
+
+        let mut engine = Engine { child, stdin, stdout: BufReader::new(stdout), config: config.clone() };
+        engine.send("uci")?;
+        engine.read_until("uciok")?;
+        for (name, value) in config.options.clone() {
+            engine.send(&format!("setoption name {} value {}", name, value))?;
+        }
+        engine.send("isready")?;
+        engine.read_until("readyok")?;
+        Ok(engine)
+    }
+
+    fn send(&mut self, command: &str) -> Result<()> {
+        writeln!(self.stdin, "{}", command)?;
+        self.stdin.flush()?;
+        Ok(())
+    }
+
+    /// Reads lines until one starts with `prefix`, returning everything read (including that line).
+    fn read_until(&mut self, prefix: &str) -> Result<Vec<String>> {
+        let mut lines = vec![];
+        loop {
+            let mut line = String::new();
+            if self.stdout.read_line(&mut line)? == 0 {
+                return Err(Error::Engine(format!("engine exited while waiting for {}", prefix)));
+            }
+            let done = line.starts_with(prefix);
+            lines.push(line.trim_end().to_string());
+            if done {
+                return Ok(lines);
+            }
+        }
+    }
+
+    /// Evaluates the position reached by playing `moves` (UCI) from `start_fen`. `board` is that final position.
+    fn evaluate(&mut self, start_fen: &str, moves: &[String], board: &Board) -> Result<Evaluation> {
+        if board.legal_moves().is_empty() {
+            let score = if board.is_check() {
+                Score::Mate { winner: !board.turn(), moves: 0 }
+            } else {
+                Score::Centipawns(0)
+            };
+            return Ok(Evaluation { score, best_move: None });
+        }
+
+        let mut position = format!("position fen {}", start_fen);
+        if !moves.is_empty() {
+            position.push_str(" moves ");
+            position.push_str(&moves.join(" "));
+        }
+        self.send(&position)?;
+        let go = self.config.go_command();
+        self.send(&go)?;
+
+        let mut score = None;
+        let mut best_move = None;
+        for line in self.read_until("bestmove")? {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            match tokens.first() {
+                Some(&"info") => {
+                    // Only the principal variation counts, in case MultiPV is set
+                    let multipv = tokens.iter().position(|&t| t == "multipv").and_then(|i| tokens.get(i + 1));
+                    if multipv.is_some_and(|&pv| pv != "1") {
+                        continue;
+                    }
+                    if let Some(index) = tokens.iter().position(|&t| t == "score") {
+                        let kind = tokens.get(index + 1).copied().unwrap_or("");
+                        let value = tokens.get(index + 2).and_then(|v| v.parse().ok());
+                        if let Some(value) = value {
+                            score = Score::from_side_to_move(kind, value, board.turn()).or(score);
+                        }
+                    }
+                }
+                Some(&"bestmove") => {
+                    best_move = tokens.get(1).filter(|&&mv| mv != "(none)").map(|mv| mv.to_string());
+                }
+                _ => {}
+            }
+        }
+
+        let score = score.ok_or_else(|| Error::Engine("engine didn't report a score".into()))?;
+        Ok(Evaluation { score, best_move })
+    }
+
+    /// Evaluates every position in the game and classifies each move.
+    pub fn analyze(&mut self, game: &GameData) -> Result<GameAnalysis> {
+        let pgn = game.pgn.as_deref().ok_or(Error::MissingPgn)?;
+        self.analyze_pgn(&ParsedPgn::parse(pgn))
+    }
+
+    pub(crate) fn analyze_pgn(&mut self, pgn: &ParsedPgn) -> Result<GameAnalysis> {
+        let start = board::starting_position(pgn)?;
+        let replay = board::replay(&start, pgn)?;
+        let chess960 = start.needs_chess960() || pgn.tags.get("Variant").is_some_and(|v| v == "Chess960");
+        let start_fen = start.to_fen();
+
+        self.send(&format!("setoption name UCI_Chess960 value {}", chess960))?;
+        self.send("ucinewgame")?;
+        self.send("isready")?;
+        self.read_until("readyok")?;
+
+        let mut uci_moves: Vec<String> = vec![];
+        let mut before = self.evaluate(&start_fen, &uci_moves, &start)?;
+        let mut moves = vec![];
+
+        for (ply, (board, mv)) in replay.iter().enumerate() {
+            let color = board.turn();
+            let san = board.san(mv);
+            let uci = board.uci(mv, chess960);
+            uci_moves.push(uci.clone());
+
+            let after_board = board.played(mv);
+            let after = self.evaluate(&start_fen, &uci_moves, &after_board)?;
+
+            let drop = before.score.winning_chances(color) - after.score.winning_chances(color);
+            let centipawn_loss = (before.score.capped_centipawns(color) - after.score.capped_centipawns(color)).max(0) as u32;
+
+            moves.push(MoveAnalysis {
+                ply: ply + 1,
+                color,
+                san,
+                uci,
+                eval_before: before.score,
+                eval_after: after.score,
+                best_move: before.best_move.clone(),
+                centipawn_loss,
+                classification: Classification::from_drop(drop),
+            });
+            before = after;
+        }
+
+        Ok(GameAnalysis { moves })
+    }
+}
+
+impl Drop for Engine {
+    fn drop(&mut self) {
+        let _ = self.send("quit");
+        let _ = self.child.wait();
+    }
+}
+
+impl GameData {
+    /// Starts the engine described by `config` and analyzes this game with it.
+    ///
+    /// Starting an engine takes a moment, so when analyzing many games, [Engine::spawn] one and reuse it with [Engine::analyze].
+    pub fn analyze(&self, config: &EngineConfig) -> Result<GameAnalysis> {
+        Engine::spawn(config)?.analyze(self)
+    }
+}
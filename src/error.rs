@@ -0,0 +1,233 @@
+use std::fmt;
+
+use reqwest::StatusCode;
+
+/// Everything that can go wrong while talking to the Chess.com API.
+///
+/// Some variants only exist with the feature they come from (e.g. `Csv` with `csv`), and more may be added, so matches
+/// need a wildcard arm.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The request couldn't be sent, or the response couldn't be read.
+    Network(reqwest::Error),
+    /// The API responded with an error status (other than `429 Too Many Requests`, which is [Error::RateLimited]).
+    Status { url: String, status: StatusCode },
+    /// The API responded, but the JSON didn't match what Hikaru expects.
+    Parse(serde_json::Error),
+    /// Chess.com doesn't know this username.
+    UserNotFound(String),
+    /// Not something that could be a Chess.com username. See [Username](crate::Username).
+    InvalidUsername(String),
+    /// Chess.com responded with `429 Too Many Requests`.
+    RateLimited,
+    /// A header configured on the [ClientBuilder](crate::ClientBuilder) isn't a valid HTTP header value.
+    InvalidHeader(String),
+    /// Reading from or writing to a file or process failed.
+    Io(std::io::Error),
+    /// A UCI engine misbehaved, e.g. it exited early or never reported a score.
+    Engine(String),
+    /// The game has no PGN, so there are no moves to work with.
+    MissingPgn,
+    /// A move in the PGN isn't legal in the position it was played in.
+    IllegalMove(String),
+    /// A FEN couldn't be parsed.
+    InvalidFen(String),
+    /// A string of TCN-encoded moves couldn't be decoded.
+    InvalidTcn(String),
+    /// A time control isn't in any of the formats Chess.com uses.
+    InvalidTimeControl(String),
+    /// A URL isn't a link to a Chess.com game.
+    InvalidGameUrl(String),
+    /// A club, tournament or country ID has characters that can't be part of one, e.g. a `/`.
+    InvalidId(String),
+    /// A game in a monthly archive didn't match what Hikaru expects. The rest of the month could still be read, see
+    /// [DownloadOptions::skip_invalid_games](crate::DownloadOptions::skip_invalid_games).
+    GameParse(GameParseError),
+    /// An export was asked for a column that `GameData` doesn't have.
+    UnknownColumn(String),
+    /// Writing CSV failed.
+    #[cfg(feature = "csv")]
+    Csv(::csv::Error),
+    /// Building an Arrow record batch failed.
+    #[cfg(feature = "arrow")]
+    Arrow(arrow::error::ArrowError),
+    /// Writing Parquet failed.
+    #[cfg(feature = "arrow")]
+    Parquet(parquet::errors::ParquetError),
+    /// Building a Polars data frame failed.
+    #[cfg(feature = "polars")]
+    Polars(polars::error::PolarsError),
+    /// Reading or writing the SQLite database failed.
+    #[cfg(feature = "sqlite")]
+    Sqlite(rusqlite::Error),
+    /// There is no blocking HTTP client in the browser, so a blocking [Client](crate::Client) needs a
+    /// [transport](crate::ClientBuilder::transport). Use the [AsyncClient](crate::AsyncClient) to reach the API.
+    #[cfg(target_arch = "wasm32")]
+    NoTransport,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// A game that couldn't be read from a monthly archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameParseError {
+    /// The API URL of the archive.
+    pub archive: String,
+    /// Where the game is in the archive, counting from 0.
+    pub index: usize,
+    /// The game's URL, if it got as far as having one.
+    pub game_url: Option<String>,
+    /// What serde_json made of it.
+    pub message: String,
+}
+
+impl fmt::Display for GameParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "couldn't parse game {} of {}", self.index, self.archive)?;
+        if let Some(game_url) = &self.game_url {
+            write!(f, " ({})", game_url)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl std::error::Error for GameParseError {}
+
+impl Error {
+    /// Turns a 404 into [`Error::UserNotFound`]. Only makes sense for endpoints that are keyed on a username.
+    pub(crate) fn for_user(self, user: &str) -> Self {
+        match self {
+            Error::Network(err) if err.status() == Some(StatusCode::NOT_FOUND) => Error::UserNotFound(user.into()),
+            Error::Status { status: StatusCode::NOT_FOUND, .. } => Error::UserNotFound(user.into()),
+            err => err,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Network(err) => write!(f, "network error: {}", err),
+            Error::Status { url, status } => write!(f, "{} responded with {}", url, status),
+            Error::Parse(err) => write!(f, "couldn't parse response: {}", err),
+            Error::UserNotFound(user) => write!(f, "user not found: {}", user),
+            Error::InvalidUsername(user) => write!(f, "invalid username: {:?}", user),
+            Error::RateLimited => write!(f, "rate limited by the Chess.com API"),
+            Error::InvalidHeader(name) => write!(f, "invalid value for header {}", name),
+            Error::Io(err) => write!(f, "I/O error: {}", err),
+            Error::Engine(message) => write!(f, "engine error: {}", message),
+            Error::MissingPgn => write!(f, "the game has no PGN"),
+            Error::IllegalMove(san) => write!(f, "illegal move: {}", san),
+            Error::InvalidFen(fen) => write!(f, "invalid FEN: {}", fen),
+            Error::InvalidTcn(tcn) => write!(f, "invalid TCN: {}", tcn),
+            Error::InvalidTimeControl(time_control) => write!(f, "invalid time control: {}", time_control),
+            Error::InvalidGameUrl(url) => write!(f, "not a Chess.com game URL: {}", url),
+            Error::InvalidId(id) => write!(f, "invalid ID: {:?}", id),
+            Error::GameParse(err) => err.fmt(f),
+            Error::UnknownColumn(column) => write!(f, "unknown column: {}", column),
+            #[cfg(feature = "csv")]
+            Error::Csv(err) => write!(f, "CSV error: {}", err),
+            #[cfg(feature = "arrow")]
+            Error::Arrow(err) => write!(f, "Arrow error: {}", err),
+            #[cfg(feature = "arrow")]
+            Error::Parquet(err) => write!(f, "Parquet error: {}", err),
+            #[cfg(feature = "polars")]
+            Error::Polars(err) => write!(f, "Polars error: {}", err),
+            #[cfg(feature = "sqlite")]
+            Error::Sqlite(err) => write!(f, "SQLite error: {}", err),
+            #[cfg(target_arch = "wasm32")]
+            Error::NoTransport => write!(f, "no HTTP transport for the blocking client on this platform"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Network(err) => Some(err),
+            Error::Parse(err) => Some(err),
+            Error::Io(err) => Some(err),
+            Error::GameParse(err) => Some(err),
+            #[cfg(feature = "csv")]
+            Error::Csv(err) => Some(err),
+            #[cfg(feature = "arrow")]
+            Error::Arrow(err) => Some(err),
+            #[cfg(feature = "arrow")]
+            Error::Parquet(err) => Some(err),
+            #[cfg(feature = "polars")]
+            Error::Polars(err) => Some(err),
+            #[cfg(feature = "sqlite")]
+            Error::Sqlite(err) => Some(err),
+            Error::Status { .. }
+            | Error::UserNotFound(_)
+            | Error::InvalidUsername(_)
+            | Error::RateLimited
+            | Error::InvalidHeader(_)
+            | Error::Engine(_)
+            | Error::MissingPgn
+            | Error::IllegalMove(_)
+            | Error::InvalidFen(_)
+            | Error::InvalidTcn(_)
+            | Error::InvalidTimeControl(_)
+            | Error::InvalidGameUrl(_)
+            | Error::InvalidId(_)
+            | Error::UnknownColumn(_) => None,
+            #[cfg(target_arch = "wasm32")]
+            Error::NoTransport => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        Error::Network(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Parse(err)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+#[cfg(feature = "csv")]
+impl From<::csv::Error> for Error {
+    fn from(err: ::csv::Error) -> Self {
+        Error::Csv(err)
+    }
+}
+
+#[cfg(feature = "arrow")]
+impl From<arrow::error::ArrowError> for Error {
+    fn from(err: arrow::error::ArrowError) -> Self {
+        Error::Arrow(err)
+    }
+}
+
+#[cfg(feature = "arrow")]
+impl From<parquet::errors::ParquetError> for Error {
+    fn from(err: parquet::errors::ParquetError) -> Self {
+        Error::Parquet(err)
+    }
+}
+
+#[cfg(feature = "polars")]
+impl From<polars::error::PolarsError> for Error {
+    fn from(err: polars::error::PolarsError) -> Self {
+        Error::Polars(err)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for Error {
+    fn from(err: rusqlite::Error) -> Self {
+        Error::Sqlite(err)
+    }
+}
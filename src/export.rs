@@ -0,0 +1,106 @@
+//! Writing games to files other tools can read.
+
+use std::io::{BufWriter, Write};
+
+use crate::{GameData, Result};
+
+/// Quotes a PGN tag value, escaping backslashes and double quotes as the standard requires.
+pub(crate) fn tag(name: &str, value: &str) -> String {
+    format!("[{} \"{}\"]", name, value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// The name of an enum variant as serde spells it, e.g. `"timevsinsufficient"`.
+#[cfg(any(feature = "arrow", feature = "polars"))]
+pub(crate) fn variant<T: serde::Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => unreachable!("only unit variants are exported"),
+    }
+}
+
+/// Headers for a game Chess.com sent without a PGN. There are no moves to go with them, only the result.
+pub(crate) fn synthesized_pgn(game: &GameData) -> String {
+    let result = game.standard_result();
+    let mut tags = vec![
+        tag("Event", "?"),
+        tag("Site", "Chess.com"),
+        tag("Date", &game.date),
+        tag("Round", "-"),
+        tag("White", &game.white_username),
+        tag("Black", &game.black_username),
+        tag("Result", result),
+        tag("WhiteElo", &game.white_rating.to_string()),
+        tag("BlackElo", &game.black_rating.to_string()),
+        tag("TimeControl", &game.time_control),
+    ];
+    if !game.eco_pgn.is_empty() {
+        tags.push(tag("ECO", &game.eco_pgn));
+    }
+    tags.push(tag("Link", &game.game_url));
+    format!("{}\n\n{}", tags.join("\n"), result)
+}
+
+impl GameData {
+    /// Writes games as newline-delimited JSON, one game per line, as they come out of `games`.
+    ///
+    /// Paired with [GameData::download_iter], nothing more than a single month is ever held in memory, however many games
+    /// the user has played. Stops at the first error, whether from `games` or from `writer`. Returns how many games were written.
+    ///
+    /// ```rust,no_run
+    /// use std::fs::File;
+    /// use hikaru::GameData;
+    ///
+    /// # fn main() -> hikaru::Result<()> {
+    /// let written = GameData::write_ndjson(GameData::download_iter("hikaru")?, File::create("hikaru.ndjson")?)?;
+    ///
+    /// // Games already in memory work too
+    /// let games = GameData::download(vec!["hikaru"])?;
+    /// GameData::write_ndjson(games.into_iter().map(Ok), File::create("hikaru.ndjson")?)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_ndjson<I, W>(games: I, writer: W) -> Result<usize>
+    where
+        I: IntoIterator<Item = Result<GameData>>,
+        W: Write,
+    {
+        let mut writer = BufWriter::new(writer);
+        let mut written = 0;
+        for game in games {
+            // Serializing separately keeps write failures as `Error::Io` rather than wrapped up in a serde_json error
+            writeln!(writer, "{}", serde_json::to_string(&game?)?)?;
+            written += 1;
+        }
+        writer.flush()?;
+        Ok(written)
+    }
+
+    /// Writes games as a single multi-game PGN file, ready to import into SCID, ChessBase or a lichess study.
+    ///
+    /// Games keep the PGN Chess.com sent, moves, clock times and all. For the rare game without one, the headers are
+    /// rebuilt from what is known about it (players, ratings, date, result), with no moves.
+    ///
+    /// ```rust,no_run
+    /// use std::fs::File;
+    /// use hikaru::GameData;
+    ///
+    /// # fn main() -> hikaru::Result<()> {
+    /// let games = GameData::download(vec!["hikaru"])?;
+    /// GameData::export_pgn(&games, File::create("hikaru.pgn")?)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn export_pgn<W: Write>(games: &[GameData], writer: W) -> Result<()> {
+        let mut writer = BufWriter::new(writer);
+        for game in games {
+            let pgn = match game.pgn.as_deref() {
+                Some(pgn) if !pgn.trim().is_empty() => pgn.replace("\r\n", "\n").trim().to_string(),
+                _ => synthesized_pgn(game),
+            };
+            // Games are separated by a blank line
+            writeln!(writer, "{}\n", pgn)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+//! Positions written in FEN, like the final position Chess.com gives for every game.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::board::{self, Board, Role};
+use crate::{Color, Error, GameData, ParsedPgn, Result};
+
+/// A position read from FEN, checked to be one that can occur on a board: one king each, no pawns on the first or last
+/// rank, and the side that just moved not left in check.
+///
+/// ```rust
+/// use hikaru::{Color, Fen};
+///
+/// // Fool's mate
+/// let fen: Fen = "rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3".parse()?;
+/// assert_eq!(fen.side_to_move(), Color::White);
+/// assert!(fen.is_checkmate_position());
+/// assert_eq!(fen.material_count().white, 39);
+///
+/// assert!("8/8/8/8/8/8/8/8 w - - 0 1".parse::<Fen>().is_err());
+/// # Ok::<(), hikaru::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Fen {
+    board: Board,
+}
+
+/// Each side's material in pawns, counting knights and bishops as 3, rooks as 5 and queens as 9.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Material {
+    pub white: u32,
+    pub black: u32,
+}
+
+impl Material {
+    /// How far ahead `color` is, negative if behind.
+    pub fn advantage(&self, color: Color) -> i32 {
+        let difference = self.white as i32 - self.black as i32;
+        if color == Color::White { difference } else { -difference }
+    }
+}
+
+impl Fen {
+    pub fn side_to_move(&self) -> Color {
+        self.board.turn()
+    }
+
+    pub fn material_count(&self) -> Material {
+        let mut material = Material::default();
+        for (_, piece) in self.board.pieces() {
+            match piece.color {
+                Color::White => material.white += piece.role.value(),
+                Color::Black => material.black += piece.role.value(),
+            }
+        }
+        material
+    }
+
+    /// Whether the side to move is in check.
+    pub fn is_check(&self) -> bool {
+        self.board.is_check()
+    }
+
+    /// Whether the side to move has been checkmated.
+    pub fn is_checkmate_position(&self) -> bool {
+        self.board.is_check() && self.board.legal_moves().is_empty()
+    }
+
+    /// Whether the side to move has no legal moves but isn't in check.
+    pub fn is_stalemate_position(&self) -> bool {
+        !self.board.is_check() && self.board.legal_moves().is_empty()
+    }
+
+    /// Whether only the kings are left, plus at most one knight or bishop, so neither side can checkmate.
+    pub fn is_insufficient_material(&self) -> bool {
+        let others: Vec<Role> = self.board.pieces()
+            .map(|(_, piece)| piece.role)
+            .filter(|&role| role != Role::King)
+            .collect();
+        others.iter().all(|&role| role == Role::Knight || role == Role::Bishop) && others.len() <= 1
+    }
+}
+
+impl FromStr for Fen {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let invalid = || Error::InvalidFen(text.into());
+        let board = Board::from_fen(text).ok_or_else(invalid)?;
+        for color in [Color::White, Color::Black] {
+            let kings = board.pieces().filter(|(_, piece)| piece.color == color && piece.role == Role::King).count();
+            if kings != 1 {
+                return Err(invalid());
+            }
+        }
+        let pawn_on_edge = board.pieces().any(|(square, piece)| piece.role == Role::Pawn && !(8..56).contains(&square));
+        let mover = !board.turn();
+        let mover_in_check = board.king_square(mover).is_some_and(|king| board.is_attacked(king, board.turn()));
+        if pawn_on_edge || mover_in_check {
+            return Err(invalid());
+        }
+        Ok(Fen { board })
+    }
+}
+
+impl fmt::Display for Fen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.board.to_fen())
+    }
+}
+
+impl ParsedPgn {
+    /// Every position in the game, from the start (its `FEN` tag, if it has one) to the position after the last move.
+    ///
+    /// ```rust
+    /// use hikaru::ParsedPgn;
+    ///
+    /// let pgn = ParsedPgn::parse("1. e4 e5 2. Nf3 *");
+    /// let positions = pgn.positions()?;
+    /// assert_eq!(positions.len(), 4);
+    /// assert_eq!(positions[3].to_string(), "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2");
+    /// # Ok::<(), hikaru::Error>(())
+    /// ```
+    pub fn positions(&self) -> Result<Vec<Fen>> {
+        let start = board::starting_position(self)?;
+        let replay = board::replay(&start, self)?;
+        let last = match replay.last() {
+            Some((board, mv)) => board.played(mv),
+            None => start,
+        };
+        Ok(replay.into_iter()
+            .map(|(board, _)| Fen { board })
+            .chain(std::iter::once(Fen { board: last }))
+            .collect())
+    }
+}
+
+impl GameData {
+    /// The position the game ended in, read from [GameData::fen].
+    pub fn final_position(&self) -> Result<Fen> {
+        self.fen.parse()
+    }
+
+    /// Replays the game's moves, returning every position from the start to the end. See [ParsedPgn::positions].
+    pub fn positions(&self) -> Result<Vec<Fen>> {
+        let pgn = self.pgn.as_deref().ok_or(Error::MissingPgn)?;
+        ParsedPgn::parse(pgn).positions()
+    }
+}
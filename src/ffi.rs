@@ -0,0 +1,94 @@
+//! A C API for embedding the downloader in other languages' programs, enabled with the `ffi` feature. The declarations
+//! are in `include/hikaru.h`, and `cargo build --release --features ffi` builds the libraries to link against:
+//! `target/release/libhikaru.a` and `libhikaru.so` (`.dylib` on macOS, `hikaru.dll` on Windows).
+//!
+//! ```c
+//! int error;
+//! char *games = hikaru_download_json("hikaru", &error);
+//! if (games) {
+//!     puts(games);
+//!     hikaru_string_free(games);
+//! } else {
+//!     fprintf(stderr, "download failed: %d\n", error);
+//! }
+//! ```
+
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use crate::{Error, GameData, Result};
+
+/// The download worked.
+pub const HIKARU_OK: c_int = 0;
+/// The username was a null pointer, not UTF-8, or not a valid Chess.com username.
+pub const HIKARU_INVALID_USERNAME: c_int = 1;
+/// There is no such user.
+pub const HIKARU_USER_NOT_FOUND: c_int = 2;
+/// Chess.com couldn't be reached, answered with an error, or kept rate limiting requests.
+pub const HIKARU_NETWORK_ERROR: c_int = 3;
+/// Anything else, e.g. a game that couldn't be read, or the TLS backend failing to start.
+pub const HIKARU_OTHER_ERROR: c_int = 4;
+
+fn error_code(err: &Error) -> c_int {
+    match err {
+        Error::InvalidUsername(_) => HIKARU_INVALID_USERNAME,
+        Error::UserNotFound(_) => HIKARU_USER_NOT_FOUND,
+        Error::Network(_) | Error::Status { .. } | Error::RateLimited => HIKARU_NETWORK_ERROR,
+        _ => HIKARU_OTHER_ERROR,
+    }
+}
+
+fn download_ndjson(user: &str) -> Result<CString> {
+    let games = GameData::download(vec![user])?;
+    let mut ndjson = vec![];
+    GameData::write_ndjson(games.into_iter().map(Ok), &mut ndjson)?;
+    // JSON escapes control characters, so there is never a NUL in the middle
+    Ok(CString::new(ndjson).unwrap_or_default())
+}
+
+/// Downloads every game `user` has played, oldest first, as newline-delimited JSON: one [GameData] object per line.
+///
+/// Returns a string to be freed with [hikaru_string_free], or null if the download failed. Either way, the outcome is
+/// written to `error` (unless it is null) as one of the `HIKARU_*` codes. A panic inside the library is caught and
+/// reported as [HIKARU_OTHER_ERROR], though the panic message is still printed to stderr.
+///
+/// # Safety
+///
+/// `user` must be null or point to a NUL-terminated string, and `error` must be null or point to an `int`.
+#[no_mangle]
+pub unsafe extern "C" fn hikaru_download_json(user: *const c_char, error: *mut c_int) -> *mut c_char {
+    let result = if user.is_null() {
+        Err(HIKARU_INVALID_USERNAME)
+    } else {
+        match CStr::from_ptr(user).to_str() {
+            // A panic mustn't unwind into the caller's frames, which would abort the whole program
+            Ok(user) => match panic::catch_unwind(AssertUnwindSafe(|| download_ndjson(user))) {
+                Ok(games) => games.map_err(|err| error_code(&err)),
+                Err(_) => Err(HIKARU_OTHER_ERROR),
+            },
+            Err(_) => Err(HIKARU_INVALID_USERNAME),
+        }
+    };
+    let (games, code) = match result {
+        Ok(games) => (games.into_raw(), HIKARU_OK),
+        Err(code) => (ptr::null_mut(), code),
+    };
+    if !error.is_null() {
+        *error = code;
+    }
+    games
+}
+
+/// Frees a string returned by [hikaru_download_json]. Does nothing if it is null.
+///
+/// # Safety
+///
+/// `string` must be null or have come from [hikaru_download_json], and mustn't be used or freed again afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn hikaru_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
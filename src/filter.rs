@@ -0,0 +1,98 @@
+//! Picking out games by their properties, e.g. rated blitz games against a particular opponent.
+
+use crate::date::year_month;
+use crate::{GameData, Rules, TimeClass};
+
+/// Which games to keep. Every condition has to hold; the defaults keep everything.
+///
+/// Used with [DownloadOptions::filter](crate::DownloadOptions::filter), a date range also decides which monthly
+/// archives are requested at all. Everything else is checked once the games have been downloaded.
+///
+/// ```rust,no_run
+/// use hikaru::{GameData, GameFilter, TimeClass};
+///
+/// # fn main() -> hikaru::Result<()> {
+/// let filter = GameFilter::new()
+///     .rated_only()
+///     .time_class(TimeClass::Blitz)
+///     .min_rating(2500);
+/// let games = GameData::download_filtered("hikaru", filter)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GameFilter {
+    rated_only: bool,
+    time_classes: Vec<TimeClass>,
+    rules: Vec<Rules>,
+    min_rating: Option<u32>,
+    max_rating: Option<u32>,
+    opponents: Vec<String>,
+    date_range: Option<(u32, u32)>,
+}
+
+impl GameFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Leaves out unrated games.
+    pub fn rated_only(mut self) -> Self {
+        self.rated_only = true;
+        self
+    }
+
+    /// Only keeps games of this time class. Calling it again allows more time classes.
+    pub fn time_class(mut self, time_class: TimeClass) -> Self {
+        self.time_classes.push(time_class);
+        self
+    }
+
+    /// Only keeps games of this variant. Calling it again allows more variants.
+    pub fn rules(mut self, rules: Rules) -> Self {
+        self.rules.push(rules);
+        self
+    }
+
+    /// Only keeps games where the player was rated at least this much.
+    pub fn min_rating(mut self, rating: u32) -> Self {
+        self.min_rating = Some(rating);
+        self
+    }
+
+    /// Only keeps games where the player was rated at most this much.
+    pub fn max_rating(mut self, rating: u32) -> Self {
+        self.max_rating = Some(rating);
+        self
+    }
+
+    /// Only keeps games against this opponent, ignoring case. Calling it again allows more opponents.
+    pub fn opponent(mut self, username: impl Into<String>) -> Self {
+        self.opponents.push(username.into());
+        self
+    }
+
+    /// Only keeps games that ended from `start` up to (but not including) `end`, both Unix timestamps.
+    pub fn date_range(mut self, start: u32, end: u32) -> Self {
+        self.date_range = Some((start, end));
+        self
+    }
+
+    /// Whether the game passes every condition.
+    pub fn matches(&self, game: &GameData) -> bool {
+        (!self.rated_only || game.rated)
+            && (self.time_classes.is_empty() || self.time_classes.contains(&game.time_class))
+            && (self.rules.is_empty() || self.rules.contains(&game.rules))
+            && self.min_rating.is_none_or(|rating| game.rating >= rating)
+            && self.max_rating.is_none_or(|rating| game.rating <= rating)
+            && (self.opponents.is_empty() || self.opponents.iter().any(|name| name.eq_ignore_ascii_case(&game.opponent_username)))
+            && self.date_range.is_none_or(|(start, end)| game.end_time >= start && game.end_time < end)
+    }
+
+    /// Whether an archive for `month` can have games in the date range.
+    pub(crate) fn includes_month(&self, month: (u16, u8)) -> bool {
+        self.date_range.is_none_or(|(start, end)| {
+            month >= year_month(start.into()) && month <= year_month(end.saturating_sub(1).into())
+        })
+    }
+}
@@ -0,0 +1,115 @@
+//! Chess.com game IDs, as found at the end of game URLs.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, GameData, Result};
+
+/// Whether a game was played live or by correspondence. Live and daily games are numbered separately, so the same
+/// number can be a different game of each kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GameKind {
+    Live,
+    Daily,
+}
+
+impl GameKind {
+    /// How URLs spell it, e.g. `"live"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GameKind::Live => "live",
+            GameKind::Daily => "daily",
+        }
+    }
+}
+
+impl fmt::Display for GameKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Identifies a game on Chess.com, e.g. for cross-referencing games from different sources or linking to them.
+///
+/// ```rust
+/// use hikaru::{GameId, GameKind};
+///
+/// let id = GameId::parse_url("https://www.chess.com/game/live/123456789")?;
+/// assert_eq!(id, GameId { kind: GameKind::Live, id: 123_456_789 });
+/// assert_eq!(id.url(), "https://www.chess.com/game/live/123456789");
+/// assert_eq!(id.analysis_url(), "https://www.chess.com/analysis/game/live/123456789");
+///
+/// // Older URLs put the kind first
+/// assert_eq!(GameId::parse_url("https://www.chess.com/daily/game/42")?.kind, GameKind::Daily);
+/// assert_eq!("live/123456789".parse::<GameId>()?, id);
+/// # Ok::<(), hikaru::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct GameId {
+    pub kind: GameKind,
+    pub id: u64,
+}
+
+impl GameId {
+    /// Reads the ID from a game's URL, e.g. `https://www.chess.com/game/live/123456789` or the older
+    /// `https://www.chess.com/live/game/123456789`. Query strings and fragments are ignored.
+    pub fn parse_url(url: &str) -> Result<GameId> {
+        let invalid = || Error::InvalidGameUrl(url.into());
+        let path = url.split(['?', '#']).next().unwrap_or_default();
+        let mut segments = path.trim_end_matches('/').rsplit('/');
+        let id = segments.next().and_then(|id| id.parse().ok()).ok_or_else(invalid)?;
+        let kind = match (segments.next(), segments.next()) {
+            (Some("live"), Some("game")) | (Some("game"), Some("live")) => GameKind::Live,
+            (Some("daily"), Some("game")) | (Some("game"), Some("daily")) => GameKind::Daily,
+            _ => return Err(invalid()),
+        };
+        Ok(GameId { kind, id })
+    }
+
+    /// The game's page on Chess.com, as the API links it.
+    pub fn url(&self) -> String {
+        format!("https://www.chess.com/game/{}/{}", self.kind, self.id)
+    }
+
+    /// The game in Chess.com's analysis board.
+    pub fn analysis_url(&self) -> String {
+        format!("https://www.chess.com/analysis/game/{}/{}", self.kind, self.id)
+    }
+}
+
+/// Formats as e.g. `live/123456789`.
+impl fmt::Display for GameId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.kind, self.id)
+    }
+}
+
+/// Parses either a URL or the `live/123456789` form [Display](fmt::Display) writes.
+impl FromStr for GameId {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let text = text.trim();
+        if text.contains("://") {
+            return GameId::parse_url(text);
+        }
+        let invalid = || Error::InvalidGameUrl(text.into());
+        let (kind, id) = text.split_once('/').ok_or_else(invalid)?;
+        let kind = match kind {
+            "live" => GameKind::Live,
+            "daily" => GameKind::Daily,
+            _ => return Err(invalid()),
+        };
+        Ok(GameId { kind, id: id.parse().map_err(|_| invalid())? })
+    }
+}
+
+impl GameData {
+    /// The game's ID, read from its URL. `None` for games from elsewhere, e.g. lichess.
+    pub fn id(&self) -> Option<GameId> {
+        GameId::parse_url(&self.game_url).ok()
+    }
+}
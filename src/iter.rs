@@ -0,0 +1,237 @@
+//! Lazy downloading: monthly archives are only requested once the iterator gets to them.
+
+use std::vec;
+
+use serde::{Deserialize, Serialize};
+
+use crate::date::{self, year_month};
+use crate::{Client, DownloadOptions, GameData, Result};
+
+/// An iterator over a user's games that downloads one monthly archive at a time, oldest first unless the
+/// [order](DownloadOptions::order) says otherwise.
+///
+/// The archive list is fetched when the iterator is created; each month is fetched when the previous one runs out.
+/// Stopping early (e.g. with `take`) skips the remaining requests entirely.
+///
+/// A month that fails to download is yielded as an `Err`, after which the iterator carries on with the next month.
+/// Likewise a game that can't be read is yielded as an [Error::GameParse](crate::Error::GameParse), and the rest of its month still comes through.
+///
+/// A long download can be stopped and picked up later with [GameIter::checkpoint] and [Client::resume].
+///
+/// If the month changes while iterating oldest first, the archive list is fetched again once the last month is done, and
+/// the newest archive is read again for the games that finished since.
+///
+/// ```rust,no_run
+/// # fn main() -> hikaru::Result<()> {
+/// let client = hikaru::Client::new();
+///
+/// // Only downloads the first month or two
+/// for game in client.games_iter("hikaru")?.take(100) {
+///     println!("{}", game?.game_url);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct GameIter {
+    client: Client,
+    user: String,
+    options: DownloadOptions,
+    archives: vec::IntoIter<String>,
+    month: vec::IntoIter<Result<GameData>>,
+    /// The month being read, or that just failed to download.
+    current: Option<String>,
+    last_end_time: Option<u32>,
+    last_game_url: Option<String>,
+    /// When resuming, where to pick up in the month that was being read.
+    resume_after: Option<ResumePoint>,
+    /// The month the iterator was created in, and whether the archive list has been fetched again since it changed.
+    started: (u16, u8),
+    refreshed: bool,
+}
+
+impl GameIter {
+    pub(crate) fn new(client: Client, user: &str, options: DownloadOptions, archives: Vec<String>) -> Self {
+        GameIter {
+            client,
+            user: user.into(),
+            options,
+            archives: archives.into_iter(),
+            month: vec![].into_iter(),
+            current: None,
+            last_end_time: None,
+            last_game_url: None,
+            resume_after: None,
+            started: year_month(date::now()),
+            refreshed: false,
+        }
+    }
+
+    /// Where the download has got to, to save and later hand to [Client::resume]. The month being read is downloaded
+    /// again when resuming, but only the games after the last one already yielded come through.
+    ///
+    /// A month that failed to download stays in the checkpoint until `next` is called again, so stopping at the error
+    /// and resuming later retries it.
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// let client = hikaru::Client::new();
+    /// let mut games = client.games_iter("hikaru")?;
+    /// for game in games.by_ref() {
+    ///     if game.is_err() {
+    ///         break;
+    ///     }
+    /// }
+    /// std::fs::write("checkpoint.json", serde_json::to_string(&games.checkpoint())?)?;
+    ///
+    /// // Later, perhaps in another process
+    /// let checkpoint = serde_json::from_str(&std::fs::read_to_string("checkpoint.json")?)?;
+    /// for game in client.resume(checkpoint) {
+    ///     println!("{}", game?.game_url);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn checkpoint(&self) -> DownloadCheckpoint {
+        DownloadCheckpoint {
+            user: self.user.clone(),
+            remaining: self.current.iter().chain(self.archives.as_slice()).cloned().collect(),
+            last_end_time: self.last_end_time,
+            last_game_url: self.last_game_url.clone(),
+        }
+    }
+
+    /// Fetches the archive list again and carries on from the month that was read last, for the games that finished
+    /// after the download started.
+    fn refresh(&mut self, previous: Option<String>) -> Result<()> {
+        let urls = self.client.get_game_month_urls(&self.user)?
+            .into_iter()
+            .filter(|url| self.options.includes_archive(url));
+        let urls: Vec<String> = match &previous {
+            Some(previous) => urls.skip_while(|url| url != previous).collect(),
+            None => urls.collect(),
+        };
+        self.resume_after = ResumePoint::new(previous, self.last_end_time, self.last_game_url.clone());
+        self.archives = urls.into_iter();
+        Ok(())
+    }
+
+    pub(crate) fn resuming(mut self, checkpoint: &DownloadCheckpoint) -> Self {
+        self.last_end_time = checkpoint.last_end_time;
+        self.last_game_url = checkpoint.last_game_url.clone();
+        self.resume_after = ResumePoint::new(
+            checkpoint.remaining.first().cloned(),
+            checkpoint.last_end_time,
+            checkpoint.last_game_url.clone(),
+        );
+        self
+    }
+}
+
+impl Iterator for GameIter {
+    type Item = Result<GameData>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(game) = self.month.next() {
+                if let Ok(game) = &game {
+                    self.last_end_time = Some(game.end_time);
+                    self.last_game_url = Some(game.game_url.clone());
+                }
+                return Some(game);
+            }
+
+            // Checked before taking the next archive, so a checkpoint still has it
+            if self.options.is_cancelled() {
+                return None;
+            }
+            let previous = self.current.take();
+            self.current = self.archives.next();
+            // Newest first, the games that finished since would come out of order, so they're left for next time
+            let rolled_over = year_month(date::now()) != self.started && !self.options.newest_first();
+            if self.current.is_none() && !self.refreshed && rolled_over {
+                self.refreshed = true;
+                if let Err(err) = self.refresh(previous) {
+                    return Some(Err(err));
+                }
+                self.current = self.archives.next();
+            }
+            let url = self.current.clone()?;
+            match self.client.get_month(&url) {
+                Ok(games) => {
+                    let mut games = self.options.game_data(&url, games, &self.user);
+                    let newest_first = self.options.newest_first();
+                    if newest_first {
+                        games.reverse();
+                    }
+                    if let Some(resume) = self.resume_after.take().filter(|resume| resume.month == url) {
+                        resume.skip_seen(&mut games, newest_first);
+                    }
+                    self.month = games.into_iter();
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+/// Where to pick up in a month that is read again: after the last game already yielded from it.
+#[derive(Debug)]
+struct ResumePoint {
+    month: String,
+    end_time: u32,
+    game_url: Option<String>,
+}
+
+impl ResumePoint {
+    fn new(month: Option<String>, end_time: Option<u32>, game_url: Option<String>) -> Option<Self> {
+        Some(ResumePoint { month: month?, end_time: end_time?, game_url })
+    }
+
+    /// Drops the games up to and including the last one yielded. If it isn't there (or the checkpoint predates game
+    /// URLs), falls back to dropping the games that ended by then, which can lose others that ended in the same second.
+    fn skip_seen(&self, games: &mut Vec<Result<GameData>>, newest_first: bool) {
+        let position = self.game_url.as_ref().and_then(|game_url| {
+            games.iter().position(|game| game.as_ref().is_ok_and(|game| game.game_url == *game_url))
+        });
+        match position {
+            Some(position) => {
+                games.drain(..=position);
+            }
+            None => {
+                let last = self.end_time;
+                let seen = |end_time: u32| if newest_first { end_time >= last } else { end_time <= last };
+                games.retain(|game| game.as_ref().map_or(true, |game| !seen(game.end_time)));
+            }
+        }
+    }
+}
+
+/// How far a [GameIter] got, from [GameIter::checkpoint]. Serializable, so it can be saved to a file and the download
+/// resumed with [Client::resume] after a crash or in another process.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DownloadCheckpoint {
+    pub user: String,
+    /// The archive URLs still to download, in the order they will be downloaded. The first one may have been partly read.
+    pub remaining: Vec<String>,
+    /// When the last game already yielded ended.
+    pub last_end_time: Option<u32>,
+    /// The last game already yielded. When resuming, it and the games before it in the first remaining month are
+    /// skipped. Checkpoints without it skip the games that ended by `last_end_time` (or after it,
+    /// [newest first](crate::DownloadOrder::NewestFirst)) instead.
+    #[serde(default)]
+    pub last_game_url: Option<String>,
+}
+
+impl Client {
+    /// Carries on a download from a [checkpoint](GameIter::checkpoint), without fetching the archive list again.
+    pub fn resume(&self, checkpoint: DownloadCheckpoint) -> GameIter {
+        self.resume_with(checkpoint, DownloadOptions::default())
+    }
+
+    /// Like [Client::resume], filtering games with `options`. The options aren't part of the checkpoint, so pass the
+    /// same ones the download started with.
+    pub fn resume_with(&self, checkpoint: DownloadCheckpoint, options: DownloadOptions) -> GameIter {
+        GameIter::new(self.configured(&options).into_owned(), &checkpoint.user, options, checkpoint.remaining.clone()).resuming(&checkpoint)
+    }
+}
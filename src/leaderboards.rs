@@ -0,0 +1,85 @@
+//! The top 50 players of each rating category, from `/pub/leaderboards`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Client, Result};
+
+/// The leaderboards for every category. Categories Chess.com leaves out of the response are empty.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Leaderboards {
+    pub daily: Vec<LeaderboardEntry>,
+    pub daily960: Vec<LeaderboardEntry>,
+    pub live_rapid: Vec<LeaderboardEntry>,
+    pub live_blitz: Vec<LeaderboardEntry>,
+    pub live_bullet: Vec<LeaderboardEntry>,
+    pub live_bughouse: Vec<LeaderboardEntry>,
+    pub live_blitz960: Vec<LeaderboardEntry>,
+    pub live_threecheck: Vec<LeaderboardEntry>,
+    pub live_crazyhouse: Vec<LeaderboardEntry>,
+    pub live_kingofthehill: Vec<LeaderboardEntry>,
+    pub tactics: Vec<LeaderboardEntry>,
+    pub rush: Vec<LeaderboardEntry>,
+    pub battle: Vec<LeaderboardEntry>,
+}
+
+/// A player's place on a leaderboard.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LeaderboardEntry {
+    pub player_id: u64,
+    /// The API URL of the player's profile.
+    #[serde(rename = "@id")]
+    pub id: String,
+    /// The player's page on Chess.com.
+    pub url: String,
+    pub username: String,
+    /// Their rating in this category.
+    pub score: i32,
+    /// Their place on the leaderboard, starting at 1.
+    pub rank: u32,
+    pub name: Option<String>,
+    /// e.g. `"GM"`, for titled players.
+    pub title: Option<String>,
+    /// The API URL of the player's country.
+    pub country: Option<String>,
+    pub avatar: Option<String>,
+    pub win_count: Option<u32>,
+    pub loss_count: Option<u32>,
+    pub draw_count: Option<u32>,
+    /// How their rating changed recently.
+    pub trend_score: Option<Trend>,
+    /// How their place on the leaderboard changed recently.
+    pub trend_rank: Option<Trend>,
+}
+
+/// A recent change in a [LeaderboardEntry].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Trend {
+    /// 1 for up, -1 for down, 0 for unchanged.
+    pub direction: i32,
+    pub delta: i32,
+}
+
+impl Leaderboards {
+    /// Downloads the current leaderboards, using a [Client] with the default settings.
+    ///
+    /// ```rust,no_run
+    /// use hikaru::{GameData, Leaderboards};
+    ///
+    /// # fn main() -> hikaru::Result<()> {
+    /// let leaderboards = Leaderboards::download()?;
+    /// let top_blitz = leaderboards.live_blitz.iter().map(|entry| entry.username.as_str());
+    /// let games = GameData::download_many(top_blitz)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn download() -> Result<Leaderboards> {
+        Client::new().leaderboards()
+    }
+}
+
+impl Client {
+    pub fn leaderboards(&self) -> Result<Leaderboards> {
+        self.get_json(&self.endpoint("leaderboards"))
+    }
+}
@@ -7,24 +7,251 @@
 //! 
 //! All you have to do is feed Hikaru a list of usernames, and you get back a Vec<[GameData]>
 //! 
-//! ```rust
+//! ```rust,no_run
 //! use hikaru::GameData;
 //! 
+//! # fn main() -> hikaru::Result<()> {
 //! let user_names = vec!["hikaru","GMHikaruOnTwitch"];
-//! let games = GameData::download(user_names);
+//! let games = GameData::download(user_names)?;
 //! 
 //! // Check out Hikaru's first game on Chess.com:
 //! dbg!(&games[0]);
+//! # Ok(())
+//! # }
 //! ```
 //! 
-//! ## Future plans
+//! Failed requests, unknown usernames and unexpected responses are reported through [Error] rather than panicking.
 //! 
-//! Create a stockfish wrapper so that you can analyze all your games. The game data include all the moves made in those games, so this can be fed into the engine for a variety of analyses.
+//! To control the User-Agent (which Chess.com asks API users to set), timeouts, headers or proxies, build a [Client] and download through it instead:
 //! 
+//! ```rust,no_run
+//! # fn main() -> hikaru::Result<()> {
+//! let client = hikaru::Client::builder()
+//!     .user_agent("my-chess-app/1.0 (me@example.com)")
+//!     .build()?;
+//! let games = client.games("hikaru")?;
+//! # Ok(())
+//! # }
+//! ```
+//! 
+//! [GameData] sees each game from the side of the player it was downloaded for. [Client::archived_games] keeps the games
+//! as the archives list them instead, as [ArchivedGame]s with both players side by side.
+//! 
+//! In tests, [ClientBuilder::transport] swaps the HTTP layer for canned responses, e.g. a [MockTransport].
+//! 
+//! Tools built for lichess exports can read games converted with [GameData::to_lichess_json] and [GameData::to_lichess_pgn].
+//! 
+//! ## Other endpoints
+//! 
+//! Besides game archives, the [Client] covers the rest of the public API:
+//! 
+//! - clubs, their members and matches: [Client::club], [Client::club_members], [Client::club_matches]
+//! - tournaments: [Client::tournament], [Client::tournament_round], [Client::tournament_round_group]
+//! - team matches: [Client::team_match], [Client::team_match_board]
+//! - countries and their players: [Client::country], [Client::country_players]
+//! - player profiles: [Client::profile], and [Client::validate_users] to check a list of usernames up front
+//! - the profiles of everyone in a set of games, for their titles, countries and account status: [Client::opponent_profiles]
+//! - titled players: [Client::titled_players]
+//! - the daily puzzle: [Client::daily_puzzle], [Client::random_puzzle]
+//! - leaderboards: [Client::leaderboards]
+//! - daily games still in progress: [Client::daily_games], [Client::games_to_move]
+//! - games as they finish: [Client::watch]
+//! - a single game by its ID, without the rest of its month: [Client::game_by_id] (unofficial, through the website)
+//! 
+//! For finished daily games, [GameData::move_timestamps] and [GameData::thinking_times] say when each move was made.
+//! 
+//! ## Features
+//! 
+//! - `async`: adds [AsyncClient] and [GameData::download_async], which fetch monthly archives concurrently using reqwest's async client.
+//! - `csv`: adds [GameData::to_csv_writer] and [GameData::write_csv_file] for exporting games to spreadsheets.
+//! - `chrono`: adds [GameData::start_datetime] and [GameData::end_datetime], which return chrono's `DateTime<Utc>`.
+//! - `arrow`: adds [games_to_record_batch] and [GameData::write_parquet], for loading games into DuckDB, Polars and friends.
+//! - `polars`: adds [games_to_dataframe], which puts games straight into a Polars `DataFrame`, with datetimes and categoricals.
+//! - `python`: builds Hikaru as a Python module, with `download(user)` returning a list of dicts and
+//!   `download_columns(user)` a dict of columns for pandas. Build the wheel with [maturin](https://www.maturin.rs).
+//! - `ffi`: adds a C API in [ffi], `hikaru_download_json`, for embedding the downloader in C, C++ or Swift programs.
+//! - `lichess`: adds [Client::lichess_games] and [LichessGameData::download], which download lichess games as [GameData].
+//! - `rayon`: adds [Client::games_parallel_parse] and [GameData::download_parallel_parse], which convert games and replay their PGNs on every core.
+//! - `tracing`: emits [tracing](https://docs.rs/tracing) spans for each monthly archive, and events for requests, retries,
+//!   rate limiting and games that couldn't be parsed, with their URLs and timings.
+//! - `raw`: adds [Client::games_raw], which keeps each game's JSON as a [GameRaw] for fields Hikaru doesn't know about yet.
+//! - `sqlite`: adds [GameStore], a local SQLite copy of downloaded games that can be synced incrementally and queried.
+//! 
+//! ## In the browser
+//! 
+//! Hikaru builds for `wasm32-unknown-unknown`, so a dashboard can pull a player's games client-side without a backend.
+//! There is no blocking HTTP client there: enable the `async` feature and use the [AsyncClient], whose requests reqwest
+//! sends with the browser's `fetch`. Proxies, timeouts and compression are left to the browser, and the on-disk
+//! [cache](CacheConfig) and [engine analysis](GameData::analyze) need a filesystem and processes, which it doesn't have.
+//! 
+//! ## Engine analysis
+//! 
+//! With Stockfish (or any other UCI engine) installed, [GameData::analyze] evaluates every move of a game and flags inaccuracies, mistakes and blunders.
+//! See [EngineConfig] for how to point Hikaru at the engine.
+//! Without one, [GameData::quick_analysis] still catches missed mates in one and hanging pieces that were never taken.
+//! 
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
 
-use reqwest::blocking::get;
 use serde::{Deserialize, Serialize};
 
+mod archive;
+mod archive_reader;
+mod archived;
+mod board;
+mod bughouse;
+mod cache;
+mod callback;
+mod cancel;
+mod chess960;
+mod client;
+mod clubs;
+mod correspondence;
+mod countries;
+mod daily;
+mod date;
+mod dedupe;
+mod eco;
+mod eco_url;
+mod engine;
+mod error;
+mod export;
+mod fen;
+mod filter;
+mod game_id;
+mod iter;
+mod leaderboards;
+mod lichess_export;
+mod lite;
+mod opponents;
+mod options;
+mod performance;
+mod pgn;
+mod player;
+mod profile;
+mod puzzle;
+mod quick_analysis;
+mod repertoire;
+mod sessions;
+mod sink;
+mod stats;
+mod team_matches;
+mod tcn;
+mod termination;
+mod throttle;
+mod time_control;
+mod time_usage;
+mod titled;
+mod tournaments;
+mod trace;
+mod transport;
+mod username;
+mod watch;
+pub use archive::{ArchiveMonth, ArchiveSummary, DownloadReport};
+pub use archived::{Accuracies, ArchivedGame, ArchivedPlayer};
+pub use board::Color;
+pub use bughouse::{Bughouse, PartnerGame};
+pub use cache::CacheConfig;
+pub use callback::CallbackGame;
+pub use cancel::CancellationToken;
+pub use client::{Client, ClientBuilder};
+pub use clubs::{Club, ClubMatch, ClubMatches, ClubMember, ClubMembers};
+pub use countries::Country;
+pub use daily::{DailyGame, GameToMove};
+pub use dedupe::dedupe_games;
+pub use eco::Opening;
+pub use eco_url::EcoUrl;
+pub use engine::{Classification, Engine, EngineConfig, Evaluation, GameAnalysis, MoveAnalysis, Score};
+pub use error::{Error, GameParseError, Result};
+pub use fen::{Fen, Material};
+pub use filter::GameFilter;
+pub use game_id::{GameId, GameKind};
+pub use iter::{DownloadCheckpoint, GameIter};
+pub use leaderboards::{LeaderboardEntry, Leaderboards, Trend};
+pub use lite::GameDataLite;
+pub use opponents::{exclude_closed_accounts, OpponentProfile, Opponents};
+pub use options::{DownloadOptions, DownloadOrder, Progress};
+pub use performance::{expected_score, performance_by_period, performance_rating, PerformancePoint};
+pub use pgn::{ClockTimes, ParsedPgn, San};
+pub use player::PlayerRef;
+pub use profile::Profile;
+pub use puzzle::Puzzle;
+pub use quick_analysis::{MissedTactic, TacticKind, TacticalReport};
+pub use repertoire::{Repertoire, RepertoireNode};
+pub use sessions::{LosingStreak, SessionReport, TiltSession};
+pub use sink::{GameSink, NdjsonSink};
+pub use stats::{
+    head_to_head, BestWin, Granularity, H2HSummary, MonthlySummary, RatingHistory, RatingPoint, RatingRange, Record, Summary,
+};
+pub use team_matches::{
+    TeamMatch, TeamMatchBoard, TeamMatchGame, TeamMatchGamePlayer, TeamMatchPlayer, TeamMatchSettings, TeamMatchTeam, TeamMatchTeams,
+};
+pub use tcn::{tcn_to_san, tcn_to_uci};
+pub use termination::Termination;
+pub use time_control::TimeControl;
+pub use time_usage::TimeUsage;
+pub use titled::{Title, TitledPlayers};
+pub use tournaments::{
+    Tournament, TournamentGroup, TournamentPlayer, TournamentRound, TournamentRoundPlayer, TournamentSettings, TournamentStanding,
+};
+pub use transport::{HttpResponse, HttpTransport, MockTransport, StreamingResponse};
+pub use username::Username;
+pub use watch::Watch;
+pub use reqwest::{header, StatusCode};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use reqwest::Proxy;
+#[cfg(not(target_arch = "wasm32"))]
+pub use transport::ReqwestTransport;
+
+#[cfg(feature = "async")]
+mod async_client;
+#[cfg(feature = "async")]
+pub use async_client::AsyncClient;
+
+#[cfg(feature = "csv")]
+mod csv_export;
+#[cfg(feature = "csv")]
+pub use csv_export::{CsvOptions, CsvSink};
+
+#[cfg(feature = "arrow")]
+mod arrow_export;
+#[cfg(feature = "arrow")]
+pub use arrow_export::games_to_record_batch;
+
+#[cfg(feature = "polars")]
+mod polars_export;
+#[cfg(feature = "polars")]
+pub use polars_export::games_to_dataframe;
+
+#[cfg(feature = "python")]
+mod python;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+#[cfg(feature = "lichess")]
+mod lichess;
+#[cfg(feature = "lichess")]
+pub use lichess::LichessGameData;
+
+#[cfg(feature = "rayon")]
+mod parallel_parse;
+
+#[cfg(feature = "raw")]
+mod raw;
+#[cfg(feature = "raw")]
+pub use raw::GameRaw;
+
+#[cfg(feature = "sqlite")]
+mod sqlite_store;
+#[cfg(feature = "sqlite")]
+pub use sqlite_store::GameStore;
+
 
 #[derive(Deserialize)]
 struct GameUrls {
@@ -32,17 +259,65 @@ struct GameUrls {
 }
 
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(rename_all="lowercase")]
+/// Chess.com adds new values from time to time, anything not listed here is kept as `Other`.
+///
+/// Ordered from fastest to slowest, with `Other` last.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(from="String", into="String")]
+#[non_exhaustive]
 pub enum TimeClass {
     Bullet,
     Blitz,
     Rapid,
     Daily,
+    Other(String),
+}
+
+impl From<String> for TimeClass {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "bullet" => Self::Bullet,
+            "blitz" => Self::Blitz,
+            "rapid" => Self::Rapid,
+            "daily" => Self::Daily,
+            _ => Self::Other(value),
+        }
+    }
+}
+
+impl TimeClass {
+    /// The name the API uses, e.g. `"blitz"`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            TimeClass::Bullet => "bullet",
+            TimeClass::Blitz => "blitz",
+            TimeClass::Rapid => "rapid",
+            TimeClass::Daily => "daily",
+            TimeClass::Other(value) => value,
+        }
+    }
+}
+
+impl fmt::Display for TimeClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<TimeClass> for String {
+    fn from(time_class: TimeClass) -> Self {
+        match time_class {
+            TimeClass::Other(value) => value,
+            known => known.as_str().into(),
+        }
+    }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(rename_all="lowercase")]
+/// The variant being played. Anything not listed here is kept as `Other`. Ordered as listed, which has no meaning beyond
+/// keeping sorts stable.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(from="String", into="String")]
+#[non_exhaustive]
 pub enum Rules {
     Chess,
     Chess960,
@@ -51,31 +326,65 @@ pub enum Rules {
     KingOfTheHill,
     Horde,
     BugHouse,
-    OddsChess
+    OddsChess,
+    Other(String),
 }
 
+impl From<String> for Rules {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "chess" => Self::Chess,
+            "chess960" => Self::Chess960,
+            "crazyhouse" => Self::CrazyHouse,
+            "threecheck" => Self::ThreeCheck,
+            "kingofthehill" => Self::KingOfTheHill,
+            "horde" => Self::Horde,
+            "bughouse" => Self::BugHouse,
+            "oddschess" => Self::OddsChess,
+            _ => Self::Other(value),
+        }
+    }
+}
 
-#[derive(Debug, Deserialize)]
-struct Game {
-    #[serde(rename="url")]
-    game_url: String,
-    pgn: Option<String>,
-    time_control: String,
-    start_time: Option<u32>,
-    end_time: u32,
-    rated: bool,
-    fen: String,
-    time_class: TimeClass,
-    rules: Rules,
-    eco: Option<String>,
-    tournament: Option<String>,
-    #[serde(rename="match")]
-    team_match: Option<String>,
-    white: Player,
-    black: Player,
+impl Rules {
+    /// The name the API uses, e.g. `"chess960"`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Rules::Chess => "chess",
+            Rules::Chess960 => "chess960",
+            Rules::CrazyHouse => "crazyhouse",
+            Rules::ThreeCheck => "threecheck",
+            Rules::KingOfTheHill => "kingofthehill",
+            Rules::Horde => "horde",
+            Rules::BugHouse => "bughouse",
+            Rules::OddsChess => "oddschess",
+            Rules::Other(value) => value,
+        }
+    }
+}
+
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<Rules> for String {
+    fn from(rules: Rules) -> Self {
+        match rules {
+            Rules::Other(value) => value,
+            known => known.as_str().into(),
+        }
+    }
 }
 
-#[derive(Debug, Serialize)]
+
+/// A game from one player's point of view.
+///
+/// Games compare, hash and sort by `end_time`, then `game_url`, then `colour`, so sorting puts them in the order they
+/// were played and each game seen from the same side is equal to itself. The other fields aren't compared, so a game
+/// downloaded again after Chess.com has added its accuracy is still the same game.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameData {
     pub game_url: String,
     pub time_control: String,
@@ -93,29 +402,142 @@ pub struct GameData {
     pub white_username: String,
     pub black_rating: u32,
     pub black_username: String,
+    /// White's title, e.g. GM. Chess.com's monthly archives don't say, so this is usually only set for games from
+    /// elsewhere, e.g. lichess.
+    #[serde(default)]
+    pub white_title: Option<Title>,
+    #[serde(default)]
+    pub black_title: Option<Title>,
+    /// The API URL of White's country, e.g. `https://api.chess.com/pub/country/US`, where the source includes it.
+    #[serde(default)]
+    pub white_country: Option<String>,
+    #[serde(default)]
+    pub black_country: Option<String>,
     pub eco_pgn: String,
     pub eco_url: String,
     pub result: GameResult,
     pub result_win_lose: GameResultWinLose,
+    /// How the game ended, e.g. by checkmate or on time, whoever won.
+    pub termination: Termination,
     pub rating: u32,
+    /// The day the game ended (UTC), e.g. `2014.01.06`.
     pub date: String,
     pub colour: String,
     pub win: f32,
+    /// The username the games were downloaded for, as it was given.
     pub player_username: String,
+    /// The player's username spelled the way Chess.com displays it, e.g. `Hikaru` when downloading for `hikaru`.
+    pub canonical_username: String,
+    /// The full PGN of the game, moves included. Chess.com occasionally leaves it out.
+    pub pgn: Option<String>,
+    /// White's accuracy (0-100), if the game has been reviewed on Chess.com.
+    pub white_accuracy: Option<f32>,
+    pub black_accuracy: Option<f32>,
+    /// The player's own accuracy, i.e. whichever of the two is theirs.
+    pub accuracy: Option<f32>,
+    pub opponent_username: String,
+    pub opponent_rating: u32,
+    /// The player's rating minus the opponent's, negative if the opponent was rated higher.
+    pub rating_diff: i32,
+    /// How the game ended for the opponent, e.g. `Resigned` when the player won by resignation.
+    pub opponent_result: GameResult,
+    /// Chess.com's unique ID for the game, the same whichever player's archive it came from.
+    pub uuid: Option<String>,
+    /// The FEN the game started from. The standard starting position, except in Chess960 and custom setups.
+    pub initial_setup: Option<String>,
+    /// For Chess960 games, the starting position's number from 0 to 959 (518 is the standard position).
+    pub start_position_number: Option<u16>,
+    /// The moves in Chess.com's compact TCN encoding.
+    pub tcn: Option<String>,
+    /// The moves in SAN, e.g. `["e4", "e5", "Nf3"]`, from the PGN or (if there isn't one) the TCN. Empty if neither
+    /// can be read.
+    pub moves: Vec<String>,
+    /// Half-moves played, i.e. `moves.len()`.
+    pub plies: u32,
+    /// Moves played, counting a White move and Black's reply as one, e.g. 2 after `1. e4 e5 2. Nf3`.
+    pub move_count: u32,
+    /// The partner's board, for bughouse games whose archive entry links it.
+    pub bughouse: Option<Bughouse>,
+}
+
+impl GameData {
+    fn key(&self) -> (u32, &str, &str) {
+        (self.end_time, &self.game_url, &self.colour)
+    }
+}
+
+impl PartialEq for GameData {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for GameData {}
+
+impl Hash for GameData {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl PartialOrd for GameData {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GameData {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
 }
 
-impl From<(Game, &str)> for GameData {
-    fn from (game_data: (Game, &str)) -> Self {
+/// A one-line summary, with the date the game ended (UTC), the players and the opening if it can be named:
+///
+/// `2023-07-01 blitz: Hikaru (2890) 1-0 MagnusCarlsen (2882), B33 Sicilian Defense: Sveshnikov Variation`
+impl fmt::Display for GameData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = date::civil_from_timestamp(self.end_time.into());
+        write!(f, "{}-{:02}-{:02} {}: {} ({}) {} {} ({})", year, month, day, self.time_class,
+            self.white_username, self.white_rating, self.standard_result(), self.black_username, self.black_rating)?;
+        if let Some(opening) = self.opening() {
+            write!(f, ", {}", opening)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<(ArchivedGame, &str)> for GameData {
+    fn from (game_data: (ArchivedGame, &str)) -> Self {
         let game = game_data.0;
         let user = game_data.1;
-        let pgn: PGN = game.pgn.into();
+        let mut parsed = game.pgn.as_deref().map(ParsedPgn::parse).unwrap_or_default();
+        let moves: Vec<String> = match (game.pgn.is_some(), game.tcn.as_deref()) {
+            (true, _) => parsed.moves.into_iter().map(String::from).collect(),
+            // Without a PGN the moves can still be had from the TCN
+            (false, Some(tcn)) => tcn::tcn_to_san(tcn, game.initial_setup.as_deref()).unwrap_or_default(),
+            (false, None) => vec![],
+        };
+        let plies = moves.len() as u32;
         
-        let is_white = user == game.white.username;
-
-        let result = if is_white {game.white.result} else {game.black.result};
-        let result_win_lose = result.into();
-        let rating = if is_white {game.white.rating} else {game.black.rating};
+        // Usernames aren't case-sensitive on Chess.com, so "Hikaru" and "hikaru" are the same player
+        let is_white = user.eq_ignore_ascii_case(&game.white.username);
+
+        let (player, opponent) = if is_white {(&game.white, &game.black)} else {(&game.black, &game.white)};
+        let result = player.result.clone();
+        let result_win_lose = GameResultWinLose::from_results(&player.result, &opponent.result);
+        let rating = player.rating;
+        let canonical_username = player.username.clone();
+        let opponent_username = opponent.username.clone();
+        let opponent_rating = opponent.rating;
+        let opponent_result = opponent.result.clone();
+        let termination = Termination::from_results(&game.white.result, &game.black.result);
         let colour = if is_white {"White"} else {"Black"};
+        let white_accuracy = game.accuracies.as_ref().map(|accuracies| accuracies.white);
+        let black_accuracy = game.accuracies.as_ref().map(|accuracies| accuracies.black);
+        let start_position_number = game.initial_setup.as_deref()
+            .filter(|_| game.rules == Rules::Chess960)
+            .and_then(chess960::start_position_number);
 
         
         let win = 
@@ -139,17 +561,39 @@ impl From<(Game, &str)> for GameData {
             team_match: game.team_match,
             result,
             result_win_lose,
+            termination,
             white_rating: game.white.rating,
             white_username: game.white.username,
             black_rating: game.black.rating,
             black_username: game.black.username,
-            eco_pgn: pgn.ECO,
-            eco_url: pgn.ECO_url,
+            white_title: game.white.title,
+            black_title: game.black.title,
+            white_country: game.white.country,
+            black_country: game.black.country,
+            eco_pgn: parsed.tags.remove("ECO").unwrap_or_default(),
+            eco_url: parsed.tags.remove("ECOUrl").unwrap_or_default(),
             rating,
             colour: colour.into(),
             win,
             player_username: user.into(),
-            date: pgn.UTC_date,
+            canonical_username,
+            date: date::pgn_date(game.end_time.into()),
+            pgn: game.pgn,
+            white_accuracy,
+            black_accuracy,
+            accuracy: if is_white {white_accuracy} else {black_accuracy},
+            rating_diff: rating as i32 - opponent_rating as i32,
+            opponent_username,
+            opponent_rating,
+            opponent_result,
+            uuid: game.uuid,
+            start_position_number,
+            initial_setup: game.initial_setup,
+            tcn: game.tcn,
+            moves,
+            plies,
+            move_count: plies.div_ceil(2),
+            bughouse: game.partner_game.map(|partner_game| Bughouse::new(partner_game, is_white)),
         }
     }
 }
@@ -157,8 +601,10 @@ impl From<(Game, &str)> for GameData {
 
 
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
-#[serde(rename_all="lowercase")]
+/// How the game ended for one of the players. Result codes not listed here are kept as `Other`. Ordered as listed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(from="String", into="String")]
+#[non_exhaustive]
 pub enum GameResult {
     Win,
     TimeOut,
@@ -169,143 +615,280 @@ pub enum GameResult {
     Repetition,
     Insufficient,
     Abandoned,
-    #[serde(rename="50move")]
     FiftyMove,
     TimeVsInsufficient,
     KingOfTheHill,
     ThreeCheck,
     BugHousePartnerLose,
     BugHousePartnerWin,
+    Other(String),
+}
+
+impl From<String> for GameResult {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "win" => Self::Win,
+            "timeout" => Self::TimeOut,
+            "checkmated" => Self::CheckMated,
+            "stalemate" => Self::StaleMate,
+            "resigned" => Self::Resigned,
+            "agreed" => Self::Agreed,
+            "repetition" => Self::Repetition,
+            "insufficient" => Self::Insufficient,
+            "abandoned" => Self::Abandoned,
+            "50move" => Self::FiftyMove,
+            "timevsinsufficient" => Self::TimeVsInsufficient,
+            "kingofthehill" => Self::KingOfTheHill,
+            "threecheck" => Self::ThreeCheck,
+            "bughousepartnerlose" => Self::BugHousePartnerLose,
+            "bughousepartnerwin" => Self::BugHousePartnerWin,
+            _ => Self::Other(value),
+        }
+    }
+}
+
+impl GameResult {
+    /// The name the API uses, e.g. `"checkmated"`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            GameResult::Win => "win",
+            GameResult::TimeOut => "timeout",
+            GameResult::CheckMated => "checkmated",
+            GameResult::StaleMate => "stalemate",
+            GameResult::Resigned => "resigned",
+            GameResult::Agreed => "agreed",
+            GameResult::Repetition => "repetition",
+            GameResult::Insufficient => "insufficient",
+            GameResult::Abandoned => "abandoned",
+            GameResult::FiftyMove => "50move",
+            GameResult::TimeVsInsufficient => "timevsinsufficient",
+            GameResult::KingOfTheHill => "kingofthehill",
+            GameResult::ThreeCheck => "threecheck",
+            GameResult::BugHousePartnerLose => "bughousepartnerlose",
+            GameResult::BugHousePartnerWin => "bughousepartnerwin",
+            GameResult::Other(value) => value,
+        }
+    }
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
-#[derive(Debug, Serialize)]
+impl From<GameResult> for String {
+    fn from(result: GameResult) -> Self {
+        match result {
+            GameResult::Other(value) => value,
+            known => known.as_str().into(),
+        }
+    }
+}
+
+/// Ordered by points, so a loss is less than a draw and a draw less than a win.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum GameResultWinLose {
-    Win,
     Loss,
     Draw,
+    Win,
 }
 
+impl GameResultWinLose {
+    /// Decides the outcome from both players' results. Chess.com gives whoever won a result of `win` and the other
+    /// player the reason they lost (the opponent reaching the hill, say, is `kingofthehill`), while in a draw both sides
+    /// get the reason for the draw. Bughouse teams win or lose together.
+    ///
+    /// ```rust
+    /// use hikaru::{GameResult, GameResultWinLose};
+    ///
+    /// let outcome = |player, opponent| GameResultWinLose::from_results(&player, &opponent);
+    /// assert_eq!(outcome(GameResult::Win, GameResult::TimeOut), GameResultWinLose::Win);
+    /// assert_eq!(outcome(GameResult::TimeOut, GameResult::Win), GameResultWinLose::Loss);
+    /// assert_eq!(outcome(GameResult::KingOfTheHill, GameResult::Win), GameResultWinLose::Loss);
+    /// assert_eq!(outcome(GameResult::ThreeCheck, GameResult::Win), GameResultWinLose::Loss);
+    /// assert_eq!(outcome(GameResult::BugHousePartnerWin, GameResult::BugHousePartnerLose), GameResultWinLose::Win);
+    /// assert_eq!(outcome(GameResult::TimeVsInsufficient, GameResult::TimeVsInsufficient), GameResultWinLose::Draw);
+    /// assert_eq!(outcome(GameResult::Agreed, GameResult::Agreed), GameResultWinLose::Draw);
+    /// ```
+    pub fn from_results(player: &GameResult, opponent: &GameResult) -> Self {
+        use GameResult::*;
+        match (player, opponent) {
+            (Win | BugHousePartnerWin, _) | (_, BugHousePartnerLose) => Self::Win,
+            (_, Win | BugHousePartnerWin) | (BugHousePartnerLose, _) => Self::Loss,
+            // Neither side has a win, so it's a draw, unless the opponent's result is one that only ever means losing
+            (_, result) if GameResultWinLose::from(result.clone()) == Self::Loss => Self::Win,
+            (result, _) if GameResultWinLose::from(result.clone()) == Self::Loss => Self::Loss,
+            _ => Self::Draw,
+        }
+    }
+}
+
+/// Reads the outcome from one player's result alone. Every code except `win` and the draw codes means a loss. Codes
+/// Hikaru doesn't know count as a draw; [GameResultWinLose::from_results] can tell better, as it sees both sides.
 impl From<GameResult> for GameResultWinLose {
     fn from( res: GameResult) -> Self {
         use GameResult::*;
         match res {
-            Win | BugHousePartnerWin | KingOfTheHill | ThreeCheck => Self::Win,
-            CheckMated | BugHousePartnerLose | Abandoned | Resigned => Self::Loss,
-            _ => Self::Draw,
+            Win | BugHousePartnerWin => Self::Win,
+            Agreed | Repetition | StaleMate | Insufficient | FiftyMove | TimeVsInsufficient | Other(_) => Self::Draw,
+            TimeOut | CheckMated | Resigned | Abandoned | KingOfTheHill | ThreeCheck | BugHousePartnerLose => Self::Loss,
         }
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-struct Player {
-    username: String,
-    rating: u32,
-    result: GameResult,
-    #[serde(rename="@id")]
-    id: String,
-}
+/// A monthly archive, `{"games": [...]}`, read game by game with `Games::read`.
+struct Games;
 
-#[derive(Debug, Deserialize)]
-struct Games {
-    games: Vec<Game>
-}
+/// A month's games, or why each one that couldn't be read failed.
+type MonthGames = Vec<std::result::Result<ArchivedGame, GameParseError>>;
 
-#[allow(non_snake_case)]
-#[derive(Debug)]
-struct PGN {
-    ECO: String,
-    ECO_url: String,
-    UTC_date: String,
-    UTC_time: String,
-    start_date: String,
-    start_time: String,
+impl GameData {
+    /// Parses the game's PGN into its tags, moves and clock times. `None` if Chess.com didn't include a PGN for this game.
+    pub fn parsed_pgn (&self) -> Option<ParsedPgn> {
+        self.pgn.as_deref().map(ParsedPgn::parse)
+    }
 
-}
+    /// Every tag pair in the game's PGN header, e.g. `"TimeControl" => "180"`. Empty without a PGN. Cheaper than
+    /// [GameData::parsed_pgn] when the moves aren't needed.
+    pub fn pgn_tags(&self) -> HashMap<String, String> {
+        self.pgn.as_deref().map(ParsedPgn::parse_tags).unwrap_or_default()
+    }
 
-impl From<Option<String>> for PGN {
-    fn from (pgn: Option<String>) -> Self {
-        
-        let mut eco = String::new();
-        let mut eco_url = String::new();
-        let mut utc_date = String::new();
-
-        if let Some(pgn_data) = pgn {
-            for line in pgn_data.split('\n') {
-                if line.starts_with("[ECO \"") {eco = line.split(" ").nth(1).unwrap_or("").replace('"',"").replace(']',"").into();}
-                if line.starts_with("[ECOUrl \"") {eco_url = line.split(" ").nth(1).unwrap_or("").replace('"',"").replace(']',"").into();}
-                if line.starts_with("[UTCDate \"") {utc_date = line.split(" ").nth(1).unwrap_or("").replace('"',"").replace(']',"").into();}
-            }
-            Self {
-                ECO: eco,
-                ECO_url: eco_url,
-                UTC_date: utc_date,
-                UTC_time: "".to_string(),
-                start_time: "".to_string(),
-                start_date: "".to_string(),
-            }
-        }
+    /// Each player's remaining clock time after every move, from the PGN's `[%clk]` annotations. `None` without a PGN.
+    pub fn clock_times (&self) -> Option<ClockTimes> {
+        self.parsed_pgn().map(|pgn| pgn.player_clock_times())
+    }
 
-        else {
-            Self {
-                ECO: "".to_string(),
-                ECO_url: "".to_string(),
-                UTC_date: "".to_string(),
-                UTC_time: "".to_string(),
-                start_time: "".to_string(),
-                start_date: "".to_string(),
-            }
+    /// The result the way PGN writes it, from White's side: `1-0`, `0-1` or `1/2-1/2`.
+    pub fn standard_result (&self) -> &'static str {
+        match GameResultWinLose::from_results(self.white().result, self.black().result) {
+            GameResultWinLose::Win => "1-0",
+            GameResultWinLose::Loss => "0-1",
+            GameResultWinLose::Draw => "1/2-1/2",
         }
-        
     }
-}
 
-fn get_game_month_urls (user: &str) -> Vec <String> {
+    /// How long the game lasted, from the first move until it ended. `None` if Chess.com didn't record when it started.
+    pub fn duration (&self) -> Option<Duration> {
+        self.start_time.map(|start_time| Duration::from_secs(self.end_time.saturating_sub(start_time).into()))
+    }
 
-    let url = format!("https://api.chess.com/pub/player/{}/games/archives", user);
-    
-    let text = get(&url)
-        .expect("Didn't get a response")
-        .text()
-        .expect("Invalid response");
-    
-    let game_urls: GameUrls = serde_json::from_str(&text).expect("Serde error!");
+    /// When the game started.
+    #[cfg(feature = "chrono")]
+    pub fn start_datetime (&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.start_time.and_then(|start_time| chrono::DateTime::from_timestamp(start_time.into(), 0))
+    }
 
-    game_urls.archives
+    /// When the game ended.
+    #[cfg(feature = "chrono")]
+    pub fn end_datetime (&self) -> chrono::DateTime<chrono::Utc> {
+        chrono::DateTime::from_timestamp(self.end_time.into(), 0).unwrap_or_default()
+    }
 
-}
+    /// The game's [TimeControl], or `None` if Chess.com sent one that isn't in a format Hikaru knows.
+    pub fn parsed_time_control (&self) -> Option<TimeControl> {
+        self.time_control.parse().ok()
+    }
 
-fn get_games (game_archive_urls: Vec<String>) -> Vec<Game> {
+    /// Downloads every game played by each of the users, using a [Client] with the default settings.
+    ///
+    /// Games are grouped by user, and a game between two of the users appears twice, once from each side.
+    /// Use [GameData::download_many] to merge the users' games instead, or [dedupe_games] afterwards.
+    pub fn download (users: Vec<&str>) -> Result<Vec<GameData>> {
+        let client = Client::new();
+        let mut game_data = vec![];
+        for user in users {
+            game_data.extend(client.games(user)?);
+        }
+    Ok(game_data)
+    }
 
-    let mut games: Vec<Game> = vec![];
+    /// Downloads and merges the games of several users, such as one player's different accounts. See [Client::games_many].
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// let games = hikaru::GameData::download_many(["hikaru", "GMHikaruOnTwitch"])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn download_many<I, S> (users: I) -> Result<Vec<GameData>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Client::new().games_many(users)
+    }
 
-    for game_month in game_archive_urls {
+    /// Downloads a single user's games, fetching up to `concurrency` monthly archives at the same time. See [Client::games_parallel].
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// let games = hikaru::GameData::download_parallel("hikaru", 4)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn download_parallel (user: &str, concurrency: usize) -> Result<Vec<GameData>> {
+        Client::new().games_parallel(user, concurrency)
+    }
 
-        let games_text = get(&game_month)
-            .expect("Didn't get a response")
-            .text()
-            .expect("Invalid response");
+    /// Iterates over a single user's games, downloading each monthly archive only when the iterator reaches it. See [GameIter].
+    pub fn download_iter (user: &str) -> Result<GameIter> {
+        Client::new().games_iter(user)
+    }
 
-        let month_games: Games = serde_json::from_str(&games_text).expect("Serde error!");
-        games.extend(month_games.games)
+    /// Downloads a single user's games the way `options` says, with a [Client] using the options' cache, retries and
+    /// rate limit. See [DownloadOptions].
+    pub fn download_with (user: &str, options: DownloadOptions) -> Result<Vec<GameData>> {
+        Client::builder().build()?.games_with(user, &options)
+    }
 
+    /// Downloads one month of a user's games, without fetching the list of archives. See [Client::games_in_month].
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// let games = hikaru::GameData::download_month("hikaru", 2024, 1)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn download_month (user: &str, year: u16, month: u8) -> Result<Vec<GameData>> {
+        Client::new().games_in_month(user, year, month)
     }
 
-    games
-}
+    /// Downloads a single user's games between two months (inclusive), given as `(year, month)`.
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// // Everything from January 2022 through June 2023
+    /// let games = hikaru::GameData::download_between("hikaru", (2022, 1), (2023, 6))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn download_between (user: &str, since: (u16, u8), until: (u16, u8)) -> Result<Vec<GameData>> {
+        let options = DownloadOptions::new()
+            .since(since.0, since.1)
+            .until(until.0, until.1);
+        Self::download_with(user, options)
+    }
 
-impl GameData {
-    pub fn download (users: Vec<&str>) -> Vec<GameData> {
-        let mut game_data = vec![];
-        for user in users {
-            let urls = get_game_month_urls(&user);
-            let games = get_games(urls);
-            let game_data_user: Vec<GameData> = games.into_iter()
-                               .map(|game| (game, user).into())
-                               .collect()
-                               ;
-            
-        game_data.extend(game_data_user);
-        }
-    game_data
+    /// Downloads only the games a user finished after `last_end_time`, for keeping a local copy of their games up to date.
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// let mut games = hikaru::GameData::download(vec!["hikaru"])?;
+    ///
+    /// // Later on, fetch whatever has been played since
+    /// let last_end_time = games.iter().map(|game| game.end_time).max().unwrap_or(0);
+    /// games.extend(hikaru::GameData::download_since("hikaru", last_end_time)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn download_since (user: &str, last_end_time: u32) -> Result<Vec<GameData>> {
+        Self::download_with(user, DownloadOptions::new().ended_after(last_end_time))
     }
-}
\ No newline at end of file
+
+    /// Downloads a single user's games that pass `filter`. See [GameFilter].
+    pub fn download_filtered (user: &str, filter: GameFilter) -> Result<Vec<GameData>> {
+        Self::download_with(user, DownloadOptions::new().filter(filter))
+    }
+}
@@ -0,0 +1,259 @@
+//! Lichess game downloads, converted into the same [GameData] as Chess.com games so both sites can be analysed together.
+//! Enabled with the `lichess` feature.
+
+use std::io::{BufRead, BufReader};
+
+use serde::Deserialize;
+
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT};
+
+use crate::board::STARTING_FEN;
+use crate::{Accuracies, ArchivedGame, ArchivedPlayer, Client, Color, Error, GameData, GameResult, ParsedPgn, Result, Rules, TimeClass,
+    Title};
+
+const LICHESS_API: &str = "https://lichess.org/api";
+
+/// A game as the lichess API exports it. Only useful for turning into a [GameData], from the perspective of one of
+/// the players.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LichessGameData {
+    id: String,
+    rated: bool,
+    variant: String,
+    speed: String,
+    created_at: u64,
+    last_move_at: u64,
+    status: String,
+    players: LichessPlayers,
+    winner: Option<Color>,
+    pgn: Option<String>,
+    clock: Option<LichessClock>,
+    days_per_turn: Option<u32>,
+    initial_fen: Option<String>,
+    last_fen: Option<String>,
+    tournament: Option<String>,
+    swiss: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct LichessPlayers {
+    white: LichessPlayer,
+    black: LichessPlayer,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LichessPlayer {
+    user: Option<LichessUser>,
+    rating: Option<u32>,
+    ai_level: Option<u8>,
+    analysis: Option<LichessAnalysis>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct LichessUser {
+    name: String,
+    #[serde(default, deserialize_with = "crate::titled::known_title")]
+    title: Option<Title>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct LichessAnalysis {
+    accuracy: Option<f32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct LichessClock {
+    initial: u32,
+    increment: u32,
+}
+
+impl LichessPlayer {
+    /// Lichess has games against its AI and anonymous players, who have no account.
+    fn username(&self) -> String {
+        match (&self.user, self.ai_level) {
+            (Some(user), _) => user.name.clone(),
+            (None, Some(level)) => format!("Stockfish level {}", level),
+            (None, None) => "Anonymous".into(),
+        }
+    }
+}
+
+impl LichessGameData {
+    /// Downloads every game a lichess user has played, oldest first, using a [Client] with the default settings.
+    /// See [Client::lichess_games].
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// let mut games = hikaru::GameData::download(vec!["hikaru"])?;
+    /// games.extend(hikaru::LichessGameData::download("DrNykterstein")?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn download(user: &str) -> Result<Vec<GameData>> {
+        Client::new().lichess_games(user)
+    }
+
+    /// How the game ended for White and for Black, in Chess.com's terms. Lichess only says "draw" for draws, so
+    /// they all come out as agreed.
+    fn results(&self) -> (GameResult, GameResult) {
+        let winner = match self.winner {
+            Some(winner) => winner,
+            None => {
+                let draw = match self.status.as_str() {
+                    "stalemate" => GameResult::StaleMate,
+                    "outoftime" => GameResult::TimeVsInsufficient,
+                    "draw" => GameResult::Agreed,
+                    status => GameResult::Other(status.into()),
+                };
+                return (draw.clone(), draw);
+            }
+        };
+        let loser = match (self.status.as_str(), self.variant.as_str()) {
+            ("mate", _) => GameResult::CheckMated,
+            ("resign", _) => GameResult::Resigned,
+            ("outoftime", _) => GameResult::TimeOut,
+            ("timeout", _) | ("noStart", _) => GameResult::Abandoned,
+            ("variantEnd", "kingOfTheHill") => GameResult::KingOfTheHill,
+            ("variantEnd", "threeCheck") => GameResult::ThreeCheck,
+            (status, _) => GameResult::Other(status.into()),
+        };
+        match winner {
+            Color::White => (GameResult::Win, loser),
+            Color::Black => (loser, GameResult::Win),
+        }
+    }
+
+    /// In Chess.com's format, e.g. `180+2` or `1/259200`.
+    fn time_control(&self) -> String {
+        match (&self.clock, self.days_per_turn) {
+            (Some(LichessClock { initial, increment: 0 }), _) => initial.to_string(),
+            (Some(LichessClock { initial, increment }), _) => format!("{}+{}", initial, increment),
+            (None, Some(days)) => format!("1/{}", days * 86_400),
+            (None, None) => "-".into(),
+        }
+    }
+
+    /// Chess.com puts every live game of ten minutes or more in rapid, so lichess's classical games go there too.
+    fn time_class(&self) -> TimeClass {
+        match self.speed.as_str() {
+            "ultraBullet" | "bullet" => TimeClass::Bullet,
+            "blitz" => TimeClass::Blitz,
+            "rapid" | "classical" => TimeClass::Rapid,
+            "correspondence" => TimeClass::Daily,
+            speed => TimeClass::Other(speed.into()),
+        }
+    }
+
+    fn rules(&self) -> Rules {
+        match self.variant.as_str() {
+            // A custom starting position is still chess; the position is kept in `initial_setup`
+            "standard" | "fromPosition" => Rules::Chess,
+            "chess960" => Rules::Chess960,
+            "crazyhouse" => Rules::CrazyHouse,
+            "threeCheck" => Rules::ThreeCheck,
+            "kingOfTheHill" => Rules::KingOfTheHill,
+            "horde" => Rules::Horde,
+            variant => Rules::Other(variant.into()),
+        }
+    }
+
+    /// The final position. Older exports don't include it, in which case it's worked out from the moves.
+    fn final_fen(&self) -> String {
+        self.last_fen.clone()
+            .or_else(|| {
+                let pgn = ParsedPgn::parse(self.pgn.as_deref()?);
+                pgn.positions().ok()?.pop().map(|fen| fen.to_string())
+            })
+            .or_else(|| self.initial_fen.clone())
+            .unwrap_or_else(|| STARTING_FEN.into())
+    }
+}
+
+/// Converts the game as seen by `user`, who should be one of the players.
+impl From<(LichessGameData, &str)> for GameData {
+    fn from((game, user): (LichessGameData, &str)) -> Self {
+        let (white_result, black_result) = game.results();
+        let player = |player: &LichessPlayer, result: GameResult| ArchivedPlayer {
+            username: player.username(),
+            rating: player.rating.unwrap_or_default(),
+            result,
+            id: format!("https://lichess.org/@/{}", player.username().to_lowercase()),
+            title: player.user.as_ref().and_then(|user| user.title),
+            country: None,
+        };
+        let accuracies = match (&game.players.white.analysis, &game.players.black.analysis) {
+            (Some(LichessAnalysis { accuracy: Some(white) }), Some(LichessAnalysis { accuracy: Some(black) })) => {
+                Some(Accuracies { white: *white, black: *black })
+            }
+            _ => None,
+        };
+        let tournament = game.tournament.as_ref()
+            .map(|id| format!("https://lichess.org/tournament/{}", id))
+            .or_else(|| game.swiss.as_ref().map(|id| format!("https://lichess.org/swiss/{}", id)));
+
+        let chess_com = ArchivedGame {
+            game_url: format!("https://lichess.org/{}", game.id),
+            time_control: game.time_control(),
+            start_time: Some((game.created_at / 1000) as u32),
+            end_time: (game.last_move_at / 1000) as u32,
+            rated: game.rated,
+            fen: game.final_fen(),
+            time_class: game.time_class(),
+            rules: game.rules(),
+            eco: None,
+            tournament,
+            team_match: None,
+            white: player(&game.players.white, white_result),
+            black: player(&game.players.black, black_result),
+            accuracies,
+            uuid: None,
+            initial_setup: Some(game.initial_fen.clone().unwrap_or_else(|| STARTING_FEN.into())),
+            tcn: None,
+            partner_game: None,
+            pgn: game.pgn,
+        };
+        (chess_com, user).into()
+    }
+}
+
+impl Client {
+    /// Downloads every game a lichess user has played, oldest first, as [GameData] from their side.
+    ///
+    /// Lichess streams the export a few dozen games a second, so this takes a while for active players. Chess.com
+    /// settings such as the [base URL](crate::ClientBuilder::base_url) and the cache don't apply; the rate limit and
+    /// retries do. [Error::UserNotFound](crate::Error::UserNotFound) if there is no such user, and
+    /// [Error::InvalidUsername](crate::Error::InvalidUsername) if it couldn't be a lichess username.
+    pub fn lichess_games(&self, user: &str) -> Result<Vec<GameData>> {
+        let url = format!(
+            "{}/games/user/{}?pgnInJson=true&clocks=true&opening=true&accuracy=true&sort=dateAsc",
+            LICHESS_API, lichess_username(user)?,
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static("application/x-ndjson"));
+        let body = self.get_streaming(&url, headers).map_err(|err| err.for_user(user))?.body;
+        // One game per line, read as each arrives rather than once the whole export is in
+        let mut games = vec![];
+        for line in BufReader::new(body).lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                let game: LichessGameData = serde_json::from_str(&line)?;
+                games.push((game, user).into());
+            }
+        }
+        Ok(games)
+    }
+}
+
+/// A lichess username: 2 to 30 letters, digits, underscores or hyphens, checked before it goes into a URL like the
+/// Chess.com ones are (see [Username](crate::Username)).
+fn lichess_username(user: &str) -> Result<String> {
+    let user = user.trim();
+    let valid = (2..=30).contains(&user.len()) && user.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if valid {
+        Ok(user.to_ascii_lowercase())
+    } else {
+        Err(Error::InvalidUsername(user.into()))
+    }
+}
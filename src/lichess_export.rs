@@ -0,0 +1,228 @@
+//! Games in the shapes lichess exports them, so tools written for lichess data can read Chess.com games unchanged.
+
+use std::io::{BufWriter, Write};
+
+use serde_json::{json, Map, Value};
+
+use crate::board::STARTING_FEN;
+use crate::export::{synthesized_pgn, tag};
+use crate::pgn::parse_tag;
+use crate::{Color, GameData, Result, Rules, Termination, TimeControl};
+
+/// Moves per player lichess assumes when sorting time controls into speeds.
+const LICHESS_ESTIMATED_MOVES: u32 = 40;
+
+/// Lichess's name for the variant. Bughouse has no lichess equivalent, so it (and anything unknown) keeps Chess.com's name.
+fn variant(rules: &Rules) -> String {
+    match rules {
+        Rules::Chess => "standard".into(),
+        Rules::Chess960 => "chess960".into(),
+        Rules::CrazyHouse => "crazyhouse".into(),
+        Rules::ThreeCheck => "threeCheck".into(),
+        Rules::KingOfTheHill => "kingOfTheHill".into(),
+        Rules::Horde => "horde".into(),
+        Rules::OddsChess => "fromPosition".into(),
+        other => String::from(other.clone()),
+    }
+}
+
+/// The `[Variant]` tag lichess writes for the variant, `None` for standard chess.
+fn variant_tag(rules: &Rules) -> Option<String> {
+    match rules {
+        Rules::Chess => None,
+        Rules::Chess960 => Some("Chess960".into()),
+        Rules::CrazyHouse => Some("Crazyhouse".into()),
+        Rules::ThreeCheck => Some("Three-check".into()),
+        Rules::KingOfTheHill => Some("King of the Hill".into()),
+        Rules::Horde => Some("Horde".into()),
+        Rules::OddsChess => Some("From Position".into()),
+        other => Some(String::from(other.clone())),
+    }
+}
+
+/// Lichess's speed, worked out from the time control the way lichess does it.
+fn speed(time_control: Option<TimeControl>) -> &'static str {
+    match time_control {
+        Some(TimeControl::Live { base, increment }) => match base + LICHESS_ESTIMATED_MOVES * increment {
+            0..=29 => "ultraBullet",
+            30..=179 => "bullet",
+            180..=479 => "blitz",
+            480..=1499 => "rapid",
+            _ => "classical",
+        },
+        Some(TimeControl::Daily { .. }) | Some(TimeControl::Unlimited) => "correspondence",
+        None => "unknown",
+    }
+}
+
+/// Lichess's `status` for how the game ended.
+fn status(termination: Termination) -> &'static str {
+    match termination {
+        Termination::Checkmate => "mate",
+        Termination::Resignation => "resign",
+        Termination::Timeout => "outoftime",
+        // Lichess calls a player leaving the game a timeout
+        Termination::Abandonment => "timeout",
+        Termination::Stalemate => "stalemate",
+        Termination::DrawAgreement | Termination::Repetition | Termination::FiftyMove | Termination::InsufficientMaterial => "draw",
+        Termination::RuleSpecific => "variantEnd",
+        _ => "unknownFinish",
+    }
+}
+
+/// A custom starting position, `None` when the game started from the standard one.
+fn initial_fen(game: &GameData) -> Option<&str> {
+    game.initial_setup.as_deref().filter(|fen| !fen.is_empty() && *fen != STARTING_FEN)
+}
+
+impl GameData {
+    /// Who won, `None` for a draw.
+    fn winner(&self) -> Option<Color> {
+        let player = if self.colour == "White" { Color::White } else { Color::Black };
+        if self.win == 1.0 {
+            Some(player)
+        } else if self.win == 0.0 {
+            Some(!player)
+        } else {
+            None
+        }
+    }
+
+    /// The game as a lichess API game object, the kind `https://lichess.org/api/games/user/{user}` streams as NDJSON.
+    ///
+    /// The `id` is Chess.com's game ID rather than an 8 character lichess one. Fields lichess has no data for (e.g.
+    /// `ratingDiff`) are left out rather than made up.
+    pub fn to_lichess_json(&self) -> Value {
+        let time_control: Option<TimeControl> = self.time_control.parse().ok();
+        let variant = variant(&self.rules);
+        let speed = speed(time_control);
+        let player = |username: &str, rating: u32, accuracy: Option<f32>| {
+            let mut player = json!({
+                "user": { "name": username, "id": username.to_lowercase() },
+                "rating": rating,
+            });
+            if let Some(accuracy) = accuracy {
+                player["analysis"] = json!({ "accuracy": accuracy.round() as u32 });
+            }
+            player
+        };
+
+        let mut object = Map::new();
+        object.insert("id".into(), self.game_url.rsplit('/').next().unwrap_or_default().into());
+        object.insert("rated".into(), self.rated.into());
+        object.insert("perf".into(), if variant == "standard" { speed.into() } else { variant.clone().into() });
+        object.insert("variant".into(), variant.into());
+        object.insert("speed".into(), speed.into());
+        object.insert("createdAt".into(), (u64::from(self.start_time.unwrap_or(self.end_time)) * 1000).into());
+        object.insert("lastMoveAt".into(), (u64::from(self.end_time) * 1000).into());
+        object.insert("status".into(), status(self.termination).into());
+        object.insert("players".into(), json!({
+            "white": player(&self.white_username, self.white_rating, self.white_accuracy),
+            "black": player(&self.black_username, self.black_rating, self.black_accuracy),
+        }));
+        if let Some(winner) = self.winner() {
+            object.insert("winner".into(), if winner == Color::White { "white" } else { "black" }.into());
+        }
+        if let Some(fen) = initial_fen(self) {
+            object.insert("initialFen".into(), fen.into());
+        }
+        if let Some(opening) = self.opening() {
+            object.insert("opening".into(), json!({ "eco": opening.eco, "name": opening.name }));
+        }
+        object.insert("moves".into(), self.moves.join(" ").into());
+        match time_control {
+            Some(TimeControl::Live { base, increment }) => {
+                object.insert("clock".into(), json!({
+                    "initial": base,
+                    "increment": increment,
+                    "totalTime": base + LICHESS_ESTIMATED_MOVES * increment,
+                }));
+            }
+            Some(TimeControl::Daily { seconds_per_move }) => {
+                object.insert("daysPerTurn".into(), (seconds_per_move / 86_400).into());
+            }
+            _ => {}
+        }
+        if let Some(tournament) = &self.tournament {
+            object.insert("tournament".into(), tournament.clone().into());
+        }
+        Value::Object(object)
+    }
+
+    /// The game's PGN with the tags lichess (and a lichess study import) relies on: `[Site]` is the game's URL, and
+    /// variants get a `[Variant]` tag, plus `[SetUp]` and `[FEN]` when the game didn't start from the standard position.
+    pub fn to_lichess_pgn(&self) -> String {
+        let pgn = match self.pgn.as_deref() {
+            Some(pgn) if !pgn.trim().is_empty() => pgn.replace("\r\n", "\n").trim().to_string(),
+            _ => synthesized_pgn(self),
+        };
+        let (header, movetext) = pgn.split_once("\n\n").unwrap_or(("", &pgn));
+
+        let mut tags = vec![];
+        let mut has_site = false;
+        let mut has_fen = false;
+        for line in header.lines() {
+            match line.strip_prefix('[').and_then(parse_tag) {
+                Some((name, _)) if name == "Site" => {
+                    has_site = true;
+                    tags.push(tag("Site", &self.game_url));
+                }
+                Some((name, _)) if name == "Variant" => {}
+                Some((name, _)) => {
+                    has_fen |= name == "FEN";
+                    tags.push(line.to_string());
+                }
+                None => tags.push(line.to_string()),
+            }
+        }
+        if !has_site {
+            tags.push(tag("Site", &self.game_url));
+        }
+        if let Some(variant) = variant_tag(&self.rules) {
+            tags.push(tag("Variant", &variant));
+        }
+        if let (Some(fen), false) = (initial_fen(self), has_fen) {
+            tags.push(tag("SetUp", "1"));
+            tags.push(tag("FEN", fen));
+        }
+        format!("{}\n\n{}", tags.join("\n"), movetext.trim())
+    }
+
+    /// Writes games as lichess-style NDJSON, one [lichess game object](GameData::to_lichess_json) per line. Returns how
+    /// many games were written. See [GameData::write_ndjson] for Hikaru's own format.
+    ///
+    /// ```rust,no_run
+    /// use std::fs::File;
+    /// use hikaru::GameData;
+    ///
+    /// # fn main() -> hikaru::Result<()> {
+    /// let games = GameData::download_iter("hikaru")?;
+    /// GameData::write_lichess_ndjson(games, File::create("hikaru.lichess.ndjson")?)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_lichess_ndjson<I, W>(games: I, writer: W) -> Result<usize>
+    where
+        I: IntoIterator<Item = Result<GameData>>,
+        W: Write,
+    {
+        let mut writer = BufWriter::new(writer);
+        let mut written = 0;
+        for game in games {
+            writeln!(writer, "{}", serde_json::to_string(&game?.to_lichess_json())?)?;
+            written += 1;
+        }
+        writer.flush()?;
+        Ok(written)
+    }
+
+    /// Like [GameData::export_pgn], but with each game's tags [made ready for lichess](GameData::to_lichess_pgn).
+    pub fn export_lichess_pgn<W: Write>(games: &[GameData], writer: W) -> Result<()> {
+        let mut writer = BufWriter::new(writer);
+        for game in games {
+            writeln!(writer, "{}\n", game.to_lichess_pgn())?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+//! A much smaller stand-in for [GameData], for when results and ratings are all that's wanted from a large download.
+
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::{Download, Downloaded};
+use crate::{ArchivedGame, Client, Color, DownloadOptions, GameData, GameResult, GameResultWinLose, MonthGames, Result, TimeClass};
+
+/// A game from one player's point of view, like [GameData] but without the PGN, moves, openings and everything else
+/// that takes up space. Every field is a number or a short string, so millions of these fit comfortably in memory.
+///
+/// Download them with [Client::games_lite], which never builds the full [GameData], or shrink games you already have
+/// with `From<&GameData>`.
+///
+/// Like [GameData], these compare, hash and sort by `end_time`, then `game_url`, then `colour`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameDataLite {
+    pub game_url: String,
+    pub start_time: Option<u32>,
+    pub end_time: u32,
+    pub time_class: TimeClass,
+    pub rated: bool,
+    /// The colour the player had.
+    pub colour: Color,
+    /// The player's rating.
+    pub rating: u32,
+    pub opponent_rating: u32,
+    /// How the game ended for the player.
+    pub result: GameResult,
+    pub result_win_lose: GameResultWinLose,
+    /// 1 for a win, 0.5 for a draw and 0 for a loss.
+    pub win: f32,
+}
+
+impl GameDataLite {
+    fn key(&self) -> (u32, &str, Color) {
+        (self.end_time, &self.game_url, self.colour)
+    }
+}
+
+impl PartialEq for GameDataLite {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for GameDataLite {}
+
+impl Hash for GameDataLite {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl PartialOrd for GameDataLite {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GameDataLite {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl From<(ArchivedGame, &str)> for GameDataLite {
+    fn from((game, user): (ArchivedGame, &str)) -> Self {
+        let is_white = user.eq_ignore_ascii_case(&game.white.username);
+        let (player, opponent) = if is_white {(game.white, game.black)} else {(game.black, game.white)};
+        let result_win_lose = GameResultWinLose::from_results(&player.result, &opponent.result);
+        GameDataLite {
+            game_url: game.game_url,
+            start_time: game.start_time,
+            end_time: game.end_time,
+            time_class: game.time_class,
+            rated: game.rated,
+            colour: if is_white {Color::White} else {Color::Black},
+            rating: player.rating,
+            opponent_rating: opponent.rating,
+            result: player.result,
+            win: match result_win_lose {
+                GameResultWinLose::Win => 1.0,
+                GameResultWinLose::Draw => 0.5,
+                GameResultWinLose::Loss => 0.0,
+            },
+            result_win_lose,
+        }
+    }
+}
+
+impl From<&GameData> for GameDataLite {
+    fn from(game: &GameData) -> Self {
+        GameDataLite {
+            game_url: game.game_url.clone(),
+            start_time: game.start_time,
+            end_time: game.end_time,
+            time_class: game.time_class.clone(),
+            rated: game.rated,
+            colour: if game.colour == "White" {Color::White} else {Color::Black},
+            rating: game.rating,
+            opponent_rating: game.opponent_rating,
+            result: game.result.clone(),
+            result_win_lose: game.result_win_lose,
+            win: game.win,
+        }
+    }
+}
+
+impl Client {
+    /// Like [Client::games], but only keeps what's in [GameDataLite]. PGNs are dropped a month at a time as they are
+    /// read, so a whole career takes a fraction of the memory.
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// let games = hikaru::Client::new().games_lite("hikaru")?;
+    /// let score: f32 = games.iter().map(|game| game.win).sum();
+    /// println!("{} points from {} games", score, games.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn games_lite(&self, user: &str) -> Result<Vec<GameDataLite>> {
+        self.games_lite_with(user, &DownloadOptions::default())
+    }
+
+    /// Like [Client::games_with], but only keeps what's in [GameDataLite]. The options' [filter](DownloadOptions::filter)
+    /// needs the full [GameData], so only its date range applies; filter the lite games afterwards instead.
+    ///
+    /// Everything else in the options applies as it does for [Client::games_with_report], e.g. the
+    /// [order](DownloadOptions::order) and [skipped archives](DownloadOptions::skip_failed_archives).
+    pub fn games_lite_with(&self, user: &str, options: &DownloadOptions) -> Result<Vec<GameDataLite>> {
+        Ok(self.download(user, options, &Lite)?.0)
+    }
+}
+
+/// Downloads [GameDataLite]s, converting each month's games as soon as it has been read.
+struct Lite;
+
+impl Download for Lite {
+    type Month = MonthGames;
+    type Game = GameDataLite;
+
+    fn fetch(&self, client: &Client, url: &str) -> Result<MonthGames> {
+        client.get_month(url)
+    }
+
+    fn convert(&self, options: &DownloadOptions, url: &str, month: MonthGames, user: &str) -> Result<Vec<GameDataLite>> {
+        options.game_data_lite(url, month, user).into_iter().collect()
+    }
+}
+
+impl Downloaded for GameDataLite {
+    fn end_time(&self) -> u32 {
+        self.end_time
+    }
+
+    fn game_url(&self) -> &str {
+        &self.game_url
+    }
+}
@@ -0,0 +1,134 @@
+//! Looking up the profiles of everyone a player has faced, for filtering out banned accounts or breaking results down
+//! by title and country.
+
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{Client, Error, GameData, Profile, Result, Username};
+
+/// The parts of an opponent's [Profile] that are useful alongside their games.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpponentProfile {
+    /// Always lowercase.
+    pub username: String,
+    /// e.g. `GM`. Only there for titled players.
+    pub title: Option<String>,
+    /// The ISO 3166 code of the country on their profile, e.g. `US`. Chess.com also has a few codes of its own, e.g.
+    /// `XE` for England.
+    pub country: String,
+    /// e.g. `premium`, `basic`, or `closed:fair_play_violations` for accounts shut down for cheating.
+    pub account_status: String,
+}
+
+impl From<Profile> for OpponentProfile {
+    fn from(profile: Profile) -> Self {
+        let country = profile.country.rsplit('/').next().unwrap_or_default().to_string();
+        OpponentProfile {
+            username: profile.username,
+            title: profile.title,
+            country,
+            account_status: profile.status,
+        }
+    }
+}
+
+impl OpponentProfile {
+    /// Whether the account has been closed, for whatever reason.
+    pub fn is_closed(&self) -> bool {
+        self.account_status.starts_with("closed")
+    }
+
+    /// Whether the account was closed for breaking the fair play policy, i.e. for cheating.
+    pub fn is_fair_play_violation(&self) -> bool {
+        self.account_status == "closed:fair_play_violations"
+    }
+}
+
+/// The profiles of the opponents in a set of games, from [Client::opponent_profiles]. Kept to the side rather than in
+/// each [GameData], since most players meet the same opponents many times.
+#[derive(Debug, Clone, Default)]
+pub struct Opponents {
+    profiles: HashMap<String, OpponentProfile>,
+}
+
+impl Opponents {
+    /// The profile of a user, whatever the capitalisation.
+    pub fn get(&self, username: &str) -> Option<&OpponentProfile> {
+        self.profiles.get(&username.to_lowercase())
+    }
+
+    /// The profile of the game's opponent. `None` if it couldn't be found, e.g. because the account was deleted.
+    pub fn for_game(&self, game: &GameData) -> Option<&OpponentProfile> {
+        self.get(&game.opponent_username)
+    }
+
+    pub fn len(&self) -> usize {
+        self.profiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.profiles.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &OpponentProfile> {
+        self.profiles.values()
+    }
+}
+
+impl Client {
+    /// Downloads the profile of everyone the games were played against, once per opponent. Requests go through the
+    /// client's [cache](crate::ClientBuilder::cache) and [rate limit](crate::ClientBuilder::rate_limit), so it's worth
+    /// setting both before enriching thousands of games.
+    ///
+    /// Opponents without a profile any more, and names that aren't valid Chess.com usernames, are left out rather than
+    /// failing the lookup.
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// let client = hikaru::Client::builder().rate_limit(2.0).build()?;
+    /// let games = client.games("hikaru")?;
+    /// let opponents = client.opponent_profiles(&games)?;
+    /// let against_cheaters = games.iter()
+    ///     .filter(|game| opponents.for_game(game).is_some_and(|opponent| opponent.is_fair_play_violation()))
+    ///     .count();
+    /// println!("{} games against accounts closed for fair play violations", against_cheaters);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn opponent_profiles(&self, games: &[GameData]) -> Result<Opponents> {
+        let usernames: BTreeSet<String> = games.iter().map(|game| game.opponent_username.to_lowercase()).collect();
+        let mut profiles = HashMap::new();
+        for username in usernames {
+            // Names that can't be Chess.com accounts, e.g. "Stockfish level 3" from a Lichess game, have no profile either
+            let valid = match Username::new(&username) {
+                Ok(valid) => valid,
+                Err(_) => continue,
+            };
+            let url = self.endpoint(&format!("player/{}", valid));
+            let profile: Profile = match self.get_cached_text(&url).map_err(|err| err.for_user(&username)) {
+                Ok(text) => serde_json::from_str(&text)?,
+                Err(Error::UserNotFound(_)) => continue,
+                Err(err) => return Err(err),
+            };
+            profiles.insert(username, profile.into());
+        }
+        Ok(Opponents { profiles })
+    }
+}
+
+/// Drops the games against accounts that have since been closed for fair play violations, whose results skew rating
+/// and opening statistics. Looks up each opponent's profile with [Client::opponent_profiles]; to keep the games and
+/// just flag them, use that directly with [OpponentProfile::is_fair_play_violation].
+///
+/// ```rust,no_run
+/// # fn main() -> hikaru::Result<()> {
+/// let client = hikaru::Client::new();
+/// let games = hikaru::exclude_closed_accounts(client.games("hikaru")?, &client)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn exclude_closed_accounts(games: Vec<GameData>, client: &Client) -> Result<Vec<GameData>> {
+    let opponents = client.opponent_profiles(&games)?;
+    Ok(games.into_iter()
+        .filter(|game| !opponents.for_game(game).is_some_and(OpponentProfile::is_fair_play_violation))
+        .collect())
+}
@@ -0,0 +1,360 @@
+//! Options that control which of a player's games get downloaded.
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::date::year_month;
+use crate::trace::event;
+use crate::{ArchivedGame, CacheConfig, CancellationToken, Error, GameData, GameDataLite, GameFilter, GameParseError, MonthGames, Result};
+
+/// What a download is up to, passed to the callback set with [DownloadOptions::on_progress].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Progress {
+    /// The list of archives came back. `count` is how many of them will be downloaded.
+    ArchiveListFetched { count: usize },
+    /// A monthly archive was downloaded. `games` counts every game in it, including any that are then filtered out.
+    ArchiveDownloaded { month: (u16, u8), games: usize },
+    /// A game made it through the filters and was turned into [GameData].
+    GameParsed,
+    /// A game couldn't be read and was left out. Only with [DownloadOptions::skip_invalid_games].
+    GameSkipped(GameParseError),
+    /// A monthly archive couldn't be downloaded or read and was left out. Only with [DownloadOptions::skip_failed_archives].
+    ArchiveSkipped { month: (u16, u8) },
+    /// The download was [cancelled](DownloadOptions::cancel_on) with `remaining` archives still to go.
+    Cancelled { remaining: usize },
+}
+
+/// Which end of a player's history a download starts from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DownloadOrder {
+    /// Oldest month first, and the games in each month in the order they ended.
+    #[default]
+    OldestFirst,
+    /// Newest month first, and the games in each month from the last to end to the first, e.g. to show recent games
+    /// while the rest of a [GameIter](crate::GameIter) is still downloading.
+    NewestFirst,
+}
+
+/// A progress callback. Wrapped so [DownloadOptions] can still be `Debug`.
+#[derive(Clone)]
+struct ProgressHook(Arc<dyn Fn(&Progress) + Send + Sync>);
+
+impl fmt::Debug for ProgressHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressHook")
+    }
+}
+
+/// Narrows down a download and says how to go about it. The defaults download everything, one month at a time.
+///
+/// Months are inclusive on both ends, so this downloads all of 2022 and the first half of 2023:
+///
+/// ```rust,no_run
+/// use hikaru::{Client, DownloadOptions};
+///
+/// # fn main() -> hikaru::Result<()> {
+/// let options = DownloadOptions::new()
+///     .since(2022, 1)
+///     .until(2023, 6);
+/// let games = Client::new().games_with("hikaru", &options)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Everything else about a download is set here too, so [GameData::download_with] covers the advanced cases:
+///
+/// ```rust,no_run
+/// use hikaru::{CacheConfig, DownloadOptions, GameData, GameFilter, TimeClass};
+///
+/// # fn main() -> hikaru::Result<()> {
+/// let options = DownloadOptions::new()
+///     .since(2022, 1)
+///     .filter(GameFilter::new().time_class(TimeClass::Blitz))
+///     .concurrency(4)
+///     .cache(CacheConfig::new("chess-cache"))
+///     .max_retries(5)
+///     .on_progress(|event| eprintln!("{:?}", event));
+/// let games = GameData::download_with("hikaru", options)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DownloadOptions {
+    since: Option<(u16, u8)>,
+    until: Option<(u16, u8)>,
+    ended_after: Option<u32>,
+    on_progress: Option<ProgressHook>,
+    skip_invalid_games: bool,
+    skip_failed_archives: bool,
+    filter: GameFilter,
+    concurrency: Option<usize>,
+    pub(crate) cache: Option<CacheConfig>,
+    pub(crate) max_retries: Option<u32>,
+    pub(crate) rate_limit: Option<f64>,
+    pub(crate) timeout: Option<Duration>,
+    cancellation: Option<CancellationToken>,
+    order: DownloadOrder,
+}
+
+impl DownloadOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Skips archives before this month.
+    pub fn since(mut self, year: u16, month: u8) -> Self {
+        self.since = Some((year, month));
+        self
+    }
+
+    /// Skips archives after this month.
+    pub fn until(mut self, year: u16, month: u8) -> Self {
+        self.until = Some((year, month));
+        self
+    }
+
+    /// Only downloads games that ended after this Unix timestamp, e.g. the `end_time` of the newest game you already have.
+    ///
+    /// Archives for months before the timestamp are skipped entirely, so repeated syncs only fetch the latest month or two.
+    pub fn ended_after(mut self, end_time: u32) -> Self {
+        self.ended_after = Some(end_time);
+        self
+    }
+
+    /// Downloads the newest games first, or the oldest (the default).
+    ///
+    /// ```rust,no_run
+    /// use hikaru::{Client, DownloadOptions, DownloadOrder};
+    ///
+    /// # fn main() -> hikaru::Result<()> {
+    /// let options = DownloadOptions::new().order(DownloadOrder::NewestFirst);
+    /// // Only downloads the latest month or two
+    /// for game in Client::new().games_iter_with("hikaru", options)?.take(20) {
+    ///     println!("{}", game?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Used by [Client::games_with](crate::Client::games_with), [Client::games_with_report](crate::Client::games_with_report),
+    /// [Client::games_lite_with](crate::Client::games_lite_with) and [Client::games_iter_with](crate::Client::games_iter_with),
+    /// and the async client's `games_with`.
+    pub fn order(mut self, order: DownloadOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    pub(crate) fn newest_first(&self) -> bool {
+        self.order == DownloadOrder::NewestFirst
+    }
+
+    /// Calls `callback` as the download goes along, e.g. to drive a progress bar.
+    ///
+    /// ```rust,no_run
+    /// use hikaru::{Client, DownloadOptions, Progress};
+    ///
+    /// # fn main() -> hikaru::Result<()> {
+    /// let options = DownloadOptions::new().on_progress(|event| match event {
+    ///     Progress::ArchiveListFetched { count } => println!("{} months to download", count),
+    ///     Progress::ArchiveDownloaded { month: (year, month), games } => println!("{}-{:02}: {} games", year, month, games),
+    ///     _ => {}
+    /// });
+    /// let games = Client::new().games_with("hikaru", &options)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// With the `async` feature the callback may be called from whichever thread polls the download.
+    pub fn on_progress(mut self, callback: impl Fn(&Progress) + Send + Sync + 'static) -> Self {
+        self.on_progress = Some(ProgressHook(Arc::new(callback)));
+        self
+    }
+
+    /// Leaves out games that can't be read, instead of failing the whole download with [Error::GameParse]. Each one
+    /// is reported as [Progress::GameSkipped], so nothing goes missing unnoticed.
+    ///
+    /// A [GameIter](crate::GameIter) yields unreadable games as errors and carries on either way; this just leaves them out.
+    pub fn skip_invalid_games(mut self, skip: bool) -> Self {
+        self.skip_invalid_games = skip;
+        self
+    }
+
+    /// Leaves out monthly archives that can't be downloaded or read (after retrying), instead of failing the whole
+    /// download. Each one is reported as [Progress::ArchiveSkipped], and listed in the
+    /// [DownloadReport](crate::DownloadReport) from [Client::games_with_report](crate::Client::games_with_report).
+    ///
+    /// Only the blocking [Client](crate::Client)'s [games_with](crate::Client::games_with),
+    /// [games_with_report](crate::Client::games_with_report) and [games_lite_with](crate::Client::games_lite_with) skip
+    /// archives.
+    pub fn skip_failed_archives(mut self, skip: bool) -> Self {
+        self.skip_failed_archives = skip;
+        self
+    }
+
+    pub(crate) fn skips_failed_archives(&self) -> bool {
+        self.skip_failed_archives
+    }
+
+    /// Only keeps the games that pass `filter`. Its date range also narrows down which archives are downloaded.
+    pub fn filter(mut self, filter: GameFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Downloads up to this many monthly archives at the same time. Games still come back in [order](DownloadOptions::order). Defaults to 1.
+    ///
+    /// Used by [Client::games_with](crate::Client::games_with), [Client::games_with_report](crate::Client::games_with_report)
+    /// and [Client::games_lite_with](crate::Client::games_lite_with). Keep it to a handful, Chess.com rate limits clients that make too many parallel requests.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = Some(concurrency.max(1));
+        self
+    }
+
+    pub(crate) fn concurrency_or_default(&self) -> usize {
+        self.concurrency.unwrap_or(1)
+    }
+
+    /// Caches archives on disk, like [ClientBuilder::cache](crate::ClientBuilder::cache).
+    ///
+    /// This and the other client settings ([max_retries](DownloadOptions::max_retries),
+    /// [rate_limit](DownloadOptions::rate_limit), [timeout](DownloadOptions::timeout)) replace the client's own for the
+    /// download they are passed to, whichever method it is. The rate limit starts afresh for each download rather than
+    /// being shared with the client's other requests. A [custom transport](crate::ClientBuilder::transport) is left to
+    /// handle timeouts itself.
+    pub fn cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// How many times failed requests are retried, like [ClientBuilder::max_retries](crate::ClientBuilder::max_retries).
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    /// Sends at most this many requests per second, like [ClientBuilder::rate_limit](crate::ClientBuilder::rate_limit).
+    /// A rate that isn't positive lifts the client's own limit for the download.
+    pub fn rate_limit(mut self, requests_per_second: f64) -> Self {
+        self.rate_limit = Some(requests_per_second);
+        self
+    }
+
+    /// Gives up on a request that takes longer than this, like [ClientBuilder::timeout](crate::ClientBuilder::timeout).
+    /// Timeouts are retried like other failed requests.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Stops the download before the next monthly archive once `token` is [cancelled](CancellationToken::cancel),
+    /// keeping the games it already has.
+    ///
+    /// [Client::games_with](crate::Client::games_with) returns those games as if the download had finished; use
+    /// [games_with_report](crate::Client::games_with_report) to tell the difference. A [GameIter](crate::GameIter)
+    /// stops, and its [checkpoint](crate::GameIter::checkpoint) picks up where it left off.
+    pub fn cancel_on(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancellation.as_ref().is_some_and(CancellationToken::is_cancelled)
+    }
+
+    /// Whether any of the client settings are set, so the client has to be [configured](crate::Client::configured).
+    pub(crate) fn configures_client(&self) -> bool {
+        self.cache.is_some() || self.max_retries.is_some() || self.rate_limit.is_some() || self.timeout.is_some()
+    }
+
+    pub(crate) fn report(&self, event: Progress) {
+        if let Some(ProgressHook(callback)) = &self.on_progress {
+            callback(&event);
+        }
+    }
+
+    /// Reports a downloaded archive, then filters its games and converts them, reporting each one. Games that couldn't
+    /// be read are errors, unless they are being skipped.
+    pub(crate) fn game_data(&self, url: &str, games: MonthGames, user: &str) -> Vec<Result<GameData>> {
+        self.convert(url, games, |game| {
+            let data: GameData = (game, user).into();
+            Some(data).filter(|data| self.matches(data))
+        })
+    }
+
+    /// Like `game_data`, but into [GameDataLite]. The [filter](DownloadOptions::filter) needs a full [GameData], so only
+    /// its date range (which picks the archives) applies.
+    pub(crate) fn game_data_lite(&self, url: &str, games: MonthGames, user: &str) -> Vec<Result<GameDataLite>> {
+        self.convert(url, games, |game| Some((game, user).into()))
+    }
+
+    /// Like `game_data`, but keeps the games as they were in the archive. Only the date range applies.
+    pub(crate) fn archived_games(&self, url: &str, games: MonthGames) -> Vec<Result<ArchivedGame>> {
+        self.convert(url, games, Some)
+    }
+
+    /// Converts the games that end in the selected range, dropping any that `convert` turns into `None`.
+    fn convert<T>(&self, url: &str, games: MonthGames, convert: impl Fn(ArchivedGame) -> Option<T>) -> Vec<Result<T>> {
+        self.selected_games(url, games).into_iter()
+            .filter_map(|game| match game {
+                Ok(game) => {
+                    let data = convert(game)?;
+                    self.report(Progress::GameParsed);
+                    Some(Ok(data))
+                }
+                Err(err) => Some(Err(err)),
+            })
+            .collect()
+    }
+
+    /// Reports a downloaded archive, and keeps the games that end in the selected range. Games that couldn't be read are
+    /// errors, unless they are being skipped.
+    pub(crate) fn selected_games(&self, url: &str, games: MonthGames) -> Vec<Result<ArchivedGame>> {
+        self.report(Progress::ArchiveDownloaded { month: archive_month(url).unwrap_or_default(), games: games.len() });
+        games.into_iter()
+            .filter_map(|game| match game {
+                Ok(game) if self.includes_game(&game) => Some(Ok(game)),
+                Ok(_) => None,
+                Err(err) if self.skip_invalid_games => {
+                    event!(warn, archive = %err.archive, index = err.index, error = %err.message, "skipping a game that couldn't be parsed");
+                    self.report(Progress::GameSkipped(err));
+                    None
+                }
+                Err(err) => {
+                    event!(error, archive = %err.archive, index = err.index, error = %err.message, "couldn't parse a game");
+                    Some(Err(Error::GameParse(err)))
+                }
+            })
+            .collect()
+    }
+
+    /// Whether a game passes the [filter](DownloadOptions::filter).
+    pub(crate) fn matches(&self, game: &GameData) -> bool {
+        self.filter.matches(game)
+    }
+
+    /// Whether the archive at `url` should be downloaded. Archives whose month can't be read from the URL are always downloaded.
+    pub(crate) fn includes_archive(&self, url: &str) -> bool {
+        let month = match archive_month(url) {
+            Some(month) => month,
+            None => return true,
+        };
+        let since = self.since.max(self.ended_after.map(|end_time| year_month(end_time.into())));
+        since.is_none_or(|since| month >= since)
+            && self.until.is_none_or(|until| month <= until)
+            && self.filter.includes_month(month)
+    }
+
+    pub(crate) fn includes_game(&self, game: &ArchivedGame) -> bool {
+        self.ended_after.is_none_or(|end_time| game.end_time > end_time)
+    }
+}
+
+/// Reads the year and month from an archive URL, e.g. `https://api.chess.com/pub/player/hikaru/games/2014/01`.
+pub(crate) fn archive_month(url: &str) -> Option<(u16, u8)> {
+    let mut segments = url.trim_end_matches('/').rsplit('/');
+    let month = segments.next()?.parse().ok()?;
+    let year = segments.next()?.parse().ok()?;
+    Some((year, month))
+}
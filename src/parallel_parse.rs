@@ -0,0 +1,58 @@
+//! Converting downloaded games on every core with rayon. Enabled with the `rayon` feature.
+//!
+//! For players with many years of games, replaying every PGN takes longer than downloading the archives does once they
+//! are cached. Each archive is still read as it arrives; it's turning its games into [GameData] that is spread out.
+
+use rayon::prelude::*;
+
+use crate::client::Download;
+use crate::{ArchivedGame, Client, DownloadOptions, GameData, MonthGames, Progress, Result};
+
+impl Client {
+    /// Like [Client::games], but converts each month's games in parallel. The archives are still downloaded one after
+    /// another, and the games come back oldest first as usual.
+    pub fn games_parallel_parse(&self, user: &str) -> Result<Vec<GameData>> {
+        self.games_parallel_parse_with(user, &DownloadOptions::default())
+    }
+
+    /// Like [Client::games_with], but converts each month's games in parallel. Everything in the options applies as it
+    /// does there, including their [concurrency](DownloadOptions::concurrency) for the downloads.
+    pub fn games_parallel_parse_with(&self, user: &str, options: &DownloadOptions) -> Result<Vec<GameData>> {
+        Ok(self.download(user, options, &ParallelParse)?.0)
+    }
+}
+
+/// Downloads the full [GameData], converting the games of each month with rayon.
+struct ParallelParse;
+
+impl Download for ParallelParse {
+    type Month = MonthGames;
+    type Game = GameData;
+
+    fn fetch(&self, client: &Client, url: &str) -> Result<MonthGames> {
+        client.get_month(url)
+    }
+
+    fn convert(&self, options: &DownloadOptions, url: &str, month: MonthGames, user: &str) -> Result<Vec<GameData>> {
+        let games: Vec<ArchivedGame> = options.selected_games(url, month).into_iter().collect::<Result<_>>()?;
+        Ok(games.into_par_iter()
+            .map(|game| GameData::from((game, user)))
+            .filter(|game| options.matches(game))
+            .inspect(|_| options.report(Progress::GameParsed))
+            .collect())
+    }
+}
+
+impl GameData {
+    /// Downloads a user's games with [Client::games_parallel_parse], using a [Client] with the default settings.
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// let games = hikaru::GameData::download_parallel_parse("hikaru")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn download_parallel_parse(user: &str) -> Result<Vec<GameData>> {
+        Client::new().games_parallel_parse(user)
+    }
+}
@@ -0,0 +1,92 @@
+//! Performance ratings: the rating at which a set of results would have been expected, given who they were against.
+
+use std::collections::BTreeMap;
+
+use crate::{GameData, Granularity};
+
+/// Uncapped, a perfect (or zero) score has no finite performance. Like FIDE, Hikaru stops 800 points past the opponents.
+const MAX_RATING_GAP: f64 = 800.0;
+
+/// A player's expected score against an opponent under the Elo model, from 0 to 1.
+///
+/// ```rust
+/// assert_eq!(hikaru::expected_score(1500.0, 1500.0), 0.5);
+/// assert!((hikaru::expected_score(1900.0, 1500.0) - 0.909).abs() < 0.001);
+/// ```
+pub fn expected_score(rating: f64, opponent_rating: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent_rating - rating) / 400.0))
+}
+
+/// The rating at which the games' total expected score equals the points actually scored, i.e. how strongly the player
+/// played. Perfect and zero scores come out 800 points above the strongest (or below the weakest) opponent. 0 without
+/// any games.
+///
+/// The games should all be from the same player's perspective.
+pub fn performance_rating(games: &[GameData]) -> f64 {
+    performance(&games.iter().collect::<Vec<_>>())
+}
+
+fn performance(games: &[&GameData]) -> f64 {
+    if games.is_empty() {
+        return 0.0;
+    }
+    let opponents: Vec<f64> = games.iter().map(|game| f64::from(game.opponent_rating)).collect();
+    let points: f64 = games.iter().map(|game| f64::from(game.win)).sum();
+    let expected = |rating: f64| opponents.iter().map(|&opponent| expected_score(rating, opponent)).sum::<f64>();
+
+    let mut low = opponents.iter().copied().fold(f64::INFINITY, f64::min) - MAX_RATING_GAP;
+    let mut high = opponents.iter().copied().fold(f64::NEG_INFINITY, f64::max) + MAX_RATING_GAP;
+    // The expected score only goes up with the rating, so halving the range homes in on the answer
+    for _ in 0..100 {
+        let middle = (low + high) / 2.0;
+        if expected(middle) < points {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    (low + high) / 2.0
+}
+
+/// How a player performed over one day or week.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PerformancePoint {
+    /// The start of the period, as a Unix timestamp.
+    pub period_start: u32,
+    pub games: u32,
+    /// Points scored as a share of the games, from 0 to 1.
+    pub score: f64,
+    /// See [performance_rating].
+    pub performance: f64,
+}
+
+/// The [performance rating](performance_rating) of each day or week that had games, oldest first.
+///
+/// ```rust,no_run
+/// use hikaru::{GameData, Granularity, TimeClass};
+///
+/// # fn main() -> hikaru::Result<()> {
+/// let games: Vec<GameData> = GameData::download(vec!["hikaru"])?
+///     .into_iter()
+///     .filter(|game| game.time_class == TimeClass::Blitz)
+///     .collect();
+/// for week in hikaru::performance_by_period(&games, Granularity::Week) {
+///     println!("{}: {:.0} over {} games", week.period_start, week.performance, week.games);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn performance_by_period(games: &[GameData], granularity: Granularity) -> Vec<PerformancePoint> {
+    let mut periods: BTreeMap<u32, Vec<&GameData>> = BTreeMap::new();
+    for game in games {
+        periods.entry(granularity.period_start(game.end_time)).or_default().push(game);
+    }
+    periods.into_iter()
+        .map(|(period_start, games)| PerformancePoint {
+            period_start,
+            games: games.len() as u32,
+            score: games.iter().map(|game| f64::from(game.win)).sum::<f64>() / games.len() as f64,
+            performance: performance(&games),
+        })
+        .collect()
+}
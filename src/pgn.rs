@@ -0,0 +1,275 @@
+//! A small PGN parser, covering what Chess.com puts in its PGNs: tag pairs, the movetext, and `[%clk]` clock annotations.
+//!
+//! Comments, variations and NAGs are skipped rather than rejected, so the parser never fails; anything it doesn't recognize
+//! is simply not in the output.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::Peekable;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Color;
+
+/// A move in Standard Algebraic Notation, exactly as it appears in the PGN (e.g. `Nf3`, `exd5`, `O-O`, `e8=Q+`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct San(String);
+
+impl San {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for San {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<San> for String {
+    fn from(san: San) -> Self {
+        san.0
+    }
+}
+
+/// A parsed PGN.
+///
+/// ```rust
+/// use hikaru::ParsedPgn;
+///
+/// let pgn = ParsedPgn::parse("[Event \"Live Chess\"]\n\n1. e4 {[%clk 0:02:59.9]} 1... e5 {[%clk 0:02:58.1]} 1-0");
+///
+/// assert_eq!(pgn.tags["Event"], "Live Chess");
+/// assert_eq!(pgn.moves.len(), 2);
+/// assert_eq!(pgn.moves[0].as_str(), "e4");
+/// assert_eq!(pgn.clock_times[1].as_millis(), 178_100);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParsedPgn {
+    /// Every tag pair in the header, e.g. `"ECO" => "B33"`.
+    pub tags: HashMap<String, String>,
+    /// The main line, without move numbers, annotations or the result.
+    pub moves: Vec<San>,
+    /// Remaining clock time from each `[%clk]` annotation, in the order they appear. Chess.com annotates every move,
+    /// so this normally lines up with `moves`.
+    pub clock_times: Vec<Duration>,
+}
+
+impl ParsedPgn {
+    /// Parses a PGN. Clock annotations that aren't a time are left out of [clock_times](ParsedPgn::clock_times) like any
+    /// other comment.
+    ///
+    /// ```rust
+    /// use hikaru::ParsedPgn;
+    ///
+    /// let pgn = ParsedPgn::parse("1. e4 {[%clk nan]} e5 {[%clk -0:00:01]} 2. Nf3 {[%clk inf]} Nc6 {[%clk 0:00:1e400]} *");
+    /// assert_eq!(pgn.moves.len(), 4);
+    /// assert!(pgn.clock_times.is_empty());
+    /// ```
+    pub fn parse(pgn: &str) -> Self {
+        let mut parsed = ParsedPgn::default();
+        let mut chars = pgn.chars().peekable();
+        let mut token = String::new();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '[' => {
+                    if let Some((name, value)) = read_tag(&mut chars) {
+                        parsed.tags.insert(name, value);
+                    }
+                }
+                '{' => {
+                    let comment: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                    if let Some(clock) = parse_clock(&comment) {
+                        parsed.clock_times.push(clock);
+                    }
+                }
+                ';' => {
+                    chars.by_ref().take_while(|&c| c != '\n').for_each(drop);
+                }
+                '(' => {
+                    let mut depth = 1;
+                    for c in chars.by_ref() {
+                        match c {
+                            '(' => depth += 1,
+                            ')' => depth -= 1,
+                            _ => {}
+                        }
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                }
+                c if c.is_whitespace() => {}
+                c => {
+                    token.clear();
+                    token.push(c);
+                    while let Some(&next) = chars.peek() {
+                        if next.is_whitespace() || "{}[]();".contains(next) {
+                            break;
+                        }
+                        token.push(next);
+                        chars.next();
+                    }
+                    if let Some(san) = parse_move(&token) {
+                        parsed.moves.push(san);
+                    }
+                }
+            }
+        }
+
+        parsed
+    }
+
+    /// Reads just the tag pairs at the start of a PGN, without going through the moves.
+    ///
+    /// ```rust
+    /// use hikaru::ParsedPgn;
+    ///
+    /// let tags = ParsedPgn::parse_tags("[Event \"Let's Play!\"]\r\n[Annotator \"\\\"Hikaru\\\" Nakamura\"]\r\n\r\n1. e4 *");
+    /// assert_eq!(tags["Event"], "Let's Play!");
+    /// assert_eq!(tags["Annotator"], "\"Hikaru\" Nakamura");
+    /// ```
+    pub fn parse_tags(pgn: &str) -> HashMap<String, String> {
+        let mut tags = HashMap::new();
+        let mut chars = pgn.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '[' => tags.extend(read_tag(&mut chars)),
+                c if c.is_whitespace() => {}
+                _ => break,
+            }
+        }
+        tags
+    }
+}
+
+/// Each player's remaining clock time after each of their moves, split out of [ParsedPgn::clock_times].
+///
+/// ```rust
+/// use std::time::Duration;
+/// use hikaru::{Color, ParsedPgn};
+///
+/// let pgn = ParsedPgn::parse("1. e4 {[%clk 0:02:59.9]} 1... e5 {[%clk 0:02:58.1]} 2. Nf3 {[%clk 0:02:57]} *");
+/// let clocks = pgn.player_clock_times();
+/// assert_eq!(clocks.white, vec![Duration::from_millis(179_900), Duration::from_secs(177)]);
+/// assert_eq!(clocks.black, vec![Duration::from_millis(178_100)]);
+/// assert_eq!(clocks.lowest(Color::White), Some(Duration::from_secs(177)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClockTimes {
+    pub white: Vec<Duration>,
+    pub black: Vec<Duration>,
+}
+
+impl ClockTimes {
+    pub fn for_color(&self, color: Color) -> &[Duration] {
+        match color {
+            Color::White => &self.white,
+            Color::Black => &self.black,
+        }
+    }
+
+    /// The least time the player had left after any of their moves, e.g. to spot games played in time trouble.
+    pub fn lowest(&self, color: Color) -> Option<Duration> {
+        self.for_color(color).iter().copied().min()
+    }
+}
+
+impl ParsedPgn {
+    /// Splits the clock times between the players, taking turns from whoever moves first (Black, if the `FEN` tag says so).
+    pub fn player_clock_times(&self) -> ClockTimes {
+        let black_first = self.tags.get("FEN").is_some_and(|fen| fen.split_whitespace().nth(1) == Some("b"));
+        let mut clocks = ClockTimes::default();
+        for (index, &time) in self.clock_times.iter().enumerate() {
+            if (index % 2 == 0) != black_first {
+                clocks.white.push(time);
+            } else {
+                clocks.black.push(time);
+            }
+        }
+        clocks
+    }
+}
+
+/// Parses the inside of a tag pair (everything after the opening `[`), e.g. `ECOUrl "https://www.chess.com/openings/..."]`.
+pub(crate) fn parse_tag(tag: &str) -> Option<(String, String)> {
+    read_tag(&mut tag.chars().peekable())
+}
+
+/// Reads a tag pair from just after its opening `[` through the closing `]`. Values may contain spaces, brackets and
+/// escaped quotes or backslashes, but not line breaks. Anything else left on the line of a tag that can't be read is
+/// skipped.
+fn read_tag<I: Iterator<Item = char>>(chars: &mut Peekable<I>) -> Option<(String, String)> {
+    let tag = read_tag_pair(chars);
+    if tag.is_none() {
+        chars.take_while(|&c| c != '\n').for_each(drop);
+    }
+    tag
+}
+
+/// [read_tag], except that it never goes past the end of the line.
+fn read_tag_pair<I: Iterator<Item = char>>(chars: &mut Peekable<I>) -> Option<(String, String)> {
+    skip_blanks(chars);
+    let mut name = String::new();
+    while let Some(c) = chars.next_if(|&c| c.is_alphanumeric() || "_+#=:-".contains(c)) {
+        name.push(c);
+    }
+    skip_blanks(chars);
+    chars.next_if_eq(&'"')?;
+
+    let mut value = String::new();
+    loop {
+        match chars.next_if(|&c| c != '\n')? {
+            '\\' => value.extend(chars.next_if(|&c| c != '\n')),
+            '"' => break,
+            c => value.push(c),
+        }
+    }
+    skip_blanks(chars);
+    chars.next_if_eq(&']');
+    Some((name, value)).filter(|(name, _)| !name.is_empty())
+}
+
+/// Skips spaces and tabs, and the `\r` of a CRLF line ending, but not the `\n`.
+fn skip_blanks<I: Iterator<Item = char>>(chars: &mut Peekable<I>) {
+    while chars.next_if(|&c| c.is_whitespace() && c != '\n').is_some() {}
+}
+
+/// Reads a `[%clk 0:02:59.9]` annotation out of a comment.
+fn parse_clock(comment: &str) -> Option<Duration> {
+    let start = comment.find("[%clk")? + "[%clk".len();
+    let end = start + comment[start..].find(']')?;
+    parse_clock_time(comment[start..end].trim())
+}
+
+/// Parses `h:mm:ss`, `h:mm:ss.f` or `mm:ss`.
+pub(crate) fn parse_clock_time(time: &str) -> Option<Duration> {
+    let mut seconds = 0.0;
+    for part in time.split(':') {
+        let part = part.parse::<f64>().ok().filter(|part| part.is_finite() && part.is_sign_positive())?;
+        seconds = seconds * 60.0 + part;
+    }
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+/// Turns a movetext token into a move, returning `None` for move numbers, results and NAGs.
+fn parse_move(token: &str) -> Option<San> {
+    if token.starts_with('$') || ["1-0", "0-1", "1/2-1/2", "*"].contains(&token) {
+        return None;
+    }
+    let token = token.trim_end_matches(['!', '?']);
+    // Some tools write castling with zeros
+    if token.starts_with("0-0") {
+        return Some(San(token.replace('0', "O")));
+    }
+    // Move numbers can be glued to the move, e.g. "1.e4" or "12...Nf6"
+    let token = token.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+    if token.is_empty() {
+        None
+    } else {
+        Some(San(token.to_string()))
+    }
+}
@@ -0,0 +1,85 @@
+//! Either side of a game, as a single value rather than [GameData]'s `white_*` and `black_*` fields.
+
+use crate::{Color, GameData, GameResult, Title};
+
+/// One of a game's players, borrowed from the [GameData].
+///
+/// ```rust,no_run
+/// # fn main() -> hikaru::Result<()> {
+/// for game in hikaru::GameData::download(vec!["hikaru"])? {
+///     let opponent = game.opponent();
+///     match opponent.title {
+///         Some(title) => println!("{} {} ({})", title, opponent.username, opponent.rating),
+///         None => println!("{} ({})", opponent.username, opponent.rating),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayerRef<'a> {
+    pub username: &'a str,
+    pub rating: u32,
+    pub colour: Color,
+    /// How the game ended for this player, e.g. `Resigned`.
+    pub result: &'a GameResult,
+    /// Their accuracy (0-100), if the game has been reviewed on Chess.com.
+    pub accuracy: Option<f32>,
+    /// Only known for games whose source includes titles, see [GameData::white_title].
+    pub title: Option<Title>,
+    /// The API URL of their country, where the source includes it.
+    pub country: Option<&'a str>,
+}
+
+impl<'a> PlayerRef<'a> {
+    /// The ISO 3166 code of their country, e.g. `US`, taken from the end of the country URL.
+    pub fn country_code(&self) -> Option<&'a str> {
+        self.country.and_then(|url| url.rsplit('/').next())
+    }
+}
+
+impl GameData {
+    /// The player with the white pieces.
+    pub fn white(&self) -> PlayerRef<'_> {
+        PlayerRef {
+            username: &self.white_username,
+            rating: self.white_rating,
+            colour: Color::White,
+            result: self.result_for(Color::White),
+            accuracy: self.white_accuracy,
+            title: self.white_title,
+            country: self.white_country.as_deref(),
+        }
+    }
+
+    /// The player with the black pieces.
+    pub fn black(&self) -> PlayerRef<'_> {
+        PlayerRef {
+            username: &self.black_username,
+            rating: self.black_rating,
+            colour: Color::Black,
+            result: self.result_for(Color::Black),
+            accuracy: self.black_accuracy,
+            title: self.black_title,
+            country: self.black_country.as_deref(),
+        }
+    }
+
+    /// The player the games were downloaded for.
+    pub fn player(&self) -> PlayerRef<'_> {
+        if self.is_white() { self.white() } else { self.black() }
+    }
+
+    /// The player's opponent.
+    pub fn opponent(&self) -> PlayerRef<'_> {
+        if self.is_white() { self.black() } else { self.white() }
+    }
+
+    fn is_white(&self) -> bool {
+        self.colour == "White"
+    }
+
+    fn result_for(&self, colour: Color) -> &GameResult {
+        if (colour == Color::White) == self.is_white() { &self.result } else { &self.opponent_result }
+    }
+}
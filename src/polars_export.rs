@@ -0,0 +1,99 @@
+//! Polars data frames, enabled with the `polars` feature.
+//!
+//! Each [GameData] field becomes a column of the same name, as with the `arrow` feature. `start_time` and `end_time` are
+//! datetimes (UTC, in milliseconds), and enums, time controls and titles are categoricals, spelled the way they are in
+//! JSON.
+
+use polars::prelude::{CategoricalOrdering, Column, DataFrame, DataType, NamedFrom, Series, TimeUnit};
+
+use crate::export::variant;
+use crate::{GameData, Result, Title};
+
+const CATEGORICAL: DataType = DataType::Categorical(None, CategoricalOrdering::Physical);
+
+/// Puts the games into a Polars data frame, one row per game.
+///
+/// ```rust,no_run
+/// # fn main() -> hikaru::Result<()> {
+/// let games = hikaru::GameData::download(vec!["hikaru"])?;
+/// let frame = hikaru::games_to_dataframe(&games)?;
+/// println!("{:?}", frame.shape());
+/// # Ok(())
+/// # }
+/// ```
+pub fn games_to_dataframe(games: &[GameData]) -> Result<DataFrame> {
+    let strings = |name: &str, f: fn(&GameData) -> String| -> Series {
+        Series::new(name.into(), games.iter().map(f).collect::<Vec<_>>())
+    };
+    let optional_strings = |name: &str, f: fn(&GameData) -> Option<&str>| -> Series {
+        Series::new(name.into(), games.iter().map(f).collect::<Vec<_>>())
+    };
+    let categories = |name: &str, f: fn(&GameData) -> Option<String>| -> Result<Series> {
+        Ok(Series::new(name.into(), games.iter().map(f).collect::<Vec<_>>()).cast(&CATEGORICAL)?)
+    };
+    let numbers = |name: &str, f: fn(&GameData) -> Option<u32>| -> Series {
+        Series::new(name.into(), games.iter().map(f).collect::<Vec<_>>())
+    };
+    let fractions = |name: &str, f: fn(&GameData) -> Option<f32>| -> Series {
+        Series::new(name.into(), games.iter().map(f).collect::<Vec<_>>())
+    };
+    let timestamps = |name: &str, f: fn(&GameData) -> Option<u32>| -> Result<Series> {
+        let milliseconds: Vec<Option<i64>> = games.iter().map(|game| f(game).map(|time| i64::from(time) * 1000)).collect();
+        Ok(Series::new(name.into(), milliseconds).cast(&DataType::Datetime(TimeUnit::Milliseconds, None))?)
+    };
+
+    let columns = vec![
+        strings("game_url", |game| game.game_url.clone()),
+        categories("time_control", |game| Some(game.time_control.clone()))?,
+        timestamps("start_time", |game| game.start_time)?,
+        timestamps("end_time", |game| Some(game.end_time))?,
+        Series::new("rated".into(), games.iter().map(|game| game.rated).collect::<Vec<_>>()),
+        strings("fen", |game| game.fen.clone()),
+        categories("time_class", |game| Some(variant(&game.time_class)))?,
+        categories("rules", |game| Some(variant(&game.rules)))?,
+        optional_strings("eco_game", |game| game.eco_game.as_deref()),
+        optional_strings("tournament", |game| game.tournament.as_deref()),
+        optional_strings("match", |game| game.team_match.as_deref()),
+        numbers("white_rating", |game| Some(game.white_rating)),
+        strings("white_username", |game| game.white_username.clone()),
+        numbers("black_rating", |game| Some(game.black_rating)),
+        strings("black_username", |game| game.black_username.clone()),
+        categories("white_title", |game| game.white_title.as_ref().map(Title::to_string))?,
+        categories("black_title", |game| game.black_title.as_ref().map(Title::to_string))?,
+        optional_strings("white_country", |game| game.white_country.as_deref()),
+        optional_strings("black_country", |game| game.black_country.as_deref()),
+        categories("eco_pgn", |game| Some(game.eco_pgn.clone()))?,
+        strings("eco_url", |game| game.eco_url.clone()),
+        categories("result", |game| Some(variant(&game.result)))?,
+        categories("result_win_lose", |game| Some(variant(&game.result_win_lose)))?,
+        categories("termination", |game| Some(variant(&game.termination)))?,
+        numbers("rating", |game| Some(game.rating)),
+        strings("date", |game| game.date.clone()),
+        categories("colour", |game| Some(game.colour.clone()))?,
+        fractions("win", |game| Some(game.win)),
+        strings("player_username", |game| game.player_username.clone()),
+        strings("canonical_username", |game| game.canonical_username.clone()),
+        optional_strings("pgn", |game| game.pgn.as_deref()),
+        fractions("white_accuracy", |game| game.white_accuracy),
+        fractions("black_accuracy", |game| game.black_accuracy),
+        fractions("accuracy", |game| game.accuracy),
+        strings("opponent_username", |game| game.opponent_username.clone()),
+        numbers("opponent_rating", |game| Some(game.opponent_rating)),
+        Series::new("rating_diff".into(), games.iter().map(|game| game.rating_diff).collect::<Vec<_>>()),
+        categories("opponent_result", |game| Some(variant(&game.opponent_result)))?,
+        optional_strings("uuid", |game| game.uuid.as_deref()),
+        optional_strings("initial_setup", |game| game.initial_setup.as_deref()),
+        numbers("start_position_number", |game| game.start_position_number.map(u32::from)),
+        optional_strings("tcn", |game| game.tcn.as_deref()),
+        strings("moves", |game| game.moves.join(" ")),
+        numbers("plies", |game| Some(game.plies)),
+        numbers("move_count", |game| Some(game.move_count)),
+        optional_strings("partner_game_url", |game| game.bughouse.as_ref().map(|bughouse| bughouse.partner_game_url.as_str())),
+        optional_strings("partner_username", |game| game.bughouse.as_ref().map(|bughouse| bughouse.partner_username.as_str())),
+        optional_strings("partner_opponent_username", |game| {
+            game.bughouse.as_ref().map(|bughouse| bughouse.partner_opponent_username.as_str())
+        }),
+        categories("partner_result", |game| game.bughouse.as_ref().map(|bughouse| variant(&bughouse.partner_result)))?,
+    ];
+    Ok(DataFrame::new(columns.into_iter().map(Column::from).collect())?)
+}
@@ -0,0 +1,65 @@
+//! Player profiles, mostly useful for checking that usernames exist before a long download.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Client, Result, Username};
+
+/// A player's public profile, from `/pub/player/{username}`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Profile {
+    /// The API URL of the profile.
+    #[serde(rename = "@id")]
+    pub id: String,
+    /// The profile page on the website.
+    pub url: String,
+    /// Always lowercase, whatever capitalisation the player chose.
+    pub username: String,
+    pub player_id: u64,
+    /// e.g. `GM`. Only there for titled players.
+    pub title: Option<String>,
+    /// e.g. `premium`, `basic`, or `closed` for accounts that have been shut down.
+    pub status: String,
+    pub name: Option<String>,
+    pub avatar: Option<String>,
+    pub location: Option<String>,
+    /// The API URL of the player's country.
+    pub country: String,
+    /// When the account was created, as a Unix timestamp.
+    pub joined: u64,
+    pub last_online: u64,
+    #[serde(default)]
+    pub followers: u32,
+    #[serde(default)]
+    pub is_streamer: bool,
+    #[serde(default)]
+    pub verified: bool,
+    pub league: Option<String>,
+}
+
+impl Client {
+    /// Downloads a player's profile. [Error::UserNotFound](crate::Error::UserNotFound) if there is no such player.
+    pub fn profile(&self, user: &str) -> Result<Profile> {
+        let url = self.endpoint(&format!("player/{}", Username::new(user)?));
+        self.get_json(&url).map_err(|err| err.for_user(user))
+    }
+
+    /// Looks up every user's profile before anything else, so a typo fails straight away with
+    /// [Error::UserNotFound](crate::Error::UserNotFound) rather than partway through a long download.
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// let client = hikaru::Client::new();
+    /// let users = ["hikaru", "magnuscarlsen", "firouzja2003"];
+    /// client.validate_users(users)?;
+    /// let games = client.games_many(users)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn validate_users<I, S>(&self, users: I) -> Result<Vec<Profile>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        users.into_iter().map(|user| self.profile(user.as_ref())).collect()
+    }
+}
@@ -0,0 +1,58 @@
+//! The daily puzzle, from `/pub/puzzle` and `/pub/puzzle/random`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Client, ParsedPgn, Result};
+
+/// A puzzle: a position, and the moves that solve it.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Puzzle {
+    pub title: String,
+    /// The puzzle's page on Chess.com.
+    pub url: String,
+    /// When the puzzle was (or will be) the daily puzzle, as a Unix timestamp.
+    pub publish_time: u32,
+    /// The starting position.
+    pub fen: String,
+    /// The solution, as a PGN starting from `fen`.
+    pub pgn: String,
+    /// A picture of the starting position.
+    pub image: String,
+}
+
+impl Puzzle {
+    /// Downloads today's daily puzzle, using a [Client] with the default settings.
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// let puzzle = hikaru::Puzzle::today()?;
+    /// println!("{}: {}", puzzle.title, puzzle.fen);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn today() -> Result<Puzzle> {
+        Client::new().daily_puzzle()
+    }
+
+    /// Downloads a random past daily puzzle, using a [Client] with the default settings.
+    pub fn random() -> Result<Puzzle> {
+        Client::new().random_puzzle()
+    }
+
+    /// The moves of the solution.
+    pub fn solution(&self) -> ParsedPgn {
+        ParsedPgn::parse(&self.pgn)
+    }
+}
+
+impl Client {
+    pub fn daily_puzzle(&self) -> Result<Puzzle> {
+        self.get_json(&self.endpoint("puzzle"))
+    }
+
+    /// Downloads a random past daily puzzle. Chess.com caches the answer for 15 seconds, so calling this more often
+    /// returns the same puzzle.
+    pub fn random_puzzle(&self) -> Result<Puzzle> {
+        self.get_json(&self.endpoint("puzzle/random"))
+    }
+}
@@ -0,0 +1,103 @@
+//! Python bindings, enabled with the `python` feature and built into a wheel with [maturin](https://www.maturin.rs)
+//! (see `pyproject.toml`):
+//!
+//! ```python
+//! import hikaru
+//! import pandas as pd
+//!
+//! games = hikaru.download("hikaru")                    # a list of dicts, one per game
+//! frame = pd.DataFrame(hikaru.download_columns("hikaru"))
+//! ```
+//!
+//! Games have the same fields as [GameData] serializes to. Downloads let go of the GIL, so other Python threads keep
+//! running in the meantime.
+
+use pyo3::exceptions::{PyConnectionError, PyLookupError, PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use pyo3::IntoPyObjectExt;
+use serde_json::{Map, Value};
+
+use crate::{Error, GameData};
+
+impl From<Error> for PyErr {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::UserNotFound(_) => PyLookupError::new_err(err.to_string()),
+            Error::InvalidUsername(_) => PyValueError::new_err(err.to_string()),
+            Error::Network(_) | Error::Status { .. } | Error::RateLimited => PyConnectionError::new_err(err.to_string()),
+            err => PyRuntimeError::new_err(err.to_string()),
+        }
+    }
+}
+
+/// Converts JSON into the Python equivalent: dicts, lists, strings, numbers, booleans and `None`.
+fn to_python(py: Python<'_>, value: &Value) -> PyResult<PyObject> {
+    match value {
+        Value::Null => Ok(py.None()),
+        Value::Bool(value) => value.into_py_any(py),
+        Value::Number(number) => match (number.as_i64(), number.as_f64()) {
+            (Some(integer), _) => integer.into_py_any(py),
+            (None, Some(float)) => float.into_py_any(py),
+            (None, None) => number.to_string().into_py_any(py),
+        },
+        Value::String(text) => text.into_py_any(py),
+        Value::Array(values) => {
+            let values = values.iter().map(|value| to_python(py, value)).collect::<PyResult<Vec<_>>>()?;
+            PyList::new(py, values)?.into_py_any(py)
+        }
+        Value::Object(fields) => dict(py, fields)?.into_py_any(py),
+    }
+}
+
+fn dict<'py>(py: Python<'py>, fields: &Map<String, Value>) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    for (field, value) in fields {
+        dict.set_item(field, to_python(py, value)?)?;
+    }
+    Ok(dict)
+}
+
+/// The user's games as JSON objects, oldest first.
+fn download_json(py: Python<'_>, user: &str) -> PyResult<Vec<Map<String, Value>>> {
+    let games = py.allow_threads(|| GameData::download(vec![user]))?;
+    games.iter()
+        .map(|game| match serde_json::to_value(game) {
+            Ok(Value::Object(fields)) => Ok(fields),
+            Ok(_) => unreachable!("GameData serializes to an object"),
+            Err(err) => Err(Error::from(err).into()),
+        })
+        .collect()
+}
+
+/// Downloads every game `user` has played, oldest first, as a list of dicts.
+#[pyfunction]
+fn download(py: Python<'_>, user: &str) -> PyResult<Py<PyList>> {
+    let games = download_json(py, user)?;
+    let games = games.iter().map(|fields| dict(py, fields)).collect::<PyResult<Vec<_>>>()?;
+    Ok(PyList::new(py, games)?.unbind())
+}
+
+/// Downloads every game `user` has played, oldest first, as a dict of columns, each a list with a value per game. This
+/// is the layout `pandas.DataFrame` and `polars.DataFrame` take.
+#[pyfunction]
+fn download_columns(py: Python<'_>, user: &str) -> PyResult<Py<PyDict>> {
+    let games = download_json(py, user)?;
+    let columns = PyDict::new(py);
+    if let Some(first) = games.first() {
+        for field in first.keys() {
+            let values = games.iter()
+                .map(|fields| to_python(py, fields.get(field).unwrap_or(&Value::Null)))
+                .collect::<PyResult<Vec<_>>>()?;
+            columns.set_item(field, PyList::new(py, values)?)?;
+        }
+    }
+    Ok(columns.unbind())
+}
+
+#[pymodule]
+fn hikaru(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(download, module)?)?;
+    module.add_function(wrap_pyfunction!(download_columns, module)?)?;
+    Ok(())
+}
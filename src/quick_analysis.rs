@@ -0,0 +1,137 @@
+//! Spots the crudest tactical misses with static checks alone, so it works without an engine installed.
+//!
+//! Only two things are looked for: a checkmate in one that wasn't played, and a piece that could have been taken for
+//! free (or for less than it is worth) but wasn't. It won't find anything deeper; use [GameData::analyze] for that.
+
+use serde::Serialize;
+
+use crate::board::{self, Board, Move};
+use crate::{Color, Error, GameData, ParsedPgn, Result};
+
+/// The smallest material gain, in pawns, that counts as a missed capture. Free pawns are too often poisoned to report.
+const MIN_MISSED_GAIN: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum TacticKind {
+    /// The player could have checkmated but didn't.
+    MissedMate,
+    /// The player could have won a piece (or more) by capturing but didn't.
+    MissedCapture,
+}
+
+/// A move that missed something.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MissedTactic {
+    /// Half-move number, starting at 1.
+    pub ply: usize,
+    pub color: Color,
+    pub kind: TacticKind,
+    /// The move that was played, in SAN.
+    pub played: String,
+    /// The move that should have been, in SAN.
+    pub better: String,
+    /// How much material (in pawns) the capture would have won. 0 for mates.
+    pub material: u32,
+}
+
+/// Everything [quick analysis](GameData::quick_analysis) found in one game.
+///
+/// ```rust
+/// use hikaru::{Color, ParsedPgn, TacticKind};
+///
+/// // White misses Qxf7#, then Black misses the undefended queen on h5
+/// let pgn = ParsedPgn::parse("1. e4 e5 2. Qh5 Nc6 3. Bc4 Nf6 4. d3 a6 *");
+/// let report = pgn.quick_analysis()?;
+///
+/// assert_eq!(report.missed_mates(Color::White), 1);
+/// assert_eq!(report.tactics[0].better, "Qxf7#");
+/// assert_eq!(report.missed_captures(Color::Black), 1);
+/// assert_eq!(report.tactics[1].material, 9);
+/// # Ok::<(), hikaru::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TacticalReport {
+    /// In the order they were played.
+    pub tactics: Vec<MissedTactic>,
+}
+
+impl TacticalReport {
+    fn count(&self, color: Color, kind: TacticKind) -> usize {
+        self.tactics.iter()
+            .filter(|tactic| tactic.color == color && tactic.kind == kind)
+            .count()
+    }
+
+    pub fn missed_mates(&self, color: Color) -> usize {
+        self.count(color, TacticKind::MissedMate)
+    }
+
+    pub fn missed_captures(&self, color: Color) -> usize {
+        self.count(color, TacticKind::MissedCapture)
+    }
+}
+
+fn is_mate(board: &Board, mv: &Move) -> bool {
+    let after = board.played(mv);
+    after.is_check() && after.legal_moves().is_empty()
+}
+
+/// What a capture wins if the opponent recaptures whenever they can: the captured piece, less the capturing one if it
+/// can be taken back. 0 for moves that aren't captures or lose material.
+fn capture_gain(board: &Board, mv: &Move) -> u32 {
+    let captured = match mv.capture {
+        Some(role) => role.value(),
+        None => return 0,
+    };
+    let defended = board.played(mv).is_attacked(mv.to, !board.turn());
+    if defended { captured.saturating_sub(mv.role.value()) } else { captured }
+}
+
+/// Looks at a single move for a missed mate or capture.
+fn check_move(board: &Board, played: &Move) -> Option<(TacticKind, Move, u32)> {
+    if is_mate(board, played) {
+        return None;
+    }
+    let legal = board.legal_moves();
+    if let Some(mate) = legal.iter().find(|mv| is_mate(board, mv)) {
+        return Some((TacticKind::MissedMate, *mate, 0));
+    }
+    let (best, gain) = legal.iter()
+        .map(|mv| (mv, capture_gain(board, mv)))
+        .max_by_key(|&(_, gain)| gain)?;
+    if gain >= MIN_MISSED_GAIN && capture_gain(board, played) < gain {
+        Some((TacticKind::MissedCapture, *best, gain))
+    } else {
+        None
+    }
+}
+
+impl ParsedPgn {
+    /// Replays the game looking for missed mates in one and missed captures. See [TacticalReport].
+    pub fn quick_analysis(&self) -> Result<TacticalReport> {
+        let start = board::starting_position(self)?;
+        let tactics = board::replay(&start, self)?
+            .iter()
+            .enumerate()
+            .filter_map(|(ply, (board, played))| {
+                check_move(board, played).map(|(kind, better, material)| MissedTactic {
+                    ply: ply + 1,
+                    color: board.turn(),
+                    kind,
+                    played: board.san(played),
+                    better: board.san(&better),
+                    material,
+                })
+            })
+            .collect();
+        Ok(TacticalReport { tactics })
+    }
+}
+
+impl GameData {
+    /// Checks the game for missed mates in one and missed captures, without an engine. See [TacticalReport].
+    pub fn quick_analysis(&self) -> Result<TacticalReport> {
+        let pgn = self.pgn.as_deref().ok_or(Error::MissingPgn)?;
+        ParsedPgn::parse(pgn).quick_analysis()
+    }
+}
@@ -0,0 +1,90 @@
+//! The JSON of each game as Chess.com sent it, next to the typed [GameData]. Enabled with the `raw` feature.
+
+use std::collections::HashMap;
+use std::io::BufReader;
+
+use serde_json::de::IoRead;
+use serde_json::Value;
+
+use crate::client::{Download, Downloaded};
+use crate::{ArchivedGame, Client, DownloadOptions, GameData, GameParseError, Games, MonthGames, Result};
+
+/// A game along with the JSON it was read from, for getting at fields Chess.com has added that [GameData] doesn't
+/// know about yet.
+///
+/// ```rust,no_run
+/// # fn main() -> hikaru::Result<()> {
+/// for game in hikaru::Client::new().games_raw("hikaru")? {
+///     if let Some(value) = game.raw.get("some_new_field") {
+///         println!("{}: {}", game.game.game_url, value);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct GameRaw {
+    pub game: GameData,
+    /// The game's object from the monthly archive, untouched.
+    pub raw: Value,
+}
+
+/// A month's games, each with its JSON.
+type RawMonth = Vec<(std::result::Result<ArchivedGame, GameParseError>, Value)>;
+
+impl Client {
+    /// Like [Client::games], but keeps each game's JSON too. See [GameRaw].
+    pub fn games_raw(&self, user: &str) -> Result<Vec<GameRaw>> {
+        self.games_raw_with(user, &DownloadOptions::default())
+    }
+
+    /// Like [Client::games_with], but keeps each game's JSON too. See [GameRaw].
+    pub fn games_raw_with(&self, user: &str, options: &DownloadOptions) -> Result<Vec<GameRaw>> {
+        Ok(self.download(user, options, &Raw)?.0)
+    }
+}
+
+/// Downloads [GameRaw]s. Each game is read into a [Value] once, and the [GameData] built from that.
+struct Raw;
+
+impl Download for Raw {
+    type Month = RawMonth;
+    type Game = GameRaw;
+
+    fn fetch(&self, client: &Client, url: &str) -> Result<RawMonth> {
+        client.get_month_with(url, |url, reader| {
+            let mut month = vec![];
+            Games::read(url, IoRead::new(BufReader::new(reader)), |game, json: Value| month.push((game, json)))?;
+            Ok(month)
+        })
+    }
+
+    fn convert(&self, options: &DownloadOptions, url: &str, month: RawMonth, user: &str) -> Result<Vec<GameRaw>> {
+        let mut raw: HashMap<String, Value> = HashMap::new();
+        let games: MonthGames = month.into_iter()
+            .map(|(game, json)| {
+                if let Ok(game) = &game {
+                    raw.insert(game.game_url.clone(), json);
+                }
+                game
+            })
+            .collect();
+        options.game_data(url, games, user).into_iter()
+            .map(|game| {
+                let game = game?;
+                let raw = raw.remove(&game.game_url).unwrap_or_default();
+                Ok(GameRaw { game, raw })
+            })
+            .collect()
+    }
+}
+
+impl Downloaded for GameRaw {
+    fn end_time(&self) -> u32 {
+        self.game.end_time
+    }
+
+    fn game_url(&self) -> &str {
+        &self.game.game_url
+    }
+}
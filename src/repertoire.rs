@@ -0,0 +1,141 @@
+//! Opening repertoires: a tree of the moves a player has played (and met) from the starting position, with how each
+//! line has gone for them.
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use serde_json::{json, Value};
+
+use crate::board::STARTING_FEN;
+use crate::{Color, GameData};
+
+/// One position in a [Repertoire], reached by a sequence of moves from the root.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RepertoireNode {
+    /// Games that reached this position.
+    pub games: u32,
+    /// Points the player scored in those games, counting draws as half a point.
+    pub points: f64,
+    opponent_rating_total: u64,
+    /// The moves played next, keyed by SAN.
+    pub children: BTreeMap<String, RepertoireNode>,
+}
+
+impl RepertoireNode {
+    fn add(&mut self, game: &GameData) {
+        self.games += 1;
+        self.points += f64::from(game.win);
+        self.opponent_rating_total += u64::from(game.opponent_rating);
+    }
+
+    /// Points scored as a share of the games, from 0 to 1. 0 without any games.
+    pub fn score(&self) -> f64 {
+        if self.games == 0 { 0.0 } else { self.points / f64::from(self.games) }
+    }
+
+    /// The opponents' average rating in the games that reached this position. 0 without any games.
+    pub fn average_opponent_rating(&self) -> f64 {
+        if self.games == 0 { 0.0 } else { self.opponent_rating_total as f64 / f64::from(self.games) }
+    }
+
+    /// The moves played next, most played first.
+    pub fn moves(&self) -> Vec<(&str, &RepertoireNode)> {
+        let mut moves: Vec<(&str, &RepertoireNode)> = self.children.iter().map(|(san, node)| (san.as_str(), node)).collect();
+        moves.sort_by_key(|(_, node)| Reverse(node.games));
+        moves
+    }
+
+    fn prune(&mut self, min_games: u32) {
+        self.children.retain(|_, child| child.games >= min_games);
+        for child in self.children.values_mut() {
+            child.prune(min_games);
+        }
+    }
+
+    fn to_json(&self, san: Option<&str>) -> Value {
+        json!({
+            "move": san,
+            "games": self.games,
+            "score": self.score(),
+            "average_opponent_rating": self.average_opponent_rating(),
+            "children": self.moves().into_iter().map(|(san, child)| child.to_json(Some(san))).collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// The opening tree of one player's games with one colour.
+///
+/// ```rust,no_run
+/// use hikaru::{Color, GameData, Repertoire};
+///
+/// # fn main() -> hikaru::Result<()> {
+/// let games = GameData::download(vec!["hikaru"])?;
+/// let mut repertoire = Repertoire::build(&games, Color::White, 12);
+/// repertoire.prune(20);
+/// for (san, node) in repertoire.root.moves() {
+///     println!("1. {}: {} games, {:.0}%", san, node.games, node.score() * 100.0);
+/// }
+/// std::fs::write("white.dot", repertoire.to_dot())?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repertoire {
+    pub color: Color,
+    /// The starting position, covering every game in the tree.
+    pub root: RepertoireNode,
+}
+
+impl Repertoire {
+    /// Builds the tree from the games where the player had `color`, following each game for at most `max_plies`
+    /// half-moves. Games from a custom starting position (e.g. Chess960) are left out.
+    pub fn build(games: &[GameData], color: Color, max_plies: usize) -> Self {
+        let colour = if color == Color::White { "White" } else { "Black" };
+        let mut root = RepertoireNode::default();
+        let from_start = |game: &&GameData| game.initial_setup.as_deref().is_none_or(|fen| fen == STARTING_FEN);
+        for game in games.iter().filter(|game| game.colour == colour).filter(from_start) {
+            let mut node = &mut root;
+            node.add(game);
+            for san in game.moves.iter().take(max_plies) {
+                node = node.children.entry(san.clone()).or_default();
+                node.add(game);
+            }
+        }
+        Repertoire { color, root }
+    }
+
+    /// Removes every line played in fewer than `min_games` games, keeping the tree to the lines that matter.
+    pub fn prune(&mut self, min_games: u32) {
+        self.root.prune(min_games);
+    }
+
+    /// The tree as nested JSON: each node has its `move`, `games`, `score`, `average_opponent_rating` and `children`.
+    pub fn to_json(&self) -> Value {
+        self.root.to_json(None)
+    }
+
+    /// The tree as a Graphviz digraph, for rendering with e.g. `dot -Tsvg`. Prune it first, whole repertoires are big.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph repertoire {\n    node [shape=box];\n");
+        let title = if self.color == Color::White { "White" } else { "Black" };
+        dot.push_str(&format!("    n0 [label=\"{}\\n{}\"];\n", title, node_stats(&self.root)));
+        let mut next_id = 1;
+        write_dot(&self.root, 0, &mut next_id, &mut dot);
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn node_stats(node: &RepertoireNode) -> String {
+    format!("{} games, {:.0}%", node.games, node.score() * 100.0)
+}
+
+fn write_dot(node: &RepertoireNode, id: usize, next_id: &mut usize, dot: &mut String) {
+    for (san, child) in node.moves() {
+        let child_id = *next_id;
+        *next_id += 1;
+        dot.push_str(&format!("    n{} [label=\"{}\\n{}\"];\n", child_id, san.replace('"', "\\\""), node_stats(child)));
+        dot.push_str(&format!("    n{} -> n{};\n", id, child_id));
+        write_dot(child, child_id, next_id, dot);
+    }
+}
@@ -0,0 +1,124 @@
+//! How results hang together over a playing session: losing streaks, the time of day, and games played straight after
+//! a loss ("tilt").
+
+use std::convert::TryFrom;
+use std::time::Duration;
+
+use crate::date;
+use crate::{GameData, GameResultWinLose, Record};
+
+/// A run of losses in a row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LosingStreak {
+    /// When the first loss ended, as a Unix timestamp.
+    pub start: u32,
+    /// When the last loss ended.
+    pub end: u32,
+    pub length: u32,
+}
+
+/// The games a player went on to play after a loss, each started soon after the previous one ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TiltSession {
+    /// When the loss that started it ended, as a Unix timestamp.
+    pub after_loss: u32,
+    /// When the session's last game ended.
+    pub end: u32,
+    /// The results of the games after the loss.
+    pub record: Record,
+}
+
+/// Patterns in when and how a player's games went, from [SessionReport::from_games].
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use hikaru::{GameData, SessionReport};
+///
+/// # fn main() -> hikaru::Result<()> {
+/// let games = GameData::download(vec!["hikaru"])?;
+/// let report = SessionReport::from_games(&games, Duration::from_secs(5 * 60));
+/// println!("{:.1}% straight after a loss, {:.1}% otherwise",
+///     report.after_loss.score() * 100.0, report.otherwise.score() * 100.0);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionReport {
+    /// Every run of two or more losses, oldest first.
+    pub losing_streaks: Vec<LosingStreak>,
+    /// Results by the hour (UTC, 0 to 23) the game started.
+    pub by_hour: [Record; 24],
+    /// Games started within the tilt window of a loss ending.
+    pub after_loss: Record,
+    /// Every other game.
+    pub otherwise: Record,
+    /// Each loss that was followed by more games within the tilt window, oldest first.
+    pub tilt_sessions: Vec<TiltSession>,
+}
+
+/// When the game started. Only daily games have a start time in the archive; live games have it in the PGN.
+fn start_time(game: &GameData) -> u32 {
+    game.start_time
+        .or_else(|| {
+            let pgn = game.parsed_pgn()?;
+            let start = date::timestamp_from_pgn(pgn.tags.get("UTCDate")?, pgn.tags.get("UTCTime")?)?;
+            u32::try_from(start).ok()
+        })
+        .unwrap_or(game.end_time)
+}
+
+impl SessionReport {
+    /// Analyses the games in the order they were played. A game counts as played "after a loss" if it started within
+    /// `tilt_window` of a loss ending, and a tilt session goes on for as long as each game starts within `tilt_window`
+    /// of the one before.
+    ///
+    /// The games should all be from the same player's perspective.
+    pub fn from_games(games: &[GameData], tilt_window: Duration) -> SessionReport {
+        let mut sorted: Vec<&GameData> = games.iter().collect();
+        sorted.sort_by_key(|game| game.end_time);
+        let window = tilt_window.as_secs();
+
+        let mut report = SessionReport::default();
+        let mut streak: Option<LosingStreak> = None;
+        let mut session: Option<TiltSession> = None;
+        let mut previous: Option<&GameData> = None;
+        for game in sorted {
+            let result = game.result_win_lose;
+            let start = start_time(game);
+            let hour = (start / 3600 % 24) as usize;
+            report.by_hour[hour].add(result);
+
+            // Close to the end of the previous game, whatever its result
+            let soon_after = previous.is_some_and(|previous| u64::from(start.saturating_sub(previous.end_time)) <= window);
+            let after_loss = soon_after && previous.is_some_and(|previous| previous.result_win_lose == GameResultWinLose::Loss);
+            if after_loss { report.after_loss.add(result) } else { report.otherwise.add(result) }
+
+            match &mut session {
+                Some(session) if soon_after => {
+                    session.record.add(result);
+                    session.end = game.end_time;
+                }
+                _ => {
+                    report.tilt_sessions.extend(session.take());
+                    if after_loss {
+                        let mut record = Record::default();
+                        record.add(result);
+                        session = previous.map(|loss| TiltSession { after_loss: loss.end_time, end: game.end_time, record });
+                    }
+                }
+            }
+
+            if result == GameResultWinLose::Loss {
+                let streak = streak.get_or_insert(LosingStreak { start: game.end_time, end: game.end_time, length: 0 });
+                streak.end = game.end_time;
+                streak.length += 1;
+            } else if let Some(ended) = streak.take().filter(|streak| streak.length >= 2) {
+                report.losing_streaks.push(ended);
+            }
+            previous = Some(game);
+        }
+        report.tilt_sessions.extend(session);
+        report.losing_streaks.extend(streak.filter(|streak| streak.length >= 2));
+        report
+    }
+}
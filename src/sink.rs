@@ -0,0 +1,121 @@
+//! Somewhere to stream games to as they are downloaded, so they never have to be held in memory all at once.
+
+use std::io::{BufWriter, Write};
+
+use crate::{Client, DownloadOptions, GameData, Result};
+
+/// A destination for downloaded games, written one at a time.
+///
+/// Hikaru provides sinks for a `Vec`, [NdjsonSink], and (with the `csv` feature) [CsvSink](crate::CsvSink); implement it
+/// to send games anywhere else, e.g. a database:
+///
+/// ```rust,no_run
+/// use hikaru::{Client, DownloadOptions, GameData, GameSink};
+///
+/// struct Counter(usize);
+///
+/// impl GameSink for Counter {
+///     fn write(&mut self, _game: &GameData) -> hikaru::Result<()> {
+///         self.0 += 1;
+///         Ok(())
+///     }
+/// }
+///
+/// # fn main() -> hikaru::Result<()> {
+/// let mut counter = Counter(0);
+/// Client::new().games_into("hikaru", DownloadOptions::new(), &mut counter)?;
+/// println!("{} games", counter.0);
+/// # Ok(())
+/// # }
+/// ```
+pub trait GameSink {
+    fn write(&mut self, game: &GameData) -> Result<()>;
+
+    /// Called once every game has been written, e.g. to flush buffered output. Does nothing by default.
+    fn finish(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl GameSink for Vec<GameData> {
+    fn write(&mut self, game: &GameData) -> Result<()> {
+        self.push(game.clone());
+        Ok(())
+    }
+}
+
+impl<S: GameSink + ?Sized> GameSink for &mut S {
+    fn write(&mut self, game: &GameData) -> Result<()> {
+        (**self).write(game)
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        (**self).finish()
+    }
+}
+
+impl<S: GameSink + ?Sized> GameSink for Box<S> {
+    fn write(&mut self, game: &GameData) -> Result<()> {
+        (**self).write(game)
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        (**self).finish()
+    }
+}
+
+/// Writes each game as a line of JSON, like [GameData::write_ndjson].
+///
+/// ```rust,no_run
+/// use std::fs::File;
+/// use hikaru::{GameData, NdjsonSink};
+///
+/// # fn main() -> hikaru::Result<()> {
+/// let mut sink = NdjsonSink::new(File::create("hikaru.ndjson")?);
+/// GameData::download_into("hikaru", &mut sink)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct NdjsonSink<W: Write> {
+    writer: BufWriter<W>,
+}
+
+impl<W: Write> NdjsonSink<W> {
+    pub fn new(writer: W) -> Self {
+        NdjsonSink { writer: BufWriter::new(writer) }
+    }
+}
+
+impl<W: Write> GameSink for NdjsonSink<W> {
+    fn write(&mut self, game: &GameData) -> Result<()> {
+        writeln!(self.writer, "{}", serde_json::to_string(game)?)?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+impl Client {
+    /// Streams a single user's games into `sink`, one monthly archive at a time, then [finishes](GameSink::finish) it.
+    /// Stops at the first error. Returns how many games were written.
+    pub fn games_into<S: GameSink>(&self, user: &str, options: DownloadOptions, mut sink: S) -> Result<usize> {
+        let mut count = 0;
+        for game in self.games_iter_with(user, options)? {
+            sink.write(&game?)?;
+            count += 1;
+        }
+        sink.finish()?;
+        Ok(count)
+    }
+}
+
+impl GameData {
+    /// Streams a single user's games into `sink`, using a [Client] with the default settings. See [Client::games_into].
+    pub fn download_into<S: GameSink>(user: &str, sink: S) -> Result<usize> {
+        Client::new().games_into(user, DownloadOptions::new(), sink)
+    }
+}
@@ -0,0 +1,142 @@
+//! A local SQLite copy of downloaded games, enabled with the `sqlite` feature.
+
+use std::path::Path;
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::{GameData, GameSink, Result};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS games (
+        game_url TEXT NOT NULL,
+        player TEXT NOT NULL,
+        end_time INTEGER NOT NULL,
+        opponent TEXT NOT NULL,
+        eco TEXT,
+        opening TEXT,
+        data TEXT NOT NULL,
+        PRIMARY KEY (game_url, player)
+    );
+    CREATE INDEX IF NOT EXISTS games_end_time ON games (end_time);
+    CREATE INDEX IF NOT EXISTS games_opponent ON games (opponent);
+";
+
+/// Games kept in a SQLite database, one row per game and player, so downloads can be synced incrementally and queried
+/// offline.
+///
+/// Usernames are stored lowercased, so queries don't depend on how a name was capitalised.
+///
+/// ```rust,no_run
+/// use hikaru::{GameData, GameStore};
+///
+/// # fn main() -> hikaru::Result<()> {
+/// let mut store = GameStore::open("games.db")?;
+///
+/// // Only fetch what isn't stored yet, from a second early for games that ended with the newest one
+/// let games = match store.last_end_time("hikaru")? {
+///     Some(end_time) => GameData::download_since("hikaru", end_time.saturating_sub(1))?,
+///     None => GameData::download(vec!["hikaru"])?,
+/// };
+/// store.upsert(&games)?;
+///
+/// let najdorfs = store.by_opening("Sicilian Defense: Najdorf")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct GameStore {
+    connection: Connection,
+}
+
+impl GameStore {
+    /// Opens the database at `path`, creating it (and its table) if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<GameStore> {
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    /// A database that only lives in memory, gone once the store is dropped.
+    pub fn open_in_memory() -> Result<GameStore> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn from_connection(connection: Connection) -> Result<GameStore> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(GameStore { connection })
+    }
+
+    /// Adds the games, replacing any already stored for the same player. Returns how many were written.
+    pub fn upsert<'a>(&mut self, games: impl IntoIterator<Item = &'a GameData>) -> Result<usize> {
+        let transaction = self.connection.transaction()?;
+        let mut count = 0;
+        {
+            let mut statement = transaction.prepare(
+                "INSERT OR REPLACE INTO games (game_url, player, end_time, opponent, eco, opening, data)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?;
+            for game in games {
+                statement.execute(params![
+                    game.game_url,
+                    game.player_username.to_lowercase(),
+                    game.end_time,
+                    game.opponent_username.to_lowercase(),
+                    game.eco_code(),
+                    game.opening_name(),
+                    serde_json::to_string(game)?,
+                ])?;
+                count += 1;
+            }
+        }
+        transaction.commit()?;
+        Ok(count)
+    }
+
+    /// When the player's most recent stored game ended, for [GameData::download_since]. `None` if none are stored.
+    pub fn last_end_time(&self, player: &str) -> Result<Option<u32>> {
+        let end_time = self.connection
+            .query_row("SELECT MAX(end_time) FROM games WHERE player = ?1", [player.to_lowercase()], |row| row.get(0))
+            .optional()?;
+        Ok(end_time.flatten())
+    }
+
+    /// Every stored game, oldest first.
+    pub fn all(&self) -> Result<Vec<GameData>> {
+        self.query("SELECT data FROM games ORDER BY end_time", [])
+    }
+
+    /// Games against `opponent`, oldest first.
+    pub fn by_opponent(&self, opponent: &str) -> Result<Vec<GameData>> {
+        self.query("SELECT data FROM games WHERE opponent = ?1 ORDER BY end_time", [opponent.to_lowercase()])
+    }
+
+    /// Games that ended from `start` up to (but not including) `end`, both Unix timestamps, oldest first.
+    pub fn by_date_range(&self, start: u32, end: u32) -> Result<Vec<GameData>> {
+        self.query("SELECT data FROM games WHERE end_time >= ?1 AND end_time < ?2 ORDER BY end_time", [start, end])
+    }
+
+    /// Games whose ECO code is `opening` (e.g. `B90`), or whose opening name starts with it (e.g. `Sicilian Defense`),
+    /// oldest first. See [GameData::opening_name].
+    pub fn by_opening(&self, opening: &str) -> Result<Vec<GameData>> {
+        self.query(
+            "SELECT data FROM games WHERE eco = ?1 OR opening LIKE ?2 ESCAPE '\\' ORDER BY end_time",
+            [opening.to_uppercase(), format!("{}%", opening.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"))],
+        )
+    }
+
+    fn query(&self, sql: &str, params: impl rusqlite::Params) -> Result<Vec<GameData>> {
+        let mut statement = self.connection.prepare(sql)?;
+        let rows = statement.query_map(params, |row| row.get::<_, String>(0))?;
+        let mut games = vec![];
+        for data in rows {
+            games.push(serde_json::from_str(&data?)?);
+        }
+        Ok(games)
+    }
+}
+
+/// Upserts each game on its own. For many games at once, [GameStore::upsert] is faster, since it writes them in a single transaction.
+impl GameSink for GameStore {
+    fn write(&mut self, game: &GameData) -> Result<()> {
+        self.upsert([game])?;
+        Ok(())
+    }
+}
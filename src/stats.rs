@@ -0,0 +1,418 @@
+//! Summary statistics over a player's games: results by colour, time class and opening, ratings, and streaks.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::date;
+use crate::{Client, DownloadOptions, GameData, GameFilter, GameResultWinLose, Result, Rules, TimeClass};
+
+/// Wins, draws and losses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+impl Record {
+    pub fn add(&mut self, result: GameResultWinLose) {
+        match result {
+            GameResultWinLose::Win => self.wins += 1,
+            GameResultWinLose::Draw => self.draws += 1,
+            GameResultWinLose::Loss => self.losses += 1,
+        }
+    }
+
+    pub fn games(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+
+    /// Points scored as a share of the games played, from 0 to 1, counting draws as half a point. 0 without any games.
+    pub fn score(&self) -> f64 {
+        match self.games() {
+            0 => 0.0,
+            games => (f64::from(self.wins) + f64::from(self.draws) / 2.0) / f64::from(games),
+        }
+    }
+}
+
+/// How a player's rating moved over a set of games.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RatingRange {
+    /// The rating after the first game.
+    pub first: u32,
+    /// The rating after the most recent game.
+    pub last: u32,
+    pub lowest: u32,
+    pub highest: u32,
+}
+
+impl RatingRange {
+    fn new(rating: u32) -> Self {
+        RatingRange { first: rating, last: rating, lowest: rating, highest: rating }
+    }
+
+    fn add(&mut self, rating: u32) {
+        self.last = rating;
+        self.lowest = self.lowest.min(rating);
+        self.highest = self.highest.max(rating);
+    }
+
+    /// How much the rating went up (or down) from the first game to the last.
+    pub fn change(&self) -> i64 {
+        i64::from(self.last) - i64::from(self.first)
+    }
+}
+
+/// Statistics for one player's games, as seen from their side of the board.
+///
+/// ```rust,no_run
+/// use hikaru::{GameData, Summary, TimeClass};
+///
+/// # fn main() -> hikaru::Result<()> {
+/// let games = GameData::download(vec!["hikaru"])?;
+/// let summary = Summary::from_games(&games);
+///
+/// println!("{:.1}% overall, {:.1}% with White", summary.overall.score() * 100.0, summary.white.score() * 100.0);
+/// if let Some(blitz) = summary.ratings.get(&TimeClass::Blitz) {
+///     println!("Blitz peak: {}", blitz.highest);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Summary {
+    pub overall: Record,
+    pub white: Record,
+    pub black: Record,
+    pub by_time_class: HashMap<TimeClass, Record>,
+    /// Keyed by opening family (e.g. `Sicilian Defense`). Games whose opening couldn't be named are left out.
+    pub by_opening: HashMap<String, Record>,
+    /// Ratings move independently in each time class, so they are tracked separately. Only rated games of standard chess count.
+    pub ratings: HashMap<TimeClass, RatingRange>,
+    pub longest_win_streak: u32,
+    pub longest_loss_streak: u32,
+    /// The run of identical results the most recent games end with, e.g. `(Win, 3)` after three wins in a row.
+    pub current_streak: Option<(GameResultWinLose, u32)>,
+    /// The opponents' average rating, 0 without any games.
+    pub average_opponent_rating: f64,
+}
+
+impl Summary {
+    /// Summarises the games, in whatever order they are given; streaks and ratings go by when each game ended.
+    ///
+    /// The games should all be from the same player's perspective, e.g. the download of a single user.
+    pub fn from_games(games: &[GameData]) -> Summary {
+        let mut sorted: Vec<&GameData> = games.iter().collect();
+        sorted.sort_by_key(|game| game.end_time);
+
+        let mut summary = Summary::default();
+        let mut opponent_ratings = 0u64;
+        let mut win_streak = 0;
+        let mut loss_streak = 0;
+        for game in sorted {
+            let result = game.result_win_lose;
+            summary.overall.add(result);
+            let by_colour = if game.colour == "White" { &mut summary.white } else { &mut summary.black };
+            by_colour.add(result);
+            summary.by_time_class.entry(game.time_class.clone()).or_default().add(result);
+            if let Some(family) = game.opening_family() {
+                summary.by_opening.entry(family).or_default().add(result);
+            }
+            if game.rated && game.rules == Rules::Chess {
+                summary.ratings.entry(game.time_class.clone())
+                    .and_modify(|range| range.add(game.rating))
+                    .or_insert_with(|| RatingRange::new(game.rating));
+            }
+            opponent_ratings += u64::from(game.opponent_rating);
+
+            win_streak = if result == GameResultWinLose::Win { win_streak + 1 } else { 0 };
+            loss_streak = if result == GameResultWinLose::Loss { loss_streak + 1 } else { 0 };
+            summary.longest_win_streak = summary.longest_win_streak.max(win_streak);
+            summary.longest_loss_streak = summary.longest_loss_streak.max(loss_streak);
+            summary.current_streak = match summary.current_streak {
+                Some((streak, length)) if streak == result => Some((streak, length + 1)),
+                _ => Some((result, 1)),
+            };
+        }
+        if !games.is_empty() {
+            summary.average_opponent_rating = opponent_ratings as f64 / games.len() as f64;
+        }
+        summary
+    }
+}
+
+/// One month of a player's games in one time class, e.g. for a row of a profile dashboard.
+///
+/// ```rust,no_run
+/// use hikaru::{GameData, MonthlySummary};
+///
+/// # fn main() -> hikaru::Result<()> {
+/// let games = GameData::download(vec!["hikaru"])?;
+/// for month in MonthlySummary::from_games(&games) {
+///     println!("{}-{:02} {}: {} games, {:.0}%", month.year, month.month, month.time_class, month.games, month.score * 100.0);
+/// }
+/// println!("{}", serde_json::to_string(&MonthlySummary::from_games(&games))?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonthlySummary {
+    pub year: u16,
+    pub month: u8,
+    pub time_class: TimeClass,
+    pub games: u32,
+    pub record: Record,
+    /// Points scored as a share of the games played, as in [Record::score].
+    pub score: f64,
+    /// Only rated games of standard chess count, so this is `None` for a month of casual games or variants.
+    pub ratings: Option<RatingRange>,
+    /// The win against the highest-rated opponent.
+    pub best_win: Option<BestWin>,
+}
+
+/// A win worth showing off, see [MonthlySummary::best_win].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BestWin {
+    pub game_url: String,
+    pub end_time: u32,
+    pub opponent_username: String,
+    pub opponent_rating: u32,
+}
+
+impl MonthlySummary {
+    /// Groups the games by the month they ended in (UTC) and their time class, oldest month first. The games should
+    /// all be from the same player's perspective.
+    pub fn from_games(games: &[GameData]) -> Vec<MonthlySummary> {
+        let mut sorted: Vec<&GameData> = games.iter().collect();
+        sorted.sort_by_key(|game| game.end_time);
+
+        let mut months: BTreeMap<(u16, u8, TimeClass), MonthlySummary> = BTreeMap::new();
+        for game in sorted {
+            let (year, month) = date::year_month(game.end_time.into());
+            let summary = months.entry((year, month, game.time_class.clone())).or_insert_with(|| MonthlySummary {
+                year,
+                month,
+                time_class: game.time_class.clone(),
+                games: 0,
+                record: Record::default(),
+                score: 0.0,
+                ratings: None,
+                best_win: None,
+            });
+            summary.games += 1;
+            summary.record.add(game.result_win_lose);
+            if game.rated && game.rules == Rules::Chess {
+                match &mut summary.ratings {
+                    Some(range) => range.add(game.rating),
+                    None => summary.ratings = Some(RatingRange::new(game.rating)),
+                }
+            }
+            let is_best = summary.best_win.as_ref().is_none_or(|best| game.opponent_rating > best.opponent_rating);
+            if game.result_win_lose == GameResultWinLose::Win && is_best {
+                summary.best_win = Some(BestWin {
+                    game_url: game.game_url.clone(),
+                    end_time: game.end_time,
+                    opponent_username: game.opponent_username.clone(),
+                    opponent_rating: game.opponent_rating,
+                });
+            }
+        }
+        months.into_values()
+            .map(|mut summary| {
+                summary.score = summary.record.score();
+                summary
+            })
+            .collect()
+    }
+}
+
+/// A player's rating after a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RatingPoint {
+    /// When the game ended, as a Unix timestamp.
+    pub timestamp: u32,
+    pub rating: u32,
+}
+
+/// How finely to [resample](RatingHistory::resample) a rating history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Granularity {
+    /// Calendar days, UTC.
+    Day,
+    /// Weeks starting on Monday, UTC.
+    Week,
+}
+
+impl Granularity {
+    /// The start of the period `timestamp` falls in.
+    pub(crate) fn period_start(&self, timestamp: u32) -> u32 {
+        const DAY: u32 = 86_400;
+        match self {
+            Granularity::Day => timestamp - timestamp % DAY,
+            // 1970-01-01 was a Thursday, so weeks start three days later than multiples of 7 days from the epoch
+            Granularity::Week => {
+                let days = timestamp / DAY + 3;
+                (days - days % 7).saturating_sub(3) * DAY
+            }
+        }
+    }
+}
+
+/// A player's rating in one time class, game by game, oldest first.
+///
+/// Only rated games of standard chess are included, since variants are rated separately.
+///
+/// ```rust,no_run
+/// use hikaru::{Granularity, RatingHistory, TimeClass};
+///
+/// # fn main() -> hikaru::Result<()> {
+/// let games = hikaru::GameData::download(vec!["hikaru"])?;
+/// let blitz = RatingHistory::from_games(&games, TimeClass::Blitz);
+/// println!("{:?} now, {:?} at best", blitz.current(), blitz.max());
+/// for point in blitz.resample(Granularity::Week) {
+///     println!("{},{}", point.timestamp, point.rating);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RatingHistory {
+    pub points: Vec<RatingPoint>,
+}
+
+impl RatingHistory {
+    pub fn from_games(games: &[GameData], time_class: TimeClass) -> RatingHistory {
+        let mut points: Vec<RatingPoint> = games.iter()
+            .filter(|game| game.rated && game.rules == Rules::Chess && game.time_class == time_class)
+            .map(|game| RatingPoint { timestamp: game.end_time, rating: game.rating })
+            .collect();
+        points.sort_by_key(|point| point.timestamp);
+        RatingHistory { points }
+    }
+
+    pub fn min(&self) -> Option<RatingPoint> {
+        self.points.iter().copied().min_by_key(|point| point.rating)
+    }
+
+    pub fn max(&self) -> Option<RatingPoint> {
+        self.points.iter().copied().max_by_key(|point| point.rating)
+    }
+
+    /// The rating after the most recent game.
+    pub fn current(&self) -> Option<RatingPoint> {
+        self.points.last().copied()
+    }
+
+    /// One point per day or week that had games, holding the rating after that period's last game. Each point's
+    /// timestamp is the start of its period.
+    pub fn resample(&self, granularity: Granularity) -> Vec<RatingPoint> {
+        let mut resampled: Vec<RatingPoint> = vec![];
+        for point in &self.points {
+            let timestamp = granularity.period_start(point.timestamp);
+            match resampled.last_mut() {
+                Some(last) if last.timestamp == timestamp => last.rating = point.rating,
+                _ => resampled.push(RatingPoint { timestamp, rating: point.rating }),
+            }
+        }
+        resampled
+    }
+}
+
+/// The games played against `opponent` (matched case-insensitively), in the order given.
+pub fn head_to_head<'a>(games: &'a [GameData], opponent_name: &str) -> Vec<&'a GameData> {
+    games.iter()
+        .filter(|game| game.opponent_username.eq_ignore_ascii_case(opponent_name))
+        .collect()
+}
+
+/// How a player has done against one particular opponent.
+///
+/// ```rust,no_run
+/// use hikaru::{GameData, H2HSummary};
+///
+/// # fn main() -> hikaru::Result<()> {
+/// let games = GameData::download_vs("hikaru", "magnuscarlsen")?;
+/// let summary = H2HSummary::from_games(&games, "magnuscarlsen");
+/// println!("{}/{}/{}, {:+.0} rating points on average", summary.record.wins, summary.record.draws,
+///     summary.record.losses, summary.average_rating_edge);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct H2HSummary {
+    pub opponent: String,
+    pub record: Record,
+    pub white: Record,
+    pub black: Record,
+    /// How much higher the player was rated than the opponent on average, negative if lower. 0 without any games.
+    pub average_rating_edge: f64,
+    /// Results by opening family, as in [Summary::by_opening].
+    pub by_opening: HashMap<String, Record>,
+}
+
+impl H2HSummary {
+    /// Summarises the games against `opponent`; any other games are ignored.
+    pub fn from_games(games: &[GameData], opponent_name: &str) -> H2HSummary {
+        let games = head_to_head(games, opponent_name);
+        let mut summary = H2HSummary { opponent: opponent_name.into(), ..H2HSummary::default() };
+        let mut rating_edge = 0i64;
+        for game in &games {
+            let result = game.result_win_lose;
+            summary.record.add(result);
+            let by_colour = if game.colour == "White" { &mut summary.white } else { &mut summary.black };
+            by_colour.add(result);
+            if let Some(family) = game.opening_family() {
+                summary.by_opening.entry(family).or_default().add(result);
+            }
+            rating_edge += i64::from(game.rating_diff);
+        }
+        if !games.is_empty() {
+            summary.average_rating_edge = rating_edge as f64 / games.len() as f64;
+        }
+        summary
+    }
+}
+
+impl GameData {
+    /// Downloads the user's games against one opponent, using a [Client] with the default settings.
+    pub fn download_vs (user: &str, opponent_name: &str) -> Result<Vec<GameData>> {
+        let games = Client::new().games(user)?;
+        Ok(games.into_iter()
+            .filter(|game| game.opponent_username.eq_ignore_ascii_case(opponent_name))
+            .collect())
+    }
+
+    /// Downloads the user's history once and splits out the games against each of `opponents`, keyed by the names as
+    /// given. Every opponent gets an entry, empty if they never played the user. Other games are dropped as they are read.
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// let rivals = hikaru::GameData::download_common("hikaru", &["magnuscarlsen", "firouzja2003"])?;
+    /// for (rival, games) in &rivals {
+    ///     println!("{}: {} games", rival, games.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn download_common<S: AsRef<str>> (user: &str, opponents: &[S]) -> Result<HashMap<String, Vec<GameData>>> {
+        // An empty filter would let every game through
+        if opponents.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let filter = opponents.iter().fold(GameFilter::new(), |filter, opponent| filter.opponent(opponent.as_ref()));
+        let games = Client::new().games_with(user, &DownloadOptions::new().filter(filter))?;
+
+        let mut by_opponent: HashMap<String, Vec<GameData>> = opponents.iter()
+            .map(|opponent| (opponent.as_ref().to_string(), vec![]))
+            .collect();
+        for game in games {
+            let opponent = opponents.iter().find(|opponent| game.opponent_username.eq_ignore_ascii_case(opponent.as_ref()));
+            if let Some(games) = opponent.and_then(|opponent| by_opponent.get_mut(opponent.as_ref())) {
+                games.push(game);
+            }
+        }
+        Ok(by_opponent)
+    }
+}
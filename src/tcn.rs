@@ -0,0 +1,102 @@
+//! Chess.com's TCN move encoding, where every move is two characters: one for the square it starts from, one for where
+//! it goes. Promotions are folded into the second character; crazyhouse drops into the first.
+
+use crate::board::{square_name, Board, Role, Square};
+use crate::{Error, GameData, Result};
+
+const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789!?{~}(^)[_]@#$,./&-*++=";
+
+/// Promotion pieces, in the order TCN numbers them.
+const PROMOTIONS: [Role; 4] = [Role::Queen, Role::Knight, Role::Rook, Role::Bishop];
+
+/// A move as TCN encodes it, before looking at the board.
+struct TcnMove {
+    from: Square,
+    to: Square,
+    promotion: Option<Role>,
+}
+
+impl TcnMove {
+    fn uci(&self) -> String {
+        let mut uci = format!("{}{}", square_name(self.from), square_name(self.to));
+        uci.extend(self.promotion.map(|role| role.char().to_ascii_lowercase()));
+        uci
+    }
+}
+
+fn index(c: u8) -> Option<usize> {
+    ALPHABET.iter().position(|&letter| letter == c)
+}
+
+fn decode(tcn: &str) -> Result<Vec<TcnMove>> {
+    let invalid = || Error::InvalidTcn(tcn.into());
+    let bytes = tcn.as_bytes();
+    if !bytes.len().is_multiple_of(2) {
+        return Err(invalid());
+    }
+    bytes.chunks(2)
+        .map(|pair| {
+            let from = index(pair[0]).ok_or_else(invalid)?;
+            let to = index(pair[1]).ok_or_else(invalid)?;
+            // Anything past the 64 squares is a drop, which only crazyhouse and bughouse have
+            if from > 63 {
+                return Err(invalid());
+            }
+            if to < 64 {
+                return Ok(TcnMove { from: from as Square, to: to as Square, promotion: None });
+            }
+            // Promotions give the piece and which way the pawn went (capturing left, straight ahead, or capturing right)
+            let promotion = *PROMOTIONS.get((to - 64) / 3).ok_or_else(invalid)?;
+            let forward: isize = if from < 16 { -8 } else { 8 };
+            let sideways = ((to - 64) % 3) as isize - 1;
+            let to = from as isize + forward + sideways;
+            if !(0..64).contains(&to) {
+                return Err(invalid());
+            }
+            Ok(TcnMove { from: from as Square, to: to as Square, promotion: Some(promotion) })
+        })
+        .collect()
+}
+
+/// Decodes TCN into UCI moves, e.g. `mC0K` into `["e2e4", "e7e5"]`. Castling comes out as the king's move.
+pub fn tcn_to_uci(tcn: &str) -> Result<Vec<String>> {
+    Ok(decode(tcn)?.iter().map(TcnMove::uci).collect())
+}
+
+/// Decodes TCN into SAN moves by playing them out from `start` (a FEN), or from the standard position if `None`.
+///
+/// ```rust
+/// assert_eq!(hikaru::tcn_to_uci("mC0Kgv")?, ["e2e4", "e7e5", "g1f3"]);
+/// assert_eq!(hikaru::tcn_to_san("mC0Kgv", None)?, ["e4", "e5", "Nf3"]);
+/// # Ok::<(), hikaru::Error>(())
+/// ```
+pub fn tcn_to_san(tcn: &str, start: Option<&str>) -> Result<Vec<String>> {
+    let mut board = match start {
+        Some(fen) => Board::from_fen(fen).ok_or_else(|| Error::InvalidFen(fen.into()))?,
+        None => Board::new(),
+    };
+    decode(tcn)?
+        .into_iter()
+        .map(|tcn_move| {
+            // Castling may be written as the king moving to its square or onto its own rook
+            let mv = board.legal_moves()
+                .into_iter()
+                .find(|mv| {
+                    mv.from == tcn_move.from
+                        && (mv.to == tcn_move.to || mv.castle == Some(tcn_move.to))
+                        && mv.promotion == tcn_move.promotion
+                })
+                .ok_or_else(|| Error::IllegalMove(tcn_move.uci()))?;
+            let san = board.san(&mv);
+            board.play(&mv);
+            Ok(san)
+        })
+        .collect()
+}
+
+impl GameData {
+    /// The moves in SAN, decoded from [GameData::tcn] rather than the PGN. `None` if the game has no TCN.
+    pub fn tcn_moves(&self) -> Option<Result<Vec<String>>> {
+        self.tcn.as_deref().map(|tcn| tcn_to_san(tcn, self.initial_setup.as_deref()))
+    }
+}
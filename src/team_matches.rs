@@ -0,0 +1,143 @@
+//! Team matches between clubs, and the individual boards they are played on.
+//!
+//! Matches are identified by the number at the end of their URL, e.g. `12803` for `https://www.chess.com/club/matches/12803`.
+//! Games played in a match link to it through [GameData::team_match], see [GameData::team_match_details].
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Client, GameData, Result};
+
+/// A team match, from `/pub/match/{id}`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TeamMatch {
+    pub name: String,
+    /// The match's page on Chess.com.
+    pub url: String,
+    pub description: Option<String>,
+    /// When the match started, as a Unix timestamp. `None` until it does.
+    pub start_time: Option<u32>,
+    /// When the match ended, as a Unix timestamp.
+    pub end_time: Option<u32>,
+    /// `"finished"`, `"in_progress"` or `"registration"`.
+    pub status: String,
+    /// How many boards the match is played on.
+    pub boards: u32,
+    pub settings: TeamMatchSettings,
+    pub teams: TeamMatchTeams,
+}
+
+/// How a [TeamMatch] is played.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TeamMatchSettings {
+    pub rules: String,
+    pub time_class: String,
+    pub time_control: String,
+    pub min_team_players: Option<u32>,
+    pub max_team_players: Option<u32>,
+    pub min_required_games: Option<u32>,
+    pub autostart: Option<bool>,
+}
+
+/// The two clubs in a [TeamMatch].
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TeamMatchTeams {
+    pub team1: TeamMatchTeam,
+    pub team2: TeamMatchTeam,
+}
+
+/// One club's side of a [TeamMatch].
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TeamMatchTeam {
+    /// The API URL of the club.
+    #[serde(rename = "@id")]
+    pub id: String,
+    pub name: String,
+    /// The club's page on Chess.com.
+    pub url: String,
+    /// Points scored so far.
+    pub score: Option<f32>,
+    /// `"win"`, `"lose"` or `"draw"` once the match is over.
+    pub result: Option<String>,
+    #[serde(default)]
+    pub players: Vec<TeamMatchPlayer>,
+}
+
+/// A player on one of the teams, and how their games on their board went.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TeamMatchPlayer {
+    pub username: String,
+    /// The API URL of the player's board.
+    pub board: Option<String>,
+    pub rating: Option<u32>,
+    pub timeout_percent: Option<f32>,
+    /// e.g. `"basic"`, `"premium"` or `"closed:fair_play_violations"`.
+    pub status: Option<String>,
+    /// How their game with White ended, e.g. `"win"` or `"checkmated"`. `None` while it's still going.
+    pub played_as_white: Option<String>,
+    /// How their game with Black ended.
+    pub played_as_black: Option<String>,
+}
+
+/// One board of a team match, from `/pub/match/{id}/{board}`. Each board is a pair of games, one with each colour.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TeamMatchBoard {
+    /// Points scored on the board, by username.
+    #[serde(default)]
+    pub board_scores: HashMap<String, f32>,
+    #[serde(default)]
+    pub games: Vec<TeamMatchGame>,
+}
+
+/// A game played on a [TeamMatchBoard]. Unlike archived games, it may still be in progress.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TeamMatchGame {
+    /// The game's page on Chess.com.
+    pub url: String,
+    pub white: TeamMatchGamePlayer,
+    pub black: TeamMatchGamePlayer,
+    /// The current position, or the final one once the game is over.
+    pub fen: Option<String>,
+    pub pgn: Option<String>,
+    pub start_time: Option<u32>,
+    pub end_time: Option<u32>,
+}
+
+/// One side of a [TeamMatchGame].
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TeamMatchGamePlayer {
+    pub username: String,
+    pub rating: Option<u32>,
+    /// How the game ended for them, e.g. `"win"` or `"timeout"`. `None` while it's still going.
+    pub result: Option<String>,
+}
+
+impl Client {
+    /// Downloads a team match's settings, teams and players.
+    pub fn team_match(&self, id: u64) -> Result<TeamMatch> {
+        self.get_json(&self.endpoint(&format!("match/{}", id)))
+    }
+
+    /// Downloads the games and scores of one board of a team match. Boards are numbered from 1.
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// let board = hikaru::Client::new().team_match_board(12803, 1)?;
+    /// for (username, score) in &board.board_scores {
+    ///     println!("{}: {}", username, score);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn team_match_board(&self, id: u64, board: u32) -> Result<TeamMatchBoard> {
+        self.get_json(&self.endpoint(&format!("match/{}/{}", id, board)))
+    }
+}
+
+impl GameData {
+    /// Downloads the team match the game was played in, or `None` if it wasn't part of one.
+    pub fn team_match_details(&self, client: &Client) -> Result<Option<TeamMatch>> {
+        self.team_match.as_deref().map(|url| client.get_json(&client.rebased(url))).transpose()
+    }
+}
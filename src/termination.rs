@@ -0,0 +1,66 @@
+//! How a game ended, independent of who won.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GameData, GameResult, GameResultWinLose};
+
+/// How the game ended, worked out from both players' [GameResult]s. Ordered as listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum Termination {
+    Checkmate,
+    Resignation,
+    /// Someone ran out of time, whether that lost the game or (against insufficient material) drew it.
+    Timeout,
+    Abandonment,
+    DrawAgreement,
+    Stalemate,
+    Repetition,
+    FiftyMove,
+    InsufficientMaterial,
+    /// A variant's own way of winning: reaching the hill, giving the third check, or a bughouse partner's game.
+    RuleSpecific,
+    /// A result code Hikaru doesn't know.
+    Unknown,
+}
+
+impl Termination {
+    /// The winner's result is always just `win`, so it's the other player's that says how the game ended. In a draw
+    /// both players have the same result.
+    ///
+    /// ```rust
+    /// use hikaru::{GameResult, Termination};
+    ///
+    /// assert_eq!(Termination::from_results(&GameResult::Resigned, &GameResult::Win), Termination::Resignation);
+    /// assert_eq!(Termination::from_results(&GameResult::Repetition, &GameResult::Repetition), Termination::Repetition);
+    /// ```
+    pub fn from_results(white: &GameResult, black: &GameResult) -> Termination {
+        use GameResult::*;
+        let result = if *white == Win { black } else { white };
+        match result {
+            CheckMated => Termination::Checkmate,
+            Resigned => Termination::Resignation,
+            TimeOut | TimeVsInsufficient => Termination::Timeout,
+            Abandoned => Termination::Abandonment,
+            Agreed => Termination::DrawAgreement,
+            StaleMate => Termination::Stalemate,
+            Repetition => Termination::Repetition,
+            FiftyMove => Termination::FiftyMove,
+            Insufficient => Termination::InsufficientMaterial,
+            KingOfTheHill | ThreeCheck | BugHousePartnerLose | BugHousePartnerWin => Termination::RuleSpecific,
+            Win | Other(_) => Termination::Unknown,
+        }
+    }
+}
+
+impl GameData {
+    /// Whether the player won because the opponent's clock ran out.
+    pub fn won_on_time(&self) -> bool {
+        self.result_win_lose == GameResultWinLose::Win && self.opponent_result == GameResult::TimeOut
+    }
+
+    /// Whether the player lost because their own clock ran out.
+    pub fn lost_on_time(&self) -> bool {
+        self.result == GameResult::TimeOut
+    }
+}
@@ -0,0 +1,110 @@
+//! Client-side rate limiting, and retrying requests that failed for reasons that might go away.
+//!
+//! Both only decide how long to wait; the blocking and async clients do the actual sleeping.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use std::time::Instant;
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+// `std::time::Instant` panics in the browser
+#[cfg(target_arch = "wasm32")]
+pub(crate) use web_time::Instant;
+
+/// Retries after a failed request, unless overridden with [ClientBuilder::max_retries](crate::ClientBuilder::max_retries).
+pub(crate) const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// The first retry waits this long, and each one after that twice as long as the previous.
+const BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// No single wait is longer than this, whatever `Retry-After` says.
+const MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Spaces requests out evenly, shared by every clone of a client and every thread using it.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    interval: Duration,
+    /// When the next request may go out. `None` once that is further off than an `Instant` can say, for absurdly low rates.
+    next: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    /// A limiter for a client to share, or `None` for rates that aren't a positive number, which mean no limit.
+    pub(crate) fn shared(requests_per_second: f64) -> Option<Arc<Self>> {
+        Some(requests_per_second).filter(|&rate| rate > 0.0).map(|rate| Arc::new(RateLimiter::new(rate)))
+    }
+
+    fn new(requests_per_second: f64) -> Self {
+        RateLimiter {
+            interval: Duration::try_from_secs_f64(1.0 / requests_per_second).unwrap_or(Duration::MAX),
+            next: Mutex::new(Some(Instant::now())),
+        }
+    }
+
+    /// Claims the next free slot, returning how long to wait until it comes up.
+    pub(crate) fn reserve(&self) -> Duration {
+        let mut next = self.next.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let now = Instant::now();
+        let slot = match *next {
+            Some(next) => next.max(now),
+            None => return Duration::MAX,
+        };
+        *next = slot.checked_add(self.interval);
+        slot - now
+    }
+}
+
+/// What happened to a request, as far as retrying is concerned.
+pub(crate) enum Outcome<'a> {
+    Response(StatusCode, &'a HeaderMap),
+    /// No response at all. Timeouts and failed connections are `transient`.
+    Failed { transient: bool },
+}
+
+impl Outcome<'_> {
+    /// A request reqwest couldn't complete. `fetch` doesn't say whether it failed to connect, so in the browser only
+    /// timeouts are retried.
+    pub(crate) fn failed(err: &reqwest::Error) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        let transient = err.is_timeout() || err.is_connect();
+        #[cfg(target_arch = "wasm32")]
+        let transient = err.is_timeout();
+        Outcome::Failed { transient }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RetryPolicy {
+    pub(crate) max_retries: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy { max_retries: DEFAULT_MAX_RETRIES }
+    }
+}
+
+impl RetryPolicy {
+    /// How long to wait before retrying, or `None` if the request shouldn't be retried. `attempt` counts from 0.
+    ///
+    /// Rate limiting (429), server errors (5xx), timeouts and failed connections are retried; anything else is final.
+    pub(crate) fn retry_delay(&self, attempt: u32, outcome: Outcome<'_>) -> Option<Duration> {
+        if attempt >= self.max_retries {
+            return None;
+        }
+        let backoff = BASE_DELAY.saturating_mul(2u32.saturating_pow(attempt)).min(MAX_DELAY);
+        match outcome {
+            Outcome::Response(status, headers) if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() => {
+                let retry_after = headers.get(RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.trim().parse().ok())
+                    .map(Duration::from_secs);
+                Some(retry_after.map_or(backoff, |retry_after| retry_after.min(MAX_DELAY)))
+            }
+            Outcome::Failed { transient: true } => Some(backoff),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! Chess.com's time control strings, e.g. `"600+5"` or `"1/259200"`.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::Error;
+
+/// How much time the players have.
+///
+/// ```rust
+/// use std::time::Duration;
+/// use hikaru::TimeControl;
+///
+/// let blitz: TimeControl = "180+2".parse()?;
+/// assert_eq!(blitz, TimeControl::Live { base: 180, increment: 2 });
+/// assert_eq!(blitz.estimated_duration(), Some(Duration::from_secs(2 * (180 + 40 * 2))));
+///
+/// let daily: TimeControl = "1/259200".parse()?;
+/// assert_eq!(daily, TimeControl::Daily { seconds_per_move: 259_200 });
+/// assert_eq!(daily.to_string(), "1/259200");
+/// # Ok::<(), hikaru::Error>(())
+/// ```
+/// Live time controls sort before daily ones, and then by base time and increment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TimeControl {
+    /// A clock for each player, starting at `base` seconds, with `increment` seconds added after every move.
+    Live { base: u32, increment: u32 },
+    /// Correspondence chess: up to `seconds_per_move` to make each move.
+    Daily { seconds_per_move: u32 },
+    /// No clock at all.
+    Unlimited,
+}
+
+/// Moves per player assumed by [TimeControl::estimated_duration], the same as lichess uses to sort games into speeds.
+const ESTIMATED_MOVES: u32 = 40;
+
+impl TimeControl {
+    /// Roughly how long a game lasts if both players use all of their time over 40 moves each. `None` for daily and
+    /// unlimited games, which take as long as they take.
+    pub fn estimated_duration(&self) -> Option<Duration> {
+        match self {
+            TimeControl::Live { base, increment } => {
+                Some(Duration::from_secs(2 * (u64::from(*base) + u64::from(ESTIMATED_MOVES) * u64::from(*increment))))
+            }
+            TimeControl::Daily { .. } | TimeControl::Unlimited => None,
+        }
+    }
+
+    pub fn is_daily(&self) -> bool {
+        matches!(self, TimeControl::Daily { .. })
+    }
+}
+
+impl FromStr for TimeControl {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Error> {
+        let invalid = || Error::InvalidTimeControl(text.into());
+        let text = text.trim();
+        if text == "-" || text.is_empty() {
+            return Ok(TimeControl::Unlimited);
+        }
+        if let Some((moves, seconds)) = text.split_once('/') {
+            // Chess.com always gives one move per period
+            if moves != "1" {
+                return Err(invalid());
+            }
+            let seconds_per_move = seconds.parse().map_err(|_| invalid())?;
+            return Ok(TimeControl::Daily { seconds_per_move });
+        }
+        let (base, increment) = text.split_once('+').unwrap_or((text, "0"));
+        Ok(TimeControl::Live {
+            base: base.parse().map_err(|_| invalid())?,
+            increment: increment.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// Formats the way Chess.com does, so parsing and formatting round-trip.
+impl fmt::Display for TimeControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeControl::Live { base, increment: 0 } => write!(f, "{}", base),
+            TimeControl::Live { base, increment } => write!(f, "{}+{}", base, increment),
+            TimeControl::Daily { seconds_per_move } => write!(f, "1/{}", seconds_per_move),
+            TimeControl::Unlimited => write!(f, "-"),
+        }
+    }
+}
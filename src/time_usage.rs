@@ -0,0 +1,81 @@
+//! How a player used their clock in a live game, from the PGN's `[%clk]` annotations.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Color, GameData, GameResult, TimeControl};
+
+/// Moves that took this long or less were almost certainly premoves.
+const PREMOVE: Duration = Duration::from_millis(100);
+
+/// One player's time over a live game. See [GameData::time_usage].
+///
+/// ```rust,no_run
+/// use hikaru::GameResultWinLose;
+///
+/// # fn main() -> hikaru::Result<()> {
+/// let games = hikaru::GameData::download(vec!["hikaru"])?;
+/// let losses = games.iter().filter(|game| game.result_win_lose == GameResultWinLose::Loss).count();
+/// let flagged = games.iter().filter_map(|game| game.time_usage()).filter(|usage| usage.flagged).count();
+/// println!("{} of {} losses were on time", flagged, losses);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeUsage {
+    /// How long each of the player's moves took, increments taken into account.
+    pub move_times: Vec<Duration>,
+    /// All the time the player spent on their moves.
+    pub time_spent_total: Duration,
+    pub avg_seconds_per_move: f32,
+    /// The least time the player had left after any of their moves.
+    pub min_clock_remaining: Duration,
+    /// Moves made in a tenth of a second or less, i.e. premoves.
+    pub premoves: u32,
+    /// Whether the player lost on time.
+    pub flagged: bool,
+}
+
+impl GameData {
+    /// How the player the games were downloaded for used their clock. `None` unless this is a live game whose PGN
+    /// has clock times.
+    pub fn time_usage(&self) -> Option<TimeUsage> {
+        let colour = if self.colour == "White" { Color::White } else { Color::Black };
+        self.time_usage_for(colour)
+    }
+
+    /// Like [GameData::time_usage], but for either player.
+    pub fn time_usage_for(&self, colour: Color) -> Option<TimeUsage> {
+        let (base, increment) = match self.time_control.parse().ok()? {
+            TimeControl::Live { base, increment } => (Duration::from_secs(base.into()), Duration::from_secs(increment.into())),
+            _ => return None,
+        };
+        let clocks = self.clock_times()?;
+        let clocks = clocks.for_color(colour);
+        let min_clock_remaining = clocks.iter().copied().min()?;
+
+        let mut previous = base;
+        let move_times: Vec<Duration> = clocks.iter()
+            .map(|&remaining| {
+                let spent = (previous + increment).saturating_sub(remaining);
+                previous = remaining;
+                spent
+            })
+            .collect();
+        let time_spent_total: Duration = move_times.iter().sum();
+        let flagged = match colour {
+            Color::White => self.white().result,
+            Color::Black => self.black().result,
+        } == &GameResult::TimeOut;
+
+        Some(TimeUsage {
+            avg_seconds_per_move: time_spent_total.as_secs_f32() / move_times.len() as f32,
+            premoves: move_times.iter().filter(|&&spent| spent <= PREMOVE).count() as u32,
+            move_times,
+            time_spent_total,
+            min_clock_remaining,
+            flagged,
+        })
+    }
+}
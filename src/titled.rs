@@ -0,0 +1,103 @@
+//! Players holding a FIDE title (verified by Chess.com), from `/pub/titled/{title}`.
+
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::{Client, Result};
+
+/// A chess title, as the API abbreviates it. Ordered as listed, i.e. the open titles before the women's of each rank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Title {
+    /// Grandmaster
+    GM,
+    /// Woman Grandmaster
+    WGM,
+    /// International Master
+    IM,
+    /// Woman International Master
+    WIM,
+    /// FIDE Master
+    FM,
+    /// Woman FIDE Master
+    WFM,
+    /// National Master
+    NM,
+    /// Woman National Master
+    WNM,
+    /// Candidate Master
+    CM,
+    /// Woman Candidate Master
+    WCM,
+}
+
+impl Title {
+    /// Every title the API knows about.
+    pub const ALL: [Title; 10] = [
+        Title::GM, Title::WGM, Title::IM, Title::WIM, Title::FM,
+        Title::WFM, Title::NM, Title::WNM, Title::CM, Title::WCM,
+    ];
+
+    /// The abbreviation, e.g. `"GM"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Title::GM => "GM",
+            Title::WGM => "WGM",
+            Title::IM => "IM",
+            Title::WIM => "WIM",
+            Title::FM => "FM",
+            Title::WFM => "WFM",
+            Title::NM => "NM",
+            Title::WNM => "WNM",
+            Title::CM => "CM",
+            Title::WCM => "WCM",
+        }
+    }
+
+    /// The title abbreviated `abbreviation`, e.g. [Title::GM] for `"GM"`.
+    pub(crate) fn from_abbreviation(abbreviation: &str) -> Option<Title> {
+        Title::ALL.iter().copied().find(|title| title.as_str() == abbreviation)
+    }
+}
+
+/// Reads an optional title, treating one the crate doesn't know (e.g. lichess's `LM` or `BOT`) as no title.
+pub(crate) fn known_title<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Option<Title>, D::Error> {
+    let abbreviation = Option::<String>::deserialize(deserializer)?;
+    Ok(abbreviation.as_deref().and_then(Title::from_abbreviation))
+}
+
+impl fmt::Display for Title {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The usernames of every player holding a title.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TitledPlayers {
+    pub players: Vec<String>,
+}
+
+impl TitledPlayers {
+    /// Downloads the players holding `title`, using a [Client] with the default settings.
+    ///
+    /// ```rust,no_run
+    /// use hikaru::{GameData, Title, TitledPlayers};
+    ///
+    /// # fn main() -> hikaru::Result<()> {
+    /// let grandmasters = TitledPlayers::download(Title::GM)?;
+    /// let games = GameData::download_many(grandmasters.players.iter().take(10))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn download(title: Title) -> Result<TitledPlayers> {
+        Client::new().titled_players(title)
+    }
+}
+
+impl Client {
+    pub fn titled_players(&self, title: Title) -> Result<TitledPlayers> {
+        self.get_json(&self.endpoint(&format!("titled/{}", title)))
+    }
+}
@@ -0,0 +1,147 @@
+//! Tournaments, their rounds, and the groups each round is split into.
+//!
+//! Tournaments are identified by the last part of their URL, e.g. `-33rd-chesscom-quick-knockouts-1401-1600`.
+//! Games played in a tournament link to it through [GameData::tournament], see [GameData::tournament_details].
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::path_id;
+use crate::{Client, GameData, Result};
+
+/// A tournament, from `/pub/tournament/{id}`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Tournament {
+    pub name: String,
+    /// The tournament's page on Chess.com.
+    pub url: String,
+    pub description: Option<String>,
+    /// The username of whoever created it.
+    pub creator: String,
+    /// `"finished"`, `"in_progress"` or `"registration"`.
+    pub status: String,
+    /// When the tournament ended, as a Unix timestamp.
+    pub finish_time: Option<u32>,
+    pub settings: TournamentSettings,
+    #[serde(default)]
+    pub players: Vec<TournamentPlayer>,
+    /// The API URLs of each round, in order.
+    #[serde(default)]
+    pub rounds: Vec<String>,
+}
+
+/// How a [Tournament] is run.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TournamentSettings {
+    /// e.g. `"swiss"` or `"round_robin"`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub rules: String,
+    pub time_class: String,
+    pub time_control: String,
+    pub is_rated: bool,
+    pub is_official: bool,
+    pub is_invite_only: bool,
+    pub initial_group_size: Option<u32>,
+    pub user_advance_count: Option<u32>,
+    pub use_tiebreak: Option<bool>,
+    pub allow_vacation: Option<bool>,
+    pub winner_places: Option<u32>,
+    pub registered_user_count: Option<u32>,
+    pub games_per_opponent: Option<u32>,
+    pub total_rounds: Option<u32>,
+    pub concurrent_games_per_opponent: Option<u32>,
+}
+
+/// A player registered for a [Tournament], and how it went for them overall.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TournamentPlayer {
+    pub username: String,
+    /// e.g. `"winner"`, `"eliminated"`, `"withdrew"` or `"registered"`.
+    pub status: String,
+}
+
+/// One round of a tournament, from `/pub/tournament/{id}/{round}`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TournamentRound {
+    /// The API URLs of the round's groups.
+    #[serde(default)]
+    pub groups: Vec<String>,
+    #[serde(default)]
+    pub players: Vec<TournamentRoundPlayer>,
+}
+
+/// A player in a [TournamentRound].
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TournamentRoundPlayer {
+    pub username: String,
+    /// Whether they go through to the next round. `None` while the round is still being played.
+    pub is_advancing: Option<bool>,
+}
+
+/// One group of a tournament round, from `/pub/tournament/{id}/{round}/{group}`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TournamentGroup {
+    /// The group's standings.
+    #[serde(default)]
+    pub players: Vec<TournamentStanding>,
+    /// Usernames of players removed from the group for fair play violations.
+    #[serde(default)]
+    pub fair_play_removals: Vec<String>,
+}
+
+/// A player's score within a [TournamentGroup].
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TournamentStanding {
+    pub username: String,
+    pub points: f32,
+    pub tie_break: f32,
+    pub is_advancing: Option<bool>,
+}
+
+impl Client {
+    /// Downloads a tournament's settings, players and list of rounds. The ID is the last part of the tournament's URL;
+    /// one with anything but letters, digits, hyphens and underscores is [Error::InvalidId](crate::Error::InvalidId).
+    pub fn tournament(&self, id: &str) -> Result<Tournament> {
+        self.get_json(&self.endpoint(&format!("tournament/{}", path_id(id)?)))
+    }
+
+    /// Downloads one round of a tournament. Rounds are numbered from 1.
+    pub fn tournament_round(&self, id: &str, round: u32) -> Result<TournamentRound> {
+        self.get_json(&self.endpoint(&format!("tournament/{}/{}", path_id(id)?, round)))
+    }
+
+    /// Downloads the standings of one group of a tournament round. Groups are numbered from 1.
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// let client = hikaru::Client::new();
+    /// let group = client.tournament_round_group("-33rd-chesscom-quick-knockouts-1401-1600", 1, 1)?;
+    /// for standing in &group.players {
+    ///     println!("{}: {}", standing.username, standing.points);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn tournament_round_group(&self, id: &str, round: u32, group: u32) -> Result<TournamentGroup> {
+        self.get_json(&self.endpoint(&format!("tournament/{}/{}/{}", path_id(id)?, round, group)))
+    }
+}
+
+impl GameData {
+    /// Downloads the tournament the game was played in, or `None` if it wasn't played in one.
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> hikaru::Result<()> {
+    /// let client = hikaru::Client::new();
+    /// for game in client.games("hikaru")? {
+    ///     if let Some(tournament) = game.tournament_details(&client)? {
+    ///         println!("{} ({} players)", tournament.name, tournament.players.len());
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn tournament_details(&self, client: &Client) -> Result<Option<Tournament>> {
+        self.tournament.as_deref().map(|url| client.get_json(&client.rebased(url))).transpose()
+    }
+}
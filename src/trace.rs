@@ -0,0 +1,13 @@
+//! Instrumentation for the `tracing` feature. Without the feature the macro expands to nothing, so call sites don't each
+//! need a `cfg`.
+
+/// Emits a `tracing` event, e.g. `event!(warn, url, attempt, "retrying request")`. The arguments are those of tracing's
+/// own level macros, and aren't evaluated at all without the feature.
+macro_rules! event {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!($($arg)+);
+    };
+}
+
+pub(crate) use event;
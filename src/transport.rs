@@ -0,0 +1,244 @@
+//! The HTTP layer under the blocking [Client](crate::Client), swappable so code built on Hikaru can be tested without
+//! the live API.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{Cursor, Read};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_LENGTH};
+use reqwest::StatusCode;
+
+use crate::Result;
+
+/// A response, with the body already read.
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// A `200 OK` with this body and no headers.
+    pub fn ok(body: impl Into<String>) -> Self {
+        HttpResponse { status: StatusCode::OK, headers: HeaderMap::new(), body: body.into() }
+    }
+
+    /// An empty response with this status.
+    pub fn status(status: StatusCode) -> Self {
+        HttpResponse { status, headers: HeaderMap::new(), body: String::new() }
+    }
+}
+
+/// A response whose body hasn't been read yet, from [HttpTransport::get_streaming].
+pub struct StreamingResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Box<dyn Read + Send>,
+}
+
+impl fmt::Debug for StreamingResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamingResponse").field("status", &self.status).field("headers", &self.headers).finish_non_exhaustive()
+    }
+}
+
+impl From<HttpResponse> for StreamingResponse {
+    fn from(response: HttpResponse) -> Self {
+        StreamingResponse {
+            status: response.status,
+            headers: response.headers,
+            body: Box::new(Cursor::new(response.body.into_bytes())),
+        }
+    }
+}
+
+/// Sends GET requests for a [Client](crate::Client). Set with [ClientBuilder::transport](crate::ClientBuilder::transport).
+///
+/// Error statuses should come back as responses rather than errors, so the client can retry them (429s and server errors)
+/// or turn them into the right [Error](crate::Error). Errors are for requests that got no response at all.
+pub trait HttpTransport: Send + Sync {
+    fn get(&self, url: &str, headers: &HeaderMap) -> Result<HttpResponse>;
+
+    /// Sends a HEAD request. By default this is a GET with the body thrown away, and a `Content-Length` header filled in
+    /// from it if the response didn't have one.
+    fn head(&self, url: &str, headers: &HeaderMap) -> Result<HttpResponse> {
+        let mut response = self.get(url, headers)?;
+        if !response.headers.contains_key(CONTENT_LENGTH) {
+            response.headers.insert(CONTENT_LENGTH, HeaderValue::from(response.body.len()));
+        }
+        response.body.clear();
+        Ok(response)
+    }
+
+    /// Sends a GET request, leaving the body to be read as it arrives. Monthly archives are parsed straight from it, so
+    /// they never have to be held in memory whole. By default this is a GET with the body read first.
+    fn get_streaming(&self, url: &str, headers: &HeaderMap) -> Result<StreamingResponse> {
+        Ok(self.get(url, headers)?.into())
+    }
+}
+
+impl<T: HttpTransport + ?Sized> HttpTransport for Arc<T> {
+    fn get(&self, url: &str, headers: &HeaderMap) -> Result<HttpResponse> {
+        (**self).get(url, headers)
+    }
+
+    fn head(&self, url: &str, headers: &HeaderMap) -> Result<HttpResponse> {
+        (**self).head(url, headers)
+    }
+
+    fn get_streaming(&self, url: &str, headers: &HeaderMap) -> Result<StreamingResponse> {
+        (**self).get_streaming(url, headers)
+    }
+}
+
+/// The default transport, a reqwest blocking client configured by the [ClientBuilder](crate::ClientBuilder). Not
+/// available in the browser.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: reqwest::blocking::Client,
+    timeout: Option<Duration>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ReqwestTransport {
+    pub fn new(client: reqwest::blocking::Client) -> Self {
+        ReqwestTransport { client, timeout: None }
+    }
+
+    /// Overrides the reqwest client's timeout for each request, e.g. for a download with its own
+    /// [timeout](crate::DownloadOptions::timeout).
+    pub(crate) fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    fn send(&self, request: reqwest::blocking::RequestBuilder, headers: &HeaderMap) -> Result<reqwest::blocking::Response> {
+        let request = request.headers(headers.clone());
+        let request = match self.timeout {
+            Some(timeout) => request.timeout(timeout),
+            None => request,
+        };
+        Ok(request.send()?)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl HttpTransport for ReqwestTransport {
+    fn get(&self, url: &str, headers: &HeaderMap) -> Result<HttpResponse> {
+        let response = self.send(self.client.get(url), headers)?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        Ok(HttpResponse { status, headers, body: response.text()? })
+    }
+
+    fn head(&self, url: &str, headers: &HeaderMap) -> Result<HttpResponse> {
+        let response = self.send(self.client.head(url), headers)?;
+        Ok(HttpResponse { status: response.status(), headers: response.headers().clone(), body: String::new() })
+    }
+
+    fn get_streaming(&self, url: &str, headers: &HeaderMap) -> Result<StreamingResponse> {
+        let response = self.send(self.client.get(url), headers)?;
+        Ok(StreamingResponse { status: response.status(), headers: response.headers().clone(), body: Box::new(response) })
+    }
+}
+
+/// Canned responses keyed by URL, for tests. Any other URL gets a `404 Not Found`, like an unknown player does.
+///
+/// ```rust
+/// use hikaru::{Client, MockTransport};
+///
+/// let transport = MockTransport::new()
+///     .with_json(
+///         "https://api.chess.com/pub/player/someone/games/archives",
+///         r#"{"archives": ["https://api.chess.com/pub/player/someone/games/2024/01"]}"#,
+///     )
+///     .with_json("https://api.chess.com/pub/player/someone/games/2024/01", r#"{"games": []}"#);
+/// let client = Client::builder().transport(transport).build()?;
+///
+/// assert!(client.games("someone")?.is_empty());
+/// assert!(matches!(client.games("nobody"), Err(hikaru::Error::UserNotFound(_))));
+/// # Ok::<(), hikaru::Error>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    responses: HashMap<String, HttpResponse>,
+    requests: Mutex<Vec<String>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answers requests for `url` with `response`.
+    pub fn with_response(mut self, url: impl Into<String>, response: HttpResponse) -> Self {
+        self.responses.insert(url.into(), response);
+        self
+    }
+
+    /// Answers requests for `url` with a `200 OK` and `body`.
+    pub fn with_json(self, url: impl Into<String>, body: impl Into<String>) -> Self {
+        self.with_response(url, HttpResponse::ok(body))
+    }
+
+    /// Every URL requested so far, in order. Hand the client an `Arc<MockTransport>` to keep a handle for checking these.
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+    }
+}
+
+impl HttpTransport for MockTransport {
+    fn get(&self, url: &str, _headers: &HeaderMap) -> Result<HttpResponse> {
+        self.requests.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(url.into());
+        Ok(self.responses.get(url).cloned().unwrap_or_else(|| HttpResponse::status(StatusCode::NOT_FOUND)))
+    }
+}
+
+/// The transport a [Client](crate::Client) sends requests through. Lets it stay `Debug` whatever the transport.
+#[derive(Clone)]
+pub(crate) struct Transport {
+    http: Arc<dyn HttpTransport>,
+    /// The default transport, kept so a download can change its timeout.
+    #[cfg(not(target_arch = "wasm32"))]
+    reqwest: Option<ReqwestTransport>,
+}
+
+impl Transport {
+    pub(crate) fn custom(transport: impl HttpTransport + 'static) -> Self {
+        Transport {
+            http: Arc::new(transport),
+            #[cfg(not(target_arch = "wasm32"))]
+            reqwest: None,
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn reqwest(transport: ReqwestTransport) -> Self {
+        Transport { http: Arc::new(transport.clone()), reqwest: Some(transport) }
+    }
+
+    pub(crate) fn http(&self) -> &dyn HttpTransport {
+        &*self.http
+    }
+
+    /// The same transport with a different timeout. Custom transports handle timeouts themselves (if at all), so they
+    /// are left as they are.
+    pub(crate) fn with_timeout(&self, timeout: Duration) -> Transport {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(reqwest) = &self.reqwest {
+            return Transport::reqwest(reqwest.clone().with_timeout(timeout));
+        }
+        let _ = timeout;
+        self.clone()
+    }
+}
+
+impl fmt::Debug for Transport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Transport")
+    }
+}
@@ -0,0 +1,87 @@
+//! Chess.com usernames, checked before they go into a URL.
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Result};
+
+/// A Chess.com username: 3 to 25 letters, digits, underscores or hyphens. Usernames aren't case-sensitive, so they
+/// are kept lowercase, the way the API spells them in URLs.
+///
+/// Every method that takes a username checks it this way, so a typo like `"hikaru nakamura"` fails with
+/// [Error::InvalidUsername] instead of requesting some other URL.
+///
+/// ```rust
+/// use hikaru::Username;
+///
+/// let user: Username = "Hikaru".parse()?;
+/// assert_eq!(user.as_str(), "hikaru");
+/// assert!("not/a/user".parse::<Username>().is_err());
+/// # Ok::<(), hikaru::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Username(String);
+
+impl Username {
+    pub fn new(username: &str) -> Result<Self> {
+        let username = username.trim();
+        let valid = (3..=25).contains(&username.len())
+            && username.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if valid {
+            Ok(Username(username.to_ascii_lowercase()))
+        } else {
+            Err(Error::InvalidUsername(username.into()))
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for Username {
+    type Err = Error;
+
+    fn from_str(username: &str) -> Result<Self> {
+        Username::new(username)
+    }
+}
+
+impl TryFrom<String> for Username {
+    type Error = Error;
+
+    fn try_from(username: String) -> Result<Self> {
+        Username::new(&username)
+    }
+}
+
+impl From<Username> for String {
+    fn from(username: Username) -> Self {
+        username.0
+    }
+}
+
+impl Deref for Username {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Username {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Username {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
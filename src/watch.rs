@@ -0,0 +1,150 @@
+//! Polling for games as they finish, for bots and notification tools that want to react to the end of a game.
+
+use std::collections::{HashSet, VecDeque};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use reqwest::StatusCode;
+
+use crate::date::{self, year_month};
+use crate::{ArchivedGame, Client, Error, GameData, Games, Result, Username};
+
+/// An endless iterator over a user's games as they finish, from [Client::watch].
+///
+/// The API has no push notifications, so every `interval` the monthly archive the user is currently playing in is
+/// downloaded again (the archive for the month before too, right after the month changes) and any game that wasn't
+/// there before is yielded, oldest first. `next` blocks until there is one.
+///
+/// A poll that fails is yielded as an `Err`, and the next poll happens as usual, so errors can be logged and skipped.
+/// A game that can't be read is yielded once as an [Error::GameParse], and the other games still come through.
+#[derive(Debug)]
+pub struct Watch {
+    client: Client,
+    username: Username,
+    /// As passed to [Client::watch], for [GameData::player_username].
+    user: String,
+    interval: Duration,
+    /// The end time of the latest game seen, and the URLs of the games that ended then.
+    last_end_time: u32,
+    last_games: HashSet<String>,
+    /// The games already reported as unreadable, by URL (or archive and position, without one).
+    invalid_games: HashSet<String>,
+    pending: VecDeque<Result<GameData>>,
+    next_poll: Instant,
+}
+
+impl Client {
+    /// Watches for the user's games that finish from now on, checking every `interval`. Keep it to a minute or so;
+    /// archives are only updated every few minutes anyway.
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> hikaru::Result<()> {
+    /// for game in hikaru::Client::new().watch("hikaru", Duration::from_secs(60))? {
+    ///     match game {
+    ///         Ok(game) => println!("{} {:?} against {}", game.game_url, game.result, game.opponent_username),
+    ///         Err(err) => eprintln!("couldn't check for new games: {}", err),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn watch(&self, user: &str, interval: Duration) -> Result<Watch> {
+        self.watch_since(user, interval, date::now() as u32)
+    }
+
+    /// Like [Client::watch], but also yields the games that ended after `end_time` (a Unix timestamp) on the first poll,
+    /// e.g. those finished since a bot last ran.
+    pub fn watch_since(&self, user: &str, interval: Duration, end_time: u32) -> Result<Watch> {
+        Ok(Watch {
+            client: self.clone(),
+            username: Username::new(user)?,
+            user: user.into(),
+            interval,
+            last_end_time: end_time,
+            last_games: HashSet::new(),
+            invalid_games: HashSet::new(),
+            pending: VecDeque::new(),
+            next_poll: Instant::now(),
+        })
+    }
+}
+
+impl Watch {
+    /// The archive URLs from the month of the last game seen up to the current one.
+    fn archive_urls(&self) -> Vec<String> {
+        let (mut year, mut month) = year_month(self.last_end_time.into());
+        let current = year_month(date::now());
+        let mut urls = vec![];
+        while (year, month) <= current {
+            urls.push(self.client.endpoint(&format!("player/{}/games/{}/{:02}", self.username, year, month)));
+            if month == 12 {
+                year += 1;
+                month = 1;
+            }
+            else {
+                month += 1;
+            }
+        }
+        urls
+    }
+
+    fn poll(&mut self) -> Result<()> {
+        let mut games: Vec<ArchivedGame> = vec![];
+        for url in self.archive_urls() {
+            let text = match self.client.get_text(&url) {
+                Ok(text) => text,
+                // No archive yet for a month without games
+                Err(Error::Status { status: StatusCode::NOT_FOUND, .. }) => continue,
+                Err(err) => return Err(err),
+            };
+            for game in Games::parse(&url, &text)? {
+                let game = match game {
+                    Ok(game) => game,
+                    Err(err) => {
+                        let key = err.game_url.clone().unwrap_or_else(|| format!("{}#{}", err.archive, err.index));
+                        if self.invalid_games.insert(key) {
+                            self.pending.push_back(Err(Error::GameParse(err)));
+                        }
+                        continue;
+                    }
+                };
+                let is_new = game.end_time > self.last_end_time
+                    || (game.end_time == self.last_end_time && !self.last_games.contains(&game.game_url));
+                if is_new {
+                    games.push(game);
+                }
+            }
+        }
+        games.sort_by_key(|game| game.end_time);
+
+        if let Some(latest) = games.last().map(|game| game.end_time) {
+            if latest > self.last_end_time {
+                self.last_end_time = latest;
+                self.last_games.clear();
+            }
+            self.last_games.extend(games.iter().filter(|game| game.end_time == latest).map(|game| game.game_url.clone()));
+        }
+        let user = &self.user;
+        self.pending.extend(games.into_iter().map(|game| Ok(GameData::from((game, user.as_str())))));
+        Ok(())
+    }
+}
+
+impl Iterator for Watch {
+    type Item = Result<GameData>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(game) = self.pending.pop_front() {
+                return Some(game);
+            }
+            thread::sleep(self.next_poll.saturating_duration_since(Instant::now()));
+            self.next_poll = Instant::now() + self.interval;
+            if let Err(err) = self.poll() {
+                return Some(Err(err));
+            }
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! How each of Chess.com's result codes comes out as a win, loss or draw, read from a monthly archive in the API's format.
+//!
+//! `fixtures/result-codes-2024-01.json` has one game for each code. `hikaru` is always the player the code belongs to,
+//! alternating between White and Black; their opponent has `win` when the code is a loss, or the same code when it's a
+//! draw.
+
+use std::collections::BTreeMap;
+
+use hikaru::{GameData, GameResult, GameResultWinLose};
+
+const ARCHIVE: &str = include_str!("fixtures/result-codes-2024-01.json");
+
+const LOSSES: [&str; 7] =
+    ["checkmated", "resigned", "timeout", "abandoned", "kingofthehill", "threecheck", "bughousepartnerlose"];
+const DRAWS: [&str; 6] = ["stalemate", "agreed", "repetition", "insufficient", "50move", "timevsinsufficient"];
+
+/// The archive's games from `user`'s side, by the code `hikaru` got.
+fn games(user: &str) -> BTreeMap<String, GameData> {
+    let mut games = vec![];
+    let count = GameData::read_archive(ARCHIVE.as_bytes(), user, |game| games.push(game.expect("every game parses")))
+        .expect("the archive parses");
+    assert_eq!(count, LOSSES.len() + DRAWS.len());
+    games.into_iter()
+        .map(|game| {
+            let code = if user == "hikaru" { &game.result } else { &game.opponent_result };
+            (code.as_str().to_string(), game)
+        })
+        .collect()
+}
+
+#[test]
+fn every_code_is_known() {
+    for (code, game) in games("hikaru") {
+        assert!(!matches!(game.result, GameResult::Other(_)), "{} was read as an unknown code", code);
+        assert_eq!(GameResult::from(code.clone()).as_str(), code);
+    }
+}
+
+#[test]
+fn losing_codes_are_losses() {
+    let games = games("hikaru");
+    for code in LOSSES {
+        let game = &games[code];
+        assert_eq!(game.result_win_lose, GameResultWinLose::Loss, "{}", code);
+        assert_eq!(GameResultWinLose::from(game.result.clone()), GameResultWinLose::Loss, "{}", code);
+        assert_eq!(GameResultWinLose::from_results(&game.result, &game.opponent_result), GameResultWinLose::Loss, "{}", code);
+        assert_eq!(game.win, 0.0, "{}", code);
+    }
+}
+
+#[test]
+fn drawing_codes_are_draws() {
+    let games = games("hikaru");
+    for code in DRAWS {
+        let game = &games[code];
+        assert_eq!(game.result, game.opponent_result, "{}", code);
+        assert_eq!(game.result_win_lose, GameResultWinLose::Draw, "{}", code);
+        assert_eq!(GameResultWinLose::from(game.result.clone()), GameResultWinLose::Draw, "{}", code);
+        assert_eq!(GameResultWinLose::from_results(&game.result, &game.opponent_result), GameResultWinLose::Draw, "{}", code);
+        assert_eq!(game.win, 0.5, "{}", code);
+    }
+}
+
+#[test]
+fn the_other_side_of_a_loss_is_a_win() {
+    let games = games("magnuscarlsen");
+    for code in LOSSES {
+        let game = &games[code];
+        assert!(matches!(game.result, GameResult::Win | GameResult::BugHousePartnerWin), "{}: {:?}", code, game.result);
+        assert_eq!(game.result_win_lose, GameResultWinLose::Win, "{}", code);
+        assert_eq!(GameResultWinLose::from_results(&game.result, &game.opponent_result), GameResultWinLose::Win, "{}", code);
+        assert_eq!(game.win, 1.0, "{}", code);
+    }
+    for code in DRAWS {
+        assert_eq!(games[code].result_win_lose, GameResultWinLose::Draw, "{}", code);
+    }
+}
+
+#[test]
+fn both_colours_are_covered() {
+    let games = games("hikaru");
+    for colour in ["White", "Black"] {
+        assert!(games.values().any(|game| game.colour == colour));
+    }
+    assert_eq!(games["50move"].standard_result(), "1/2-1/2");
+    assert_eq!(games["checkmated"].standard_result(), "0-1");
+    assert_eq!(games["threecheck"].standard_result(), "1-0");
+}